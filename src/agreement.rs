@@ -0,0 +1,390 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    distributions::f::F,
+    numerical::{sum_of_squared_errors, sum_of_squares_between_groups, NumericalSample},
+    special::{normal_cdf, normal_quantile},
+    Alternative, TestResult,
+};
+
+/// Agreement credited to a pair of different ordinal categories in [`cohens_kappa`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum KappaWeights {
+    /// Only identical categories agree
+    Unweighted,
+    /// `1 - |i - j| / (k - 1)`
+    Linear,
+    /// `1 - (i - j)^2 / (k - 1)^2`
+    Quadratic,
+}
+impl KappaWeights {
+    fn weight(&self, i: usize, j: usize, k: usize) -> f64 {
+        let distance = i.abs_diff(j) as f64 / (k - 1) as f64;
+        match self {
+            KappaWeights::Unweighted => (i == j) as u8 as f64,
+            KappaWeights::Linear => 1. - distance,
+            KappaWeights::Quadratic => 1. - distance.powi(2),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kappa {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub kappa: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub standard_error: PositiveF64,
+    /// `z` of kappa against its standard error under the null hypothesis
+    ///
+    /// Null hypothesis: the agreement is no better than chance.
+    pub test: TestResult,
+}
+
+/// Cohen's kappa of two raters, optionally weighted for ordinal categories.
+///
+/// - `matrix[i][j]`: count of subjects the first rater put in category `i` and the second in category `j`
+/// - The standard error is the large-sample one, which gives the confidence interval of kappa in `test`; the test itself uses the standard error under the null hypothesis, which stays positive under perfect agreement.
+///
+/// ref: Fleiss, J. L., Cohen, J., & Everitt, B. S. (1969). Large sample standard errors of kappa and weighted kappa.
+pub fn cohens_kappa(
+    matrix: &[Vec<usize>],
+    weights: KappaWeights,
+    confidence: NormalizedF64,
+) -> Kappa {
+    let k = matrix.len();
+    assert!(k >= 2);
+    matrix.iter().for_each(|row| assert_eq!(row.len(), k));
+    let n = matrix.iter().flatten().sum::<usize>() as f64;
+    assert!(n > 0.);
+    let p = |i: usize, j: usize| matrix[i][j] as f64 / n;
+    let w = |i: usize, j: usize| weights.weight(i, j, k);
+    let rows = (0..k)
+        .map(|i| (0..k).map(|j| p(i, j)).sum())
+        .collect::<Vec<f64>>();
+    let cols = (0..k)
+        .map(|j| (0..k).map(|i| p(i, j)).sum())
+        .collect::<Vec<f64>>();
+
+    let cells = || (0..k).flat_map(|i| (0..k).map(move |j| (i, j)));
+    let observed = cells().map(|(i, j)| w(i, j) * p(i, j)).sum::<f64>();
+    let expected = cells()
+        .map(|(i, j)| w(i, j) * rows[i] * cols[j])
+        .sum::<f64>();
+    assert!(expected < 1.);
+    let kappa = (observed - expected) / (1. - expected);
+
+    let row_weights = (0..k)
+        .map(|i| (0..k).map(|j| cols[j] * w(i, j)).sum())
+        .collect::<Vec<f64>>();
+    let col_weights = (0..k)
+        .map(|j| (0..k).map(|i| rows[i] * w(i, j)).sum())
+        .collect::<Vec<f64>>();
+    let sum = cells()
+        .map(|(i, j)| {
+            p(i, j) * (w(i, j) - (row_weights[i] + col_weights[j]) * (1. - kappa)).powi(2)
+        })
+        .sum::<f64>();
+    let variance =
+        (sum - (kappa - expected * (1. - kappa)).powi(2)) / (n * (1. - expected).powi(2));
+    let standard_error = variance.max(0.).sqrt();
+    let null_sum = cells()
+        .map(|(i, j)| rows[i] * cols[j] * (w(i, j) - (row_weights[i] + col_weights[j])).powi(2))
+        .sum::<f64>();
+    let null_variance = (null_sum - expected.powi(2)) / (n * (1. - expected).powi(2));
+
+    let margin = normal_quantile(0.5 + confidence.get() / 2.) * standard_error;
+    let mut test = z_test(kappa, null_variance.max(0.).sqrt());
+    test.confidence_interval = Some((
+        FiniteF64::new(kappa - margin).unwrap(),
+        FiniteF64::new(kappa + margin).unwrap(),
+    ));
+    Kappa {
+        kappa: FiniteF64::new(kappa).unwrap(),
+        standard_error: PositiveF64::new(standard_error).unwrap(),
+        test,
+    }
+}
+
+/// Fleiss' kappa of a fixed number of raters per subject.
+///
+/// - `ratings[i][j]`: count of raters who put subject `i` in category `j`; every subject has the same count of raters
+/// - The standard error is the one under the null hypothesis, so there is no confidence interval.
+///
+/// ref: Fleiss, J. L., Nee, J. C. M., & Landis, J. R. (1979). Large sample variance of kappa in the case of different sets of raters.
+pub fn fleiss_kappa(ratings: &[Vec<usize>]) -> Kappa {
+    let subjects = ratings.len();
+    assert!(subjects >= 2);
+    let k = ratings[0].len();
+    assert!(k >= 2);
+    let raters = ratings[0].iter().sum::<usize>();
+    assert!(raters >= 2);
+    ratings.iter().for_each(|row| {
+        assert_eq!(row.len(), k);
+        assert_eq!(row.iter().sum::<usize>(), raters);
+    });
+    let (subjects, raters) = (subjects as f64, raters as f64);
+
+    let proportions = (0..k)
+        .map(|j| ratings.iter().map(|row| row[j]).sum::<usize>() as f64 / (subjects * raters))
+        .collect::<Vec<_>>();
+    let observed = ratings
+        .iter()
+        .map(|row| {
+            let pairs = row.iter().map(|&x| (x * x) as f64).sum::<f64>() - raters;
+            pairs / (raters * (raters - 1.))
+        })
+        .sum::<f64>()
+        / subjects;
+    let expected = proportions.iter().map(|p| p.powi(2)).sum::<f64>();
+    assert!(expected < 1.);
+    let kappa = (observed - expected) / (1. - expected);
+
+    let pq = proportions.iter().map(|p| p * (1. - p)).sum::<f64>();
+    let skew = proportions
+        .iter()
+        .map(|p| p * (1. - p) * (1. - 2. * p))
+        .sum::<f64>();
+    let standard_error = 2_f64.sqrt() / (pq * (subjects * raters * (raters - 1.)).sqrt())
+        * (pq.powi(2) - skew).sqrt();
+    Kappa {
+        kappa: FiniteF64::new(kappa).unwrap(),
+        standard_error: PositiveF64::new(standard_error).unwrap(),
+        test: z_test(kappa, standard_error),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CronbachAlpha {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub alpha: FiniteF64,
+    /// Feldt's interval from the F distribution
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub confidence_interval: (FiniteF64, FiniteF64),
+}
+
+/// Cronbach's alpha of the internal consistency of a scale.
+///
+/// - `scores[i][j]`: score of respondent `i` on item `j`
+///
+/// ref: Feldt, L. S., Woodruff, D. J., & Salih, F. A. (1987). Statistical inference for coefficient alpha.
+pub fn cronbach_alpha(scores: &[Vec<f64>], confidence: NormalizedF64) -> CronbachAlpha {
+    let anova = TwoWayAnova::new(scores);
+    let alpha = 1. - anova.ms_error / anova.ms_rows;
+
+    // `(1 - alpha) / (1 - alpha_hat)` follows `F(n - 1, (n - 1)(k - 1))`
+    let (n, k) = (anova.rows as f64, anova.cols as f64);
+    let f = F {
+        df_1: PositiveF64::new(n - 1.).unwrap(),
+        df_2: PositiveF64::new((n - 1.) * (k - 1.)).unwrap(),
+    };
+    let tail = (1. - confidence.get()) / 2.;
+    let bound = |p: f64| 1. - (1. - alpha) * f.quantile(NormalizedF64::new(p).unwrap()).get();
+    CronbachAlpha {
+        alpha: FiniteF64::new(alpha).unwrap(),
+        confidence_interval: (
+            FiniteF64::new(bound(1. - tail)).unwrap(),
+            FiniteF64::new(bound(tail)).unwrap(),
+        ),
+    }
+}
+
+/// Single-rater intraclass correlations in the notation of Shrout and Fleiss
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntraclassCorrelation {
+    /// ICC(1,1): each subject is rated by a different random set of raters
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub one_way_random: FiniteF64,
+    /// ICC(2,1): absolute agreement of raters randomly drawn from a population
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub two_way_random: FiniteF64,
+    /// ICC(3,1): consistency of a fixed set of raters
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub two_way_mixed: FiniteF64,
+}
+
+/// Intraclass correlations from the two-way ANOVA of subjects by raters.
+///
+/// - `scores[i][j]`: score of subject `i` from rater `j`
+///
+/// ref: Shrout, P. E., & Fleiss, J. L. (1979). Intraclass correlations: uses in assessing rater reliability.
+pub fn intraclass_correlation(scores: &[Vec<f64>]) -> IntraclassCorrelation {
+    let anova = TwoWayAnova::new(scores);
+    let (n, k) = (anova.rows as f64, anova.cols as f64);
+    let TwoWayAnova {
+        ms_rows,
+        ms_cols,
+        ms_error,
+        ..
+    } = anova;
+    let ms_within = anova.ms_within();
+
+    let one_way_random = (ms_rows - ms_within) / (ms_rows + (k - 1.) * ms_within);
+    let two_way_random =
+        (ms_rows - ms_error) / (ms_rows + (k - 1.) * ms_error + k * (ms_cols - ms_error) / n);
+    let two_way_mixed = (ms_rows - ms_error) / (ms_rows + (k - 1.) * ms_error);
+    IntraclassCorrelation {
+        one_way_random: FiniteF64::new(one_way_random).unwrap(),
+        two_way_random: FiniteF64::new(two_way_random).unwrap(),
+        two_way_mixed: FiniteF64::new(two_way_mixed).unwrap(),
+    }
+}
+
+/// Mean squares of a two-way ANOVA without replication
+struct TwoWayAnova {
+    rows: usize,
+    cols: usize,
+    ms_rows: f64,
+    ms_cols: f64,
+    ms_error: f64,
+}
+impl TwoWayAnova {
+    fn new(scores: &[Vec<f64>]) -> Self {
+        let rows = scores.len();
+        assert!(rows >= 2);
+        let cols = scores[0].len();
+        assert!(cols >= 2);
+        scores.iter().for_each(|row| assert_eq!(row.len(), cols));
+
+        let row_samples = scores
+            .iter()
+            .map(|row| NumericalSample::from_values(row))
+            .collect::<Vec<_>>();
+        let col_samples = (0..cols)
+            .map(|j| {
+                NumericalSample::from_values(&scores.iter().map(|row| row[j]).collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        let total = rows * cols;
+        let ss_rows = sum_of_squares_between_groups(&row_samples, total);
+        let ss_cols = sum_of_squares_between_groups(&col_samples, total);
+        // Within subjects, the variation splits into the raters and the residual
+        let ss_error = sum_of_squared_errors(&row_samples) - ss_cols;
+
+        let (n, k) = (rows as f64, cols as f64);
+        Self {
+            rows,
+            cols,
+            ms_rows: ss_rows / (n - 1.),
+            ms_cols: ss_cols / (k - 1.),
+            ms_error: ss_error.max(0.) / ((n - 1.) * (k - 1.)),
+        }
+    }
+
+    /// Mean square within subjects of the one-way ANOVA
+    fn ms_within(&self) -> f64 {
+        let (n, k) = (self.rows as f64, self.cols as f64);
+        (self.ms_cols * (k - 1.) + self.ms_error * (n - 1.) * (k - 1.)) / (n * (k - 1.))
+    }
+}
+
+fn z_test(kappa: f64, standard_error: f64) -> TestResult {
+    let z = if kappa == 0. {
+        0.
+    } else {
+        kappa / standard_error
+    };
+    TestResult {
+        statistic: FiniteF64::new(z.clamp(f64::MIN, f64::MAX)).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(2. * normal_cdf(-z.abs())).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cohens_kappa() {
+        let confidence = NormalizedF64::new(0.95).unwrap();
+        let matrix = [vec![20, 5], vec![10, 15]];
+        let kappa = cohens_kappa(&matrix, KappaWeights::Unweighted, confidence);
+        assert!((kappa.kappa.get() - 0.4).abs() < 1e-12);
+        assert!((kappa.standard_error.get() - 0.126_996_063).abs() < 1e-9);
+        let (lower, upper) = kappa.test.confidence_interval.unwrap();
+        assert!((lower.get() - (0.4 - 1.959_964 * 0.126_996_063)).abs() < 1e-6);
+        assert!((upper.get() - (0.4 + 1.959_964 * 0.126_996_063)).abs() < 1e-6);
+        // Against the null standard error `sqrt(0.0192)`
+        assert!((kappa.test.statistic.get() - 0.4 / 0.0192_f64.sqrt()).abs() < 1e-9);
+
+        let matrix = [vec![22, 5, 1], vec![4, 18, 6], vec![2, 3, 19]];
+        let kappa = cohens_kappa(&matrix, KappaWeights::Unweighted, confidence);
+        assert!((kappa.kappa.get() - 0.606_003_752).abs() < 1e-9);
+        assert!((kappa.standard_error.get() - 0.073_783_289).abs() < 1e-9);
+        let kappa = cohens_kappa(&matrix, KappaWeights::Linear, confidence);
+        assert!((kappa.kappa.get() - 0.661_016_949).abs() < 1e-9);
+        assert!((kappa.standard_error.get() - 0.069_015_826).abs() < 1e-9);
+        let kappa = cohens_kappa(&matrix, KappaWeights::Quadratic, confidence);
+        assert!((kappa.kappa.get() - 0.716_446_125).abs() < 1e-9);
+        assert!((kappa.standard_error.get() - 0.071_843_588).abs() < 1e-9);
+
+        // Perfect agreement
+        let kappa = cohens_kappa(
+            &[vec![10, 0], vec![0, 10]],
+            KappaWeights::Unweighted,
+            confidence,
+        );
+        assert_eq!(kappa.kappa.get(), 1.);
+        assert_eq!(kappa.standard_error.get(), 0.);
+        assert!((kappa.test.statistic.get() - 20_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fleiss_kappa() {
+        let ratings = [
+            [0, 0, 0, 0, 14],
+            [0, 2, 6, 4, 2],
+            [0, 0, 3, 5, 6],
+            [0, 3, 9, 2, 0],
+            [2, 2, 8, 1, 1],
+            [7, 7, 0, 0, 0],
+            [3, 2, 6, 3, 0],
+            [2, 5, 3, 2, 2],
+            [6, 5, 2, 1, 0],
+            [0, 2, 2, 3, 7],
+        ]
+        .map(|row| row.to_vec());
+        let kappa = fleiss_kappa(&ratings);
+        assert!((kappa.kappa.get() - 0.209_930_704).abs() < 1e-9);
+        assert!((kappa.standard_error.get() - 0.016_965_069).abs() < 1e-9);
+        assert!(kappa.test.p_value.get() < 1e-9);
+    }
+
+    /// Shrout and Fleiss (1979), Table 2
+    fn shrout_fleiss() -> Vec<Vec<f64>> {
+        [
+            [9., 2., 5., 8.],
+            [6., 1., 3., 2.],
+            [8., 4., 6., 8.],
+            [7., 1., 2., 6.],
+            [10., 5., 6., 9.],
+            [6., 2., 4., 7.],
+        ]
+        .map(|row| row.to_vec())
+        .to_vec()
+    }
+
+    #[test]
+    fn test_cronbach_alpha() {
+        let alpha = cronbach_alpha(&shrout_fleiss(), NormalizedF64::new(0.95).unwrap());
+        assert!((alpha.alpha.get() - 0.909_315_542).abs() < 1e-9);
+        let (lower, upper) = alpha.confidence_interval;
+        assert!((lower.get() - 0.675_674_714).abs() < 1e-6);
+        assert!((upper.get() - 0.985_891_678).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_intraclass_correlation() {
+        let icc = intraclass_correlation(&shrout_fleiss());
+        assert!((icc.one_way_random.get() - 0.165_741_768).abs() < 1e-9);
+        assert!((icc.two_way_random.get() - 0.289_763_780).abs() < 1e-9);
+        assert!((icc.two_way_mixed.get() - 0.714_840_715).abs() < 1e-9);
+    }
+}
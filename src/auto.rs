@@ -0,0 +1,152 @@
+#[cfg(feature = "rand")]
+use strict_num::FiniteF64;
+use strict_num::NormalizedF64;
+
+#[cfg(feature = "rand")]
+use crate::numerical::{difference_of_two_means_result, NumericalSample};
+use crate::{
+    categorical::{
+        binomial_test_result, fisher_exact_result, one_proportion_result,
+        two_way_table_independence_result, CountAndProportion,
+    },
+    TestResult,
+};
+
+/// Smallest count of each sample for the t-test to lean on the central limit theorem
+#[cfg(feature = "rand")]
+pub const MIN_COUNT_FOR_T_TEST: usize = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Method {
+    ZTest,
+    ExactBinomial,
+    ChiSquare,
+    FisherExact,
+    TTest,
+    Permutation,
+}
+
+/// Result of a test that checks its large-sample conditions and falls back to an exact or a resampling test when they fail
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AutoResult {
+    /// The test actually run
+    pub method: Method,
+    pub result: TestResult,
+}
+
+/// [`one_proportion_result`] if the normal approximation holds, otherwise [`binomial_test_result`]
+pub fn one_proportion(successes: usize, trials: usize, p_0: NormalizedF64) -> AutoResult {
+    assert!(trials > 0);
+    let sample = CountAndProportion {
+        count: trials,
+        proportion: NormalizedF64::new(successes as f64 / trials as f64).unwrap(),
+        population: None,
+    };
+    if sample.is_normally_distributed_enough() {
+        AutoResult {
+            method: Method::ZTest,
+            result: one_proportion_result(sample, p_0),
+        }
+    } else {
+        AutoResult {
+            method: Method::ExactBinomial,
+            result: binomial_test_result(successes, trials, p_0),
+        }
+    }
+}
+
+/// [`two_way_table_independence_result`] if every expected count is at least 5, otherwise [`fisher_exact_result`]
+pub fn two_by_two(table: [[usize; 2]; 2]) -> AutoResult {
+    let total = table.iter().flatten().sum::<usize>();
+    let row = |r: usize| table[r].iter().sum::<usize>();
+    let col = |c: usize| table.iter().map(|row| row[c]).sum::<usize>();
+    let large_enough =
+        total > 0 && (0..2).all(|r| (0..2).all(|c| (row(r) * col(c)) as f64 / total as f64 >= 5.));
+    if large_enough {
+        AutoResult {
+            method: Method::ChiSquare,
+            result: two_way_table_independence_result(&table),
+        }
+    } else {
+        AutoResult {
+            method: Method::FisherExact,
+            result: fisher_exact_result(table),
+        }
+    }
+}
+
+/// [`difference_of_two_means_result`] if both samples have at least [`MIN_COUNT_FOR_T_TEST`] observations, otherwise a permutation test of the difference of means with `n_perms` regroupings
+#[cfg(feature = "rand")]
+pub fn two_means(x: &[f64], y: &[f64], n_perms: usize, seed: u64) -> AutoResult {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{resampling::permutation_test, Alternative};
+
+    if x.len() >= MIN_COUNT_FOR_T_TEST && y.len() >= MIN_COUNT_FOR_T_TEST {
+        let (sample_x, sample_y) = (
+            NumericalSample::from_values(x),
+            NumericalSample::from_values(y),
+        );
+        return AutoResult {
+            method: Method::TTest,
+            result: difference_of_two_means_result(sample_x, sample_y, FiniteF64::new(0.).unwrap()),
+        };
+    }
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let test = permutation_test(x, y, |x, y| mean(x) - mean(y), n_perms, &mut rng);
+    AutoResult {
+        method: Method::Permutation,
+        result: TestResult {
+            statistic: test.statistic,
+            df: None,
+            p_value: test.p_value,
+            alternative: Alternative::TwoSided,
+            effect_size: None,
+            confidence_interval: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_proportion() {
+        let p_0 = NormalizedF64::new(0.5).unwrap();
+        let auto = one_proportion(37, 100, p_0);
+        assert_eq!(auto.method, Method::ZTest);
+        let auto = one_proportion(7, 20, p_0);
+        assert_eq!(auto.method, Method::ExactBinomial);
+        assert!((auto.result.p_value.get() - 0.263_175_964).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_two_by_two() {
+        let auto = two_by_two([[30, 20], [15, 35]]);
+        assert_eq!(auto.method, Method::ChiSquare);
+        let auto = two_by_two([[3, 1], [1, 3]]);
+        assert_eq!(auto.method, Method::FisherExact);
+        assert!((auto.result.p_value.get() - 34. / 70.).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_two_means() {
+        let x = [5.1, 4.8, 6.2, 5.9, 5.5, 6.8, 4.9, 5.3];
+        let y = [4.2, 4.9, 5.0, 4.4, 5.2, 4.7];
+        let auto = two_means(&x, &y, 2_000, 0);
+        assert_eq!(auto.method, Method::Permutation);
+        assert!(auto.result.p_value.get() < 0.05);
+        let auto = two_means(&x, &[4.2], 2_000, 0);
+        assert_eq!(auto.method, Method::Permutation);
+
+        let x = (0..40).map(|i| (i % 7) as f64).collect::<Vec<_>>();
+        let y = (0..40).map(|i| (i % 5) as f64 + 1.).collect::<Vec<_>>();
+        let auto = two_means(&x, &y, 2_000, 0);
+        assert_eq!(auto.method, Method::TTest);
+    }
+}
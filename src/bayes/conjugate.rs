@@ -0,0 +1,351 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use super::hdi;
+use crate::{
+    distributions::{beta::Beta, gamma::Gamma, negative_binomial::NegativeBinomial, t::StudentT},
+    numerical::NumericalSample,
+    special::{ln_binomial_coefficient, ln_gamma},
+};
+
+/// Prior of a parameter whose posterior stays in the same family
+pub trait Prior: Sized {
+    type Data;
+
+    /// The posterior after observing `data`
+    fn update(&self, data: &Self::Data) -> Self;
+
+    fn mean(&self) -> FiniteF64;
+
+    fn variance(&self) -> PositiveF64;
+
+    /// Equal-tailed credible interval holding `mass` of the distribution
+    fn credible_interval(&self, mass: NormalizedF64) -> (FiniteF64, FiniteF64);
+
+    /// Highest-density interval holding `mass` of the distribution
+    fn hdi(&self, mass: NormalizedF64) -> (FiniteF64, FiniteF64);
+}
+
+/// Successes out of independent trials with the same probability
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinomialData {
+    pub successes: usize,
+    pub trials: usize,
+}
+
+/// `Beta(alpha, beta)` on the probability of success of binomial data
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BetaBinomial {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub alpha: PositiveF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub beta: PositiveF64,
+}
+impl BetaBinomial {
+    fn distribution(&self) -> Beta {
+        Beta {
+            a: self.alpha,
+            b: self.beta,
+        }
+    }
+
+    /// Posterior predictive probability of `successes` out of `trials` new trials
+    pub fn predictive_pmf(&self, successes: usize, trials: usize) -> NormalizedF64 {
+        assert!(successes <= trials);
+        let (a, b) = (self.alpha.get(), self.beta.get());
+        let ln_beta = |a: f64, b: f64| ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b);
+        let k = successes as f64;
+        let n = trials as f64;
+        let ln_pmf =
+            ln_binomial_coefficient(trials, successes) + ln_beta(k + a, n - k + b) - ln_beta(a, b);
+        NormalizedF64::new(ln_pmf.exp().clamp(0., 1.)).unwrap()
+    }
+}
+impl Prior for BetaBinomial {
+    type Data = BinomialData;
+
+    fn update(&self, data: &Self::Data) -> Self {
+        assert!(data.successes <= data.trials);
+        Self {
+            alpha: PositiveF64::new(self.alpha.get() + data.successes as f64).unwrap(),
+            beta: PositiveF64::new(self.beta.get() + (data.trials - data.successes) as f64)
+                .unwrap(),
+        }
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(self.distribution().mean().get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        self.distribution().variance()
+    }
+
+    fn credible_interval(&self, mass: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        let tail = (1. - mass.get()) / 2.;
+        let quantile = |p: f64| {
+            let x = self.distribution().quantile(NormalizedF64::new(p).unwrap());
+            FiniteF64::new(x.get()).unwrap()
+        };
+        (quantile(tail), quantile(1. - tail))
+    }
+
+    fn hdi(&self, mass: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        hdi(&self.distribution(), mass)
+    }
+}
+
+/// Total count of events over a total exposure, with events occurring at the same rate per unit of exposure
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoissonData {
+    pub count: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub exposure: PositiveF64,
+}
+
+/// `Gamma(shape, rate)` on the event rate of Poisson data
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GammaPoisson {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub shape: PositiveF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub rate: PositiveF64,
+}
+impl GammaPoisson {
+    fn distribution(&self) -> Gamma {
+        Gamma {
+            shape: self.shape,
+            rate: self.rate,
+        }
+    }
+
+    /// Posterior predictive distribution of the count over a new `exposure`
+    pub fn predictive(&self, exposure: PositiveF64) -> NegativeBinomial {
+        assert!(exposure.get() > 0.);
+        let probability = self.rate.get() / (self.rate.get() + exposure.get());
+        NegativeBinomial {
+            size: self.shape,
+            probability: NormalizedF64::new(probability).unwrap(),
+        }
+    }
+}
+impl Prior for GammaPoisson {
+    type Data = PoissonData;
+
+    fn update(&self, data: &Self::Data) -> Self {
+        Self {
+            shape: PositiveF64::new(self.shape.get() + data.count as f64).unwrap(),
+            rate: PositiveF64::new(self.rate.get() + data.exposure.get()).unwrap(),
+        }
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(self.distribution().mean().get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        self.distribution().variance()
+    }
+
+    fn credible_interval(&self, mass: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        let tail = (1. - mass.get()) / 2.;
+        let quantile = |p: f64| {
+            let x = self.distribution().quantile(NormalizedF64::new(p).unwrap());
+            FiniteF64::new(x.get()).unwrap()
+        };
+        (quantile(tail), quantile(1. - tail))
+    }
+
+    fn hdi(&self, mass: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        hdi(&self.distribution(), mass)
+    }
+}
+
+/// Normal–inverse-gamma prior on the mean `mu` and the variance `sigma^2` of normal data:
+/// `sigma^2 ~ InvGamma(shape, scale)` and `mu | sigma^2 ~ N(mean, sigma^2 / precision)`
+///
+/// - [`Prior::mean`], [`Prior::variance`], and [`Prior::credible_interval`] are of the marginal distribution of `mu`, a Student's t with `2 shape` degrees of freedom.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NormalInverseGamma {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub mean: FiniteF64,
+    /// Pseudo-count of observations behind [`Self::mean`]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub precision: PositiveF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub shape: PositiveF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub scale: PositiveF64,
+}
+impl NormalInverseGamma {
+    fn t(&self) -> StudentT {
+        StudentT {
+            df: PositiveF64::new(2. * self.shape.get()).unwrap(),
+        }
+    }
+
+    /// Scale of the marginal t distribution of `mu`
+    fn mean_scale(&self) -> f64 {
+        (self.scale.get() / (self.shape.get() * self.precision.get())).sqrt()
+    }
+
+    /// Central interval holding `mass` of a location-scale t distribution
+    fn t_interval(&self, scale: f64, mass: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        let tail = (1. - mass.get()) / 2.;
+        let t = self
+            .t()
+            .quantile(NormalizedF64::new(1. - tail).unwrap())
+            .get();
+        let center = self.mean.get();
+        (
+            FiniteF64::new(center - t * scale).unwrap(),
+            FiniteF64::new(center + t * scale).unwrap(),
+        )
+    }
+
+    /// Posterior predictive interval holding `mass` of a new observation
+    ///
+    /// - The predictive distribution is a Student's t with `2 shape` degrees of freedom centered at [`Self::mean`].
+    pub fn predictive_interval(&self, mass: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        let scale = self.mean_scale() * (self.precision.get() + 1.).sqrt();
+        self.t_interval(scale, mass)
+    }
+
+    /// Posterior mean of the variance `sigma^2`, which requires `shape > 1`
+    pub fn variance_mean(&self) -> PositiveF64 {
+        assert!(self.shape.get() > 1.);
+        PositiveF64::new(self.scale.get() / (self.shape.get() - 1.)).unwrap()
+    }
+}
+impl Prior for NormalInverseGamma {
+    type Data = NumericalSample;
+
+    fn update(&self, data: &Self::Data) -> Self {
+        let n = data.count.get() as f64;
+        let precision = self.precision.get() + n;
+        let mean = (self.precision.get() * self.mean.get() + n * data.mean.get()) / precision;
+        let shape = self.shape.get() + n / 2.;
+        let shift = data.mean.get() - self.mean.get();
+        let scale = self.scale.get()
+            + (n - 1.) * data.variance.get() / 2.
+            + self.precision.get() * n * shift.powi(2) / (2. * precision);
+        Self {
+            mean: FiniteF64::new(mean).unwrap(),
+            precision: PositiveF64::new(precision).unwrap(),
+            shape: PositiveF64::new(shape).unwrap(),
+            scale: PositiveF64::new(scale).unwrap(),
+        }
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        self.mean
+    }
+
+    /// Requires `shape > 1`
+    fn variance(&self) -> PositiveF64 {
+        assert!(self.shape.get() > 1.);
+        let variance = self.scale.get() / ((self.shape.get() - 1.) * self.precision.get());
+        PositiveF64::new(variance).unwrap()
+    }
+
+    fn credible_interval(&self, mass: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        self.t_interval(self.mean_scale(), mass)
+    }
+
+    /// The same as [`Prior::credible_interval`] since the t distribution is symmetric
+    fn hdi(&self, mass: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        self.credible_interval(mass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    #[test]
+    fn test_beta_binomial() {
+        let prior = BetaBinomial {
+            alpha: PositiveF64::new(1.).unwrap(),
+            beta: PositiveF64::new(1.).unwrap(),
+        };
+        let data = BinomialData {
+            successes: 7,
+            trials: 20,
+        };
+        let posterior = prior.update(&data);
+        assert_eq!(posterior.alpha.get(), 8.);
+        assert_eq!(posterior.beta.get(), 14.);
+        assert!((posterior.mean().get() - 8. / 22.).abs() < 1e-12);
+        assert!((posterior.variance().get() - 112. / (484. * 23.)).abs() < 1e-12);
+        let (lower, upper) = posterior.credible_interval(NormalizedF64::new(0.95).unwrap());
+        assert!((lower.get() - 0.181_071_626).abs() < 1e-7);
+        assert!((upper.get() - 0.569_675_483).abs() < 1e-7);
+        let (lower, upper) = posterior.hdi(NormalizedF64::new(0.95).unwrap());
+        assert!((lower.get() - 0.173_390_666).abs() < 1e-6);
+        assert!((upper.get() - 0.560_555_341).abs() < 1e-6);
+
+        assert!((posterior.predictive_pmf(1, 1).get() - 8. / 22.).abs() < 1e-12);
+        let total = (0..=5)
+            .map(|k| posterior.predictive_pmf(k, 5).get())
+            .sum::<f64>();
+        assert!((total - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_gamma_poisson() {
+        let prior = GammaPoisson {
+            shape: PositiveF64::new(2.).unwrap(),
+            rate: PositiveF64::new(1.).unwrap(),
+        };
+        let data = PoissonData {
+            count: 10,
+            exposure: PositiveF64::new(4.).unwrap(),
+        };
+        let posterior = prior.update(&data);
+        assert!((posterior.mean().get() - 2.4).abs() < 1e-12);
+        assert!((posterior.variance().get() - 12. / 25.).abs() < 1e-12);
+        let (lower, upper) = posterior.credible_interval(NormalizedF64::new(0.95).unwrap());
+        assert!((lower.get() - 1.240_115_022).abs() < 1e-7);
+        assert!((upper.get() - 3.936_407_703).abs() < 1e-7);
+
+        let predictive = posterior.predictive(PositiveF64::new(1.).unwrap());
+        assert!((predictive.mean().get() - 2.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_normal_inverse_gamma() {
+        let prior = NormalInverseGamma {
+            mean: FiniteF64::new(0.).unwrap(),
+            precision: PositiveF64::new(1.).unwrap(),
+            shape: PositiveF64::new(2.).unwrap(),
+            scale: PositiveF64::new(2.).unwrap(),
+        };
+        let data = NumericalSample {
+            mean: FiniteF64::new(5.).unwrap(),
+            variance: PositiveF64::new(4.).unwrap(),
+            count: NonZeroUsize::new(10).unwrap(),
+            population: None,
+        };
+        let posterior = prior.update(&data);
+        assert!((posterior.mean().get() - 50. / 11.).abs() < 1e-12);
+        assert_eq!(posterior.precision.get(), 11.);
+        assert_eq!(posterior.shape.get(), 7.);
+        assert!((posterior.scale.get() - 31.363_636_364).abs() < 1e-9);
+        assert!((posterior.variance().get() - 0.475_206_612).abs() < 1e-9);
+        assert!((posterior.variance_mean().get() - 31.363_636_364 / 6.).abs() < 1e-9);
+
+        let mass = NormalizedF64::new(0.95).unwrap();
+        let (lower, upper) = posterior.credible_interval(mass);
+        assert!((lower.get() - 3.176_616_877).abs() < 1e-6);
+        assert!((upper.get() - 5.914_292_214).abs() < 1e-6);
+        let (lower, upper) = posterior.predictive_interval(mass);
+        assert!((lower.get() - -0.196_338_232).abs() < 1e-6);
+        assert!((upper.get() - 9.287_247_323).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,237 @@
+use std::num::NonZeroUsize;
+
+use rand::Rng;
+use strict_num::{NormalizedF64, PositiveF64};
+
+use crate::distributions::normal::standard_normal_sample;
+
+/// Length and thinning of each chain
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sampling {
+    /// Draws kept from each chain
+    pub n_samples: usize,
+    /// Draws discarded from the start of each chain
+    pub burn_in: usize,
+    /// Keep every `thin`-th draw after the burn-in
+    pub thin: NonZeroUsize,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Chain {
+    /// `samples[i][j]`: parameter `j` of the `i`-th kept draw
+    pub samples: Vec<Vec<f64>>,
+    /// Proportion of accepted proposals over all iterations, including the burn-in
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub acceptance_rate: NormalizedF64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mcmc {
+    pub chains: Vec<Chain>,
+    /// Split potential scale reduction factor of each parameter; close to 1 once the chains have mixed, and NaN if no draws vary
+    pub r_hat: Vec<f64>,
+    /// Effective sample size of each parameter over all chains, from the autocorrelations of the split chains
+    pub effective_size: Vec<f64>,
+}
+impl Mcmc {
+    /// Kept draws of `parameter` from all chains
+    pub fn samples(&self, parameter: usize) -> Vec<f64> {
+        self.chains
+            .iter()
+            .flat_map(|chain| chain.samples.iter().map(|draw| draw[parameter]))
+            .collect()
+    }
+}
+
+/// Random-walk Metropolis sampler of a user-supplied posterior.
+///
+/// - `log_posterior`: the log density up to a constant; `-inf` outside the support
+/// - `initial`: the starting point of each chain, with a finite log posterior
+/// - `step_sizes`: the standard deviation of the normal proposal of each parameter; an acceptance rate of about 0.2 to 0.5 usually mixes well
+/// - Diagnose with at least two chains from dispersed starting points.
+pub fn metropolis(
+    log_posterior: impl Fn(&[f64]) -> f64,
+    initial: &[Vec<f64>],
+    step_sizes: &[PositiveF64],
+    sampling: Sampling,
+    rng: &mut impl Rng,
+) -> Mcmc {
+    assert!(!initial.is_empty());
+    assert!(sampling.n_samples >= 4);
+    let dimension = step_sizes.len();
+    initial
+        .iter()
+        .for_each(|start| assert_eq!(start.len(), dimension));
+
+    let iterations = sampling.burn_in + sampling.n_samples * sampling.thin.get();
+    let chains = initial
+        .iter()
+        .map(|start| {
+            let mut current = start.clone();
+            let mut current_log_posterior = log_posterior(&current);
+            assert!(current_log_posterior.is_finite());
+            let mut proposal = vec![0.; dimension];
+            let mut accepted = 0;
+            let mut samples = Vec::with_capacity(sampling.n_samples);
+            for iteration in 0..iterations {
+                proposal
+                    .iter_mut()
+                    .zip(current.iter().zip(step_sizes))
+                    .for_each(|(proposal, (current, step_size))| {
+                        *proposal = current + step_size.get() * standard_normal_sample(rng);
+                    });
+                let proposal_log_posterior = log_posterior(&proposal);
+                let log_ratio = proposal_log_posterior - current_log_posterior;
+                if log_ratio >= 0. || rng.gen::<f64>().ln() < log_ratio {
+                    current.copy_from_slice(&proposal);
+                    current_log_posterior = proposal_log_posterior;
+                    accepted += 1;
+                }
+                let after_burn_in = (iteration + 1).saturating_sub(sampling.burn_in);
+                if after_burn_in > 0 && after_burn_in.is_multiple_of(sampling.thin.get()) {
+                    samples.push(current.clone());
+                }
+            }
+            Chain {
+                samples,
+                acceptance_rate: NormalizedF64::new(accepted as f64 / iterations as f64).unwrap(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let (r_hat, effective_size) = (0..dimension)
+        .map(|j| {
+            let draws = chains
+                .iter()
+                .map(|chain| chain.samples.iter().map(|draw| draw[j]).collect())
+                .collect::<Vec<Vec<f64>>>();
+            diagnose(&draws)
+        })
+        .unzip();
+    Mcmc {
+        chains,
+        r_hat,
+        effective_size,
+    }
+}
+
+/// Split R-hat and effective sample size of one parameter
+///
+/// ref: Gelman, A., et al. (2013). Bayesian Data Analysis (3rd ed.), section 11.4-11.5.
+fn diagnose(chains: &[Vec<f64>]) -> (f64, f64) {
+    let n = chains[0].len() / 2;
+    let halves = chains
+        .iter()
+        .flat_map(|chain| [&chain[..n], &chain[chain.len() - n..]])
+        .collect::<Vec<_>>();
+    let m = halves.len() as f64;
+    let n_f64 = n as f64;
+
+    let means = halves
+        .iter()
+        .map(|half| half.iter().sum::<f64>() / n_f64)
+        .collect::<Vec<_>>();
+    let grand_mean = means.iter().sum::<f64>() / m;
+    let between = n_f64 / (m - 1.) * means.iter().map(|x| (x - grand_mean).powi(2)).sum::<f64>();
+    let within = halves
+        .iter()
+        .zip(&means)
+        .map(|(half, mean)| half.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n_f64 - 1.))
+        .sum::<f64>()
+        / m;
+    let variance = (n_f64 - 1.) / n_f64 * within + between / n_f64;
+    let r_hat = (variance / within).sqrt();
+
+    // Autocorrelation at `lag` from the variogram
+    let autocorrelation = |lag: usize| {
+        let variogram = halves
+            .iter()
+            .map(|half| {
+                half.iter()
+                    .zip(&half[lag..])
+                    .map(|(a, b)| (b - a).powi(2))
+                    .sum::<f64>()
+            })
+            .sum::<f64>()
+            / (m * (n - lag) as f64);
+        1. - variogram / (2. * variance)
+    };
+    // Geyer's initial positive sequence
+    let mut sum = 0.;
+    let mut lag = 1;
+    while lag + 1 < n {
+        let pair = autocorrelation(lag) + autocorrelation(lag + 1);
+        if pair.is_nan() || pair < 0. {
+            break;
+        }
+        sum += pair;
+        lag += 2;
+    }
+    let effective_size = m * n_f64 / (1. + 2. * sum);
+    (r_hat, effective_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn sampling() -> Sampling {
+        Sampling {
+            n_samples: 2000,
+            burn_in: 500,
+            thin: NonZeroUsize::new(2).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_metropolis() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // Independent normals with means 1 and -2 and standard deviations 1 and 0.5
+        let log_posterior =
+            |x: &[f64]| -0.5 * (x[0] - 1.).powi(2) - 0.5 * ((x[1] + 2.) / 0.5).powi(2);
+        let initial = [vec![-5., 5.], vec![5., -5.], vec![0., 0.], vec![3., 3.]];
+        let step_sizes = [1.5, 0.75].map(|s| PositiveF64::new(s).unwrap());
+        let mcmc = metropolis(log_posterior, &initial, &step_sizes, sampling(), &mut rng);
+
+        assert_eq!(mcmc.chains.len(), 4);
+        assert_eq!(mcmc.chains[0].samples.len(), 2000);
+        for (j, (mean, standard_deviation)) in [(1., 1.), (-2., 0.5)].into_iter().enumerate() {
+            let samples = mcmc.samples(j);
+            let n = samples.len() as f64;
+            let sample_mean = samples.iter().sum::<f64>() / n;
+            let variance = samples
+                .iter()
+                .map(|x| (x - sample_mean).powi(2))
+                .sum::<f64>()
+                / n;
+            assert!((sample_mean - mean).abs() < 0.1);
+            assert!((variance.sqrt() - standard_deviation).abs() < 0.1);
+            assert!(mcmc.r_hat[j] < 1.05);
+            assert!(500. < mcmc.effective_size[j] && mcmc.effective_size[j] < n);
+        }
+        mcmc.chains.iter().for_each(|chain| {
+            let rate = chain.acceptance_rate.get();
+            assert!(0.2 < rate && rate < 0.7);
+        });
+    }
+
+    #[test]
+    fn test_r_hat_of_stuck_chains() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // Two far apart modes that small steps never cross
+        let log_posterior = |x: &[f64]| {
+            let a = -0.5 * (x[0] - 10.).powi(2);
+            let b = -0.5 * (x[0] + 10.).powi(2);
+            a.max(b) + (1. + (-(a - b).abs()).exp()).ln()
+        };
+        let initial = [vec![-10.], vec![10.]];
+        let step_sizes = [PositiveF64::new(0.5).unwrap()];
+        let mcmc = metropolis(log_posterior, &initial, &step_sizes, sampling(), &mut rng);
+        assert!(mcmc.r_hat[0] > 1.5);
+    }
+}
@@ -0,0 +1,293 @@
+pub mod conjugate;
+#[cfg(feature = "rand")]
+pub mod mcmc;
+
+use std::f64::consts::PI;
+
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    categorical::CountAndProportion,
+    distributions::{beta::Beta, ContinuousDistribution},
+    numerical::NumericalSample,
+    special::{betainc_regularized, golden_section_min, simpson},
+};
+
+/// Subintervals of Simpson's rule
+const INTEGRATION_STEPS: usize = 4000;
+/// Half width of the integration range in posterior standard deviations
+const INTEGRATION_DEVIATIONS: f64 = 12.;
+/// Half width of the integration range of `ln g` for the JZS Bayes factor
+const LN_G_RANGE: f64 = 40.;
+
+/// `Beta(alpha, beta)` prior on a conversion rate
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BetaPrior {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub alpha: PositiveF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub beta: PositiveF64,
+}
+impl BetaPrior {
+    /// `Beta(1, 1)`
+    pub fn uniform() -> Self {
+        Self {
+            alpha: PositiveF64::new(1.).unwrap(),
+            beta: PositiveF64::new(1.).unwrap(),
+        }
+    }
+
+    fn posterior(&self, sample: CountAndProportion) -> Beta {
+        let successes = (sample.count as f64 * sample.proportion.get()).round();
+        let failures = sample.count as f64 - successes;
+        Beta {
+            a: PositiveF64::new(self.alpha.get() + successes).unwrap(),
+            b: PositiveF64::new(self.beta.get() + failures).unwrap(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BayesianAbTest {
+    /// Posterior probability that the conversion rate of B exceeds that of A
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub probability_b_greater: NormalizedF64,
+    /// Expected shortfall in conversion rate when choosing A, `E[max(p_B - p_A, 0)]`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub expected_loss_choosing_a: PositiveF64,
+    /// Expected shortfall in conversion rate when choosing B, `E[max(p_A - p_B, 0)]`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub expected_loss_choosing_b: PositiveF64,
+    /// Equal-tailed credible interval of the conversion rate of A
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub credible_interval_a: (NormalizedF64, NormalizedF64),
+    /// Equal-tailed credible interval of the conversion rate of B
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub credible_interval_b: (NormalizedF64, NormalizedF64),
+}
+
+/// Bayesian A/B test on conversion rates with Beta–Binomial conjugate posteriors.
+///
+/// - The success count of each sample is its count times its proportion, rounded.
+/// - `credible_mass`: the posterior mass inside each credible interval, e.g. 0.95
+pub fn ab_test(
+    sample_a: CountAndProportion,
+    sample_b: CountAndProportion,
+    prior: BetaPrior,
+    credible_mass: NormalizedF64,
+) -> BayesianAbTest {
+    let a = prior.posterior(sample_a);
+    let b = prior.posterior(sample_b);
+
+    let cdf = |distribution: &Beta, x: f64| distribution.cdf(NormalizedF64::new(x).unwrap()).get();
+    let probability_b_greater = integrate_over(&b, |x| pdf(&b, x) * cdf(&a, x));
+    let expected_loss_choosing_b = integrate_over(&b, |x| pdf(&b, x) * expected_excess_over(&a, x));
+    let expected_loss_choosing_a = integrate_over(&a, |x| pdf(&a, x) * expected_excess_over(&b, x));
+
+    BayesianAbTest {
+        probability_b_greater: NormalizedF64::new(probability_b_greater.clamp(0., 1.)).unwrap(),
+        expected_loss_choosing_a: PositiveF64::new(expected_loss_choosing_a.max(0.)).unwrap(),
+        expected_loss_choosing_b: PositiveF64::new(expected_loss_choosing_b.max(0.)).unwrap(),
+        credible_interval_a: credible_interval(&a, credible_mass),
+        credible_interval_b: credible_interval(&b, credible_mass),
+    }
+}
+
+/// Density of `distribution` at `x`, taking the endpoints as zero where it may be unbounded
+fn pdf(distribution: &Beta, x: f64) -> f64 {
+    if x <= 0. || x >= 1. {
+        return 0.;
+    }
+    distribution.pdf(NormalizedF64::new(x).unwrap()).get()
+}
+
+/// `E[max(p - x, 0)] = mean (1 - I_x(a + 1, b)) - x (1 - I_x(a, b))`
+fn expected_excess_over(distribution: &Beta, x: f64) -> f64 {
+    let (a, b) = (distribution.a.get(), distribution.b.get());
+    let shifted = betainc_regularized(a + 1., b, x);
+    distribution.mean().get() * (1. - shifted) - x * (1. - betainc_regularized(a, b, x))
+}
+
+/// Equal-tailed credible interval holding `mass` of `distribution`
+fn credible_interval(distribution: &Beta, mass: NormalizedF64) -> (NormalizedF64, NormalizedF64) {
+    let tail = (1. - mass.get()) / 2.;
+    (
+        distribution.quantile(NormalizedF64::new(tail).unwrap()),
+        distribution.quantile(NormalizedF64::new(1. - tail).unwrap()),
+    )
+}
+
+/// JZS Bayes factor `BF_10` of the two-sample t-test.
+///
+/// Null hypothesis: the two means are equal.
+///
+/// - The standardized effect size has a `Cauchy(0, cauchy_scale)` prior under the alternative.
+/// - `cauchy_scale`: usually `sqrt(2) / 2` ("medium")
+/// - Values above 1 favor the alternative hypothesis; values below 1 favor the null hypothesis.
+///
+/// ref: Rouder, J. N., Speckman, P. L., Sun, D., Morey, R. D., & Iverson, G. (2009). Bayesian t tests for accepting and rejecting the null hypothesis.
+pub fn t_test_bf(
+    sample_1: NumericalSample,
+    sample_2: NumericalSample,
+    cauchy_scale: PositiveF64,
+) -> PositiveF64 {
+    assert!(cauchy_scale.get() > 0.);
+    let n_1 = sample_1.count.get() as f64;
+    let n_2 = sample_2.count.get() as f64;
+    let df = n_1 + n_2 - 2.;
+    assert!(df > 0.);
+    let pooled_variance =
+        ((n_1 - 1.) * sample_1.variance.get() + (n_2 - 1.) * sample_2.variance.get()) / df;
+    let t = (sample_1.mean.get() - sample_2.mean.get())
+        / (pooled_variance * (1. / n_1 + 1. / n_2)).sqrt();
+    let t = FiniteF64::new(t).unwrap();
+    let effective_count = n_1 * n_2 / (n_1 + n_2);
+    let bf = jzs_bayes_factor(t, effective_count, df, cauchy_scale.get());
+    PositiveF64::new(bf).unwrap()
+}
+
+/// `BF_10` of a t statistic, integrating over `g` where `δ | g ~ N(0, g)` and `g ~ InverseGamma(1/2, r^2/2)`
+fn jzs_bayes_factor(t: FiniteF64, effective_count: f64, df: f64, r: f64) -> f64 {
+    let t_squared = t.get().powi(2);
+    let ln_null_kernel = (1. + t_squared / df).ln();
+    // Substitute `g = e^u` to cover the heavy tails of the prior
+    let integrand = |u: f64| {
+        let g = u.exp();
+        let scaled = 1. + effective_count * g;
+        let ln_likelihood_ratio = -0.5 * scaled.ln()
+            - (df + 1.) / 2. * ((1. + t_squared / (scaled * df)).ln() - ln_null_kernel);
+        let ln_prior = r.ln() - 0.5 * (2. * PI).ln() - 1.5 * u - r.powi(2) / (2. * g);
+        (ln_likelihood_ratio + ln_prior + u).exp()
+    };
+    simpson(integrand, -LN_G_RANGE, LN_G_RANGE, INTEGRATION_STEPS)
+}
+
+/// Highest-density interval: the shortest interval holding `mass` of a unimodal `distribution`
+///
+/// - Golden-section search over the lower tail probability of the interval
+pub fn hdi(
+    distribution: &dyn ContinuousDistribution,
+    mass: NormalizedF64,
+) -> (FiniteF64, FiniteF64) {
+    assert!(0. < mass.get() && mass.get() < 1.);
+    let quantile = |p: f64| distribution.quantile(NormalizedF64::new(p.clamp(0., 1.)).unwrap());
+    let width = |p: f64| quantile(p + mass.get()).get() - quantile(p).get();
+    let p = golden_section_min(width, 0., 1. - mass.get());
+    (quantile(p), quantile(p + mass.get()))
+}
+
+/// Highest-density interval from Monte Carlo draws: the narrowest window of `ceil(mass n)` sorted samples
+pub fn hdi_of_samples(samples: &[f64], mass: NormalizedF64) -> (FiniteF64, FiniteF64) {
+    assert!(!samples.is_empty());
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = sorted.len();
+    let window = ((mass.get() * n as f64).ceil() as usize).clamp(1, n);
+    let start = (0..=n - window)
+        .min_by(|&a, &b| {
+            let width_a = sorted[a + window - 1] - sorted[a];
+            let width_b = sorted[b + window - 1] - sorted[b];
+            width_a.partial_cmp(&width_b).unwrap()
+        })
+        .unwrap();
+    (
+        FiniteF64::new(sorted[start]).unwrap(),
+        FiniteF64::new(sorted[start + window - 1]).unwrap(),
+    )
+}
+
+/// Simpson's rule over the bulk of `distribution`
+fn integrate_over(distribution: &Beta, f: impl Fn(f64) -> f64) -> f64 {
+    let mean = distribution.mean().get();
+    let spread = INTEGRATION_DEVIATIONS * distribution.variance().get().sqrt();
+    let lower = (mean - spread).max(0.);
+    let upper = (mean + spread).min(1.);
+    simpson(f, lower, upper, INTEGRATION_STEPS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hdi() {
+        let mass = NormalizedF64::new(0.95).unwrap();
+        let beta = Beta {
+            a: PositiveF64::new(8.).unwrap(),
+            b: PositiveF64::new(14.).unwrap(),
+        };
+        let (lower, upper) = hdi(&beta, mass);
+        assert!((lower.get() - 0.173_390_666).abs() < 1e-6);
+        assert!((upper.get() - 0.560_555_341).abs() < 1e-6);
+
+        // Symmetric distributions have equal tails
+        let t = crate::distributions::t::StudentT {
+            df: PositiveF64::new(5.).unwrap(),
+        };
+        let (lower, upper) = hdi(&t, mass);
+        assert!((lower.get() + upper.get()).abs() < 1e-6);
+        assert!((upper.get() - 2.570_581_836).abs() < 1e-6);
+
+        let samples = [1., 2., 2.5, 3., 3.2, 3.4, 3.5, 4., 6., 9.];
+        let mass = NormalizedF64::new(0.5).unwrap();
+        let (lower, upper) = hdi_of_samples(&samples, mass);
+        assert_eq!((lower.get(), upper.get()), (2.5, 3.5));
+    }
+
+    #[test]
+    fn test_ab_test() {
+        let sample_a = CountAndProportion {
+            count: 100,
+            proportion: NormalizedF64::new(0.2).unwrap(),
+            population: None,
+        };
+        let sample_b = CountAndProportion {
+            count: 100,
+            proportion: NormalizedF64::new(0.3).unwrap(),
+            population: None,
+        };
+        let credible_mass = NormalizedF64::new(0.95).unwrap();
+        let test = ab_test(sample_a, sample_b, BetaPrior::uniform(), credible_mass);
+
+        // Closed form for integer posterior parameters
+        assert!((test.probability_b_greater.get() - 0.947_603_103).abs() < 1e-6);
+        // Monte Carlo
+        assert!((test.expected_loss_choosing_a.get() - 0.0994).abs() < 1e-3);
+        assert!((test.expected_loss_choosing_b.get() - 0.001_345).abs() < 1e-4);
+
+        let (lower, upper) = test.credible_interval_a;
+        let posterior = Beta {
+            a: PositiveF64::new(21.).unwrap(),
+            b: PositiveF64::new(81.).unwrap(),
+        };
+        assert!((posterior.cdf(lower).get() - 0.025).abs() < 1e-9);
+        assert!((posterior.cdf(upper).get() - 0.975).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_t_test_bf() {
+        use std::num::NonZeroUsize;
+
+        let sample_1 = NumericalSample {
+            mean: FiniteF64::new(5.6125).unwrap(),
+            variance: PositiveF64::new(0.235_535_714).unwrap(),
+            count: NonZeroUsize::new(8).unwrap(),
+            population: None,
+        };
+        let sample_2 = NumericalSample {
+            mean: FiniteF64::new(4.675).unwrap(),
+            variance: PositiveF64::new(0.147_857_143).unwrap(),
+            count: NonZeroUsize::new(8).unwrap(),
+            population: None,
+        };
+        let medium = PositiveF64::new(2_f64.sqrt() / 2.).unwrap();
+        let bf = t_test_bf(sample_1, sample_2, medium);
+        assert!((bf.get() - 36.4224).abs() < 0.01);
+
+        // Evidence for the null hypothesis
+        let bf = jzs_bayes_factor(FiniteF64::new(0.5).unwrap(), 10., 38., 2_f64.sqrt() / 2.);
+        assert!((bf - 0.341_129).abs() < 1e-4);
+    }
+}
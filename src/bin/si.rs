@@ -0,0 +1,359 @@
+//! `si`: statistical tests from the command line.
+
+use std::{fs::File, io::Read, path::PathBuf, process::ExitCode};
+
+use clap::{Args, Parser, Subcommand};
+use statistical_inference::{
+    categorical::{one_proportion_result, ContingencyTable, CountAndProportion},
+    distributions::t::StudentT,
+    io::{read_column, read_crosstab},
+    numerical::{
+        anova_result, difference_of_two_means_result, one_sample_mean_result, NumericalSample,
+    },
+    special::normal_quantile,
+    CheckAssumptions, DegreesOfFreedom, Diagnostic, TestResult,
+};
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+#[derive(Parser)]
+#[command(name = "si", about = "Statistical inference from the command line")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// One-proportion z test
+    PropTest {
+        /// Number of successes
+        #[arg(long)]
+        successes: usize,
+        /// Number of trials
+        #[arg(long)]
+        trials: usize,
+        /// Null proportion
+        #[arg(long, default_value_t = 0.5)]
+        p0: f64,
+        #[arg(long, default_value_t = 0.95)]
+        confidence: f64,
+    },
+    /// One-sample t test, or two-sample if `--other` is given
+    TTest {
+        #[command(flatten)]
+        input: Input,
+        /// The second sample, comma separated
+        #[arg(long, value_delimiter = ',', allow_negative_numbers = true)]
+        other: Vec<f64>,
+        /// Null mean, or null difference of the means
+        #[arg(long, default_value_t = 0., allow_negative_numbers = true)]
+        mu: f64,
+        #[arg(long, default_value_t = 0.95)]
+        confidence: f64,
+    },
+    /// Chi-square test of independence
+    Chisq {
+        /// A row of counts, comma separated; repeat for each row
+        #[arg(long = "row")]
+        rows: Vec<String>,
+        /// Cross tabulate two columns of a CSV file instead
+        #[arg(long, requires_all = ["row_column", "col_column"])]
+        csv: Option<PathBuf>,
+        #[arg(long)]
+        row_column: Option<String>,
+        #[arg(long)]
+        col_column: Option<String>,
+    },
+    /// One-way ANOVA
+    Anova {
+        /// Observations of a group, comma separated; repeat for each group.
+        /// Without any, each line of stdin is a group.
+        #[arg(long = "group", allow_negative_numbers = true)]
+        groups: Vec<String>,
+    },
+}
+
+/// Observations from arguments, a CSV column, or stdin, in that order of preference
+#[derive(Args)]
+struct Input {
+    #[arg(allow_negative_numbers = true)]
+    values: Vec<f64>,
+    #[arg(long, requires = "column")]
+    csv: Option<PathBuf>,
+    #[arg(long)]
+    column: Option<String>,
+}
+impl Input {
+    fn read(self) -> Result<Vec<f64>, String> {
+        if !self.values.is_empty() {
+            return Ok(self.values);
+        }
+        if let Some(path) = self.csv {
+            let file = File::open(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+            return read_column(file, &self.column.unwrap()).map_err(|e| e.to_string());
+        }
+        parse_numbers(&read_stdin()?)
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), String> {
+    match command {
+        Command::PropTest {
+            successes,
+            trials,
+            p0,
+            confidence,
+        } => {
+            if successes > trials || trials == 0 {
+                return Err("expected 0 < trials and successes <= trials".to_string());
+            }
+            if !(0. < p0 && p0 < 1.) {
+                return Err(format!("`--p0 {p0}` is not in (0, 1)"));
+            }
+            let confidence = self::confidence(confidence)?;
+            let proportion = successes as f64 / trials as f64;
+            let sample = CountAndProportion {
+                count: trials,
+                proportion: NormalizedF64::new(proportion).unwrap(),
+                population: None,
+            };
+            check(sample.check_assumptions())?;
+            let mut result = one_proportion_result(sample, normalized(p0)?);
+            let z = normal_quantile(0.5 + confidence.get() / 2.);
+            let margin = z * (proportion * (1. - proportion) / trials as f64).sqrt();
+            result.confidence_interval = Some(interval(proportion, margin));
+            print(&result);
+        }
+        Command::TTest {
+            input,
+            other,
+            mu,
+            confidence,
+        } => {
+            let sample = sample(&input.read()?)?;
+            let mu = FiniteF64::new(mu).ok_or("`--mu` must be finite")?;
+            let confidence = self::confidence(confidence)?;
+            let (mut result, estimate, standard_error) = if other.is_empty() {
+                let standard_error = sample.standard_error_squared().sqrt();
+                if standard_error == 0. {
+                    return Err("expected observations that are not all equal".to_string());
+                }
+                let result = one_sample_mean_result(sample, mu);
+                (result, sample.mean.get(), standard_error)
+            } else {
+                let other = self::sample(&other)?;
+                let standard_error =
+                    (sample.standard_error_squared() + other.standard_error_squared()).sqrt();
+                if standard_error == 0. {
+                    return Err("expected observations that are not all equal".to_string());
+                }
+                let result = difference_of_two_means_result(sample, other, mu);
+                (result, sample.mean.get() - other.mean.get(), standard_error)
+            };
+            let Some(DegreesOfFreedom::One(df)) = result.df else {
+                unreachable!()
+            };
+            let p = NormalizedF64::new(0.5 + confidence.get() / 2.).unwrap();
+            let t = StudentT { df }.quantile(p).get();
+            result.confidence_interval = Some(interval(estimate, t * standard_error));
+            print(&result);
+        }
+        Command::Chisq {
+            rows,
+            csv,
+            row_column,
+            col_column,
+        } => {
+            let table = match csv {
+                Some(path) => {
+                    let file = File::open(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+                    read_crosstab(file, &row_column.unwrap(), &col_column.unwrap())
+                        .map_err(|e| e.to_string())?
+                }
+                None => {
+                    let counts = rows
+                        .iter()
+                        .map(|row| {
+                            row.split(',')
+                                .map(|count| count.trim().parse::<usize>())
+                                .collect::<Result<Vec<_>, _>>()
+                                .map_err(|e| format!("`{row}`: {e}"))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let cols = counts.first().map_or(0, |row| row.len());
+                    if counts.len() < 2 || cols < 2 || counts.iter().any(|row| row.len() != cols) {
+                        return Err("expected at least two `--row`s of equal length".to_string());
+                    }
+                    let labels = |n: usize| (0..n).map(|i| i.to_string()).collect();
+                    ContingencyTable::new(labels(counts.len()), labels(cols), counts)
+                }
+            };
+            if table.row_labels.len() < 2 || table.col_labels.len() < 2 {
+                return Err("expected at least two levels of each variable".to_string());
+            }
+            if table.total() == 0 {
+                return Err("expected a table with a nonzero total".to_string());
+            }
+            check(table.expected_count_report())?;
+            print(&table.independence());
+        }
+        Command::Anova { groups } => {
+            let groups = if groups.is_empty() {
+                read_stdin()?
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(str::to_string)
+                    .collect()
+            } else {
+                groups
+            };
+            let groups = groups
+                .iter()
+                .map(|group| sample(&parse_numbers(group)?))
+                .collect::<Result<Vec<_>, _>>()?;
+            if groups.len() < 2 {
+                return Err("expected at least two groups".to_string());
+            }
+            if groups.iter().all(|group| group.variance.get() == 0.) {
+                return Err("expected groups whose observations are not all equal".to_string());
+            }
+            print(&anova_result(&groups));
+        }
+    }
+    Ok(())
+}
+
+fn print(result: &TestResult) {
+    println!("statistic: {}", result.statistic.get());
+    match result.df {
+        Some(DegreesOfFreedom::One(df)) => println!("df: {}", df.get()),
+        Some(DegreesOfFreedom::Two(df_1, df_2)) => println!("df: {}, {}", df_1.get(), df_2.get()),
+        None => println!("df: -"),
+    }
+    println!("p-value: {}", result.p_value.get());
+    match result.confidence_interval {
+        Some((lower, upper)) => println!("ci: [{}, {}]", lower.get(), upper.get()),
+        None => println!("ci: -"),
+    }
+}
+
+fn sample(values: &[f64]) -> Result<NumericalSample, String> {
+    if values.len() < 2 {
+        return Err("expected at least two observations".to_string());
+    }
+    if values.iter().any(|x| !x.is_finite()) {
+        return Err("expected finite observations".to_string());
+    }
+    Ok(NumericalSample::from_values(values))
+}
+
+/// The first condition of `report` that fails, as an error
+fn check(report: Vec<Diagnostic>) -> Result<(), String> {
+    match report
+        .into_iter()
+        .find(|diagnostic| !diagnostic.is_satisfied())
+    {
+        Some(diagnostic) => Err(format!(
+            "{} is {}, less than the {} the approximation needs",
+            diagnostic.condition,
+            diagnostic.observed.get(),
+            diagnostic.required.get(),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn confidence(x: f64) -> Result<NormalizedF64, String> {
+    if !(0. < x && x < 1.) {
+        return Err(format!("`--confidence {x}` is not in (0, 1)"));
+    }
+    Ok(NormalizedF64::new(x).unwrap())
+}
+
+fn normalized(x: f64) -> Result<NormalizedF64, String> {
+    NormalizedF64::new(x).ok_or_else(|| format!("`{x}` is not in [0, 1]"))
+}
+
+fn interval(estimate: f64, margin: f64) -> (FiniteF64, FiniteF64) {
+    let margin = PositiveF64::new(margin).unwrap().get();
+    (
+        FiniteF64::new(estimate - margin).unwrap(),
+        FiniteF64::new(estimate + margin).unwrap(),
+    )
+}
+
+fn read_stdin() -> Result<String, String> {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| e.to_string())?;
+    Ok(input)
+}
+
+/// Numbers separated by commas or whitespace
+fn parse_numbers(text: &str) -> Result<Vec<f64>, String> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token
+                .parse::<f64>()
+                .map_err(|_| format!("`{token}` is not a number"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_numbers() {
+        assert_eq!(parse_numbers("1, 2.5\n-3\t4").unwrap(), [1., 2.5, -3., 4.]);
+        assert!(parse_numbers("1 x").is_err());
+    }
+
+    #[test]
+    fn test_run_rejects_invalid_input() {
+        let run = |args: &[&str]| run(Cli::try_parse_from(args).unwrap().command);
+        assert!(run(&["si", "prop-test", "--successes", "3", "--trials", "10"]).is_err());
+        assert!(run(&[
+            "si",
+            "prop-test",
+            "--successes",
+            "30",
+            "--trials",
+            "60",
+            "--confidence",
+            "1"
+        ])
+        .is_err());
+        assert!(run(&["si", "chisq", "--row", "1,2", "--row", "3,4"]).is_err());
+        assert!(run(&["si", "chisq", "--row", "0,0", "--row", "0,0"]).is_err());
+        let path = std::env::temp_dir().join("si_chisq_one_level.csv");
+        std::fs::write(&path, "a,b\nx,1\nx,2\n").unwrap();
+        let csv = path.to_str().unwrap();
+        let args = [
+            "si",
+            "chisq",
+            "--csv",
+            csv,
+            "--row-column",
+            "a",
+            "--col-column",
+            "b",
+        ];
+        assert!(run(&args).is_err());
+        assert!(run(&["si", "t-test", "1", "1", "1"]).is_err());
+        assert!(run(&["si", "anova", "--group", "1,1", "--group", "2,2"]).is_err());
+    }
+}
@@ -1,37 +1,120 @@
-use std::num::NonZeroUsize;
+use std::{collections::HashMap, f64::consts::PI, hash::Hash, num::NonZeroUsize};
 
 use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
 
-use crate::distributions::{chi_square::CHI_SQUARE_TABLE, normal::Z_SCORE_TABLE};
+use crate::{
+    distributions::{
+        beta::Beta, binomial::Binomial, chi_square::CHI_SQUARE_TABLE,
+        hypergeometric::Hypergeometric, normal::Z_SCORE_TABLE, poisson::Poisson,
+    },
+    linear_algebra::solve_symmetric,
+    special::{
+        gammainc_upper_regularized, golden_section_min, ln_binomial_coefficient, normal_cdf,
+        normal_quantile,
+    },
+    Alternative, CheckAssumptions, DegreesOfFreedom, Diagnostic, TestResult,
+};
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CountAndProportion {
     pub count: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
     pub proportion: NormalizedF64,
+    /// Size of the finite population sampled without replacement, if known
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub population: Option<NonZeroUsize>,
 }
 impl CountAndProportion {
     pub fn is_normally_distributed_enough(&self) -> bool {
-        let a = 10. <= self.count as f64 * self.proportion.get();
-        let b = 10. <= self.count as f64 * (1. - self.proportion.get());
-        a && b
+        self.satisfies_assumptions()
     }
 
+    /// At least 10 expected successes and 10 expected failures for the normal approximation
+    pub fn normality_report(&self) -> Vec<Diagnostic> {
+        let n = self.count as f64;
+        let p = self.proportion.get();
+        vec![
+            Diagnostic::at_least("expected successes n p", n * p, MIN_EXPECTED_OUTCOMES),
+            Diagnostic::at_least(
+                "expected failures n (1 - p)",
+                n * (1. - p),
+                MIN_EXPECTED_OUTCOMES,
+            ),
+        ]
+    }
+
+    /// With the finite population correction if [`Self::population`] is known
     pub fn standard_error_squared(&self) -> f64 {
         self.proportion.get() * (1. - self.proportion.get()) / self.count as f64
+            * finite_population_correction(self.count, self.population)
     }
 }
 
+impl CheckAssumptions for CountAndProportion {
+    fn check_assumptions(&self) -> Vec<Diagnostic> {
+        self.normality_report()
+    }
+}
+
+/// Least expected successes and failures for the normal approximation of a proportion
+const MIN_EXPECTED_OUTCOMES: f64 = 10.;
+
+/// Least expected count of each cell for the chi-square approximation
+pub const MIN_EXPECTED_COUNT: f64 = 5.;
+
 pub fn one_proportion(sample: CountAndProportion, p_0: NormalizedF64) -> NormalizedF64 {
+    one_proportion_result(sample, p_0).p_value
+}
+
+/// [`one_proportion`] with the `z` statistic and Cohen's h
+pub fn one_proportion_result(sample: CountAndProportion, p_0: NormalizedF64) -> TestResult {
+    one_proportion_result_with(sample, p_0, ContinuityCorrection::None)
+}
+
+/// Whether a normal approximation of counts corrects for their discreteness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ContinuityCorrection {
+    None,
+    /// Shrink the difference by half a count, which brings the p-value closer to that of the exact test
+    Yates,
+}
+
+/// [`one_proportion_result`] with an option for the continuity correction
+///
+/// - [`ContinuityCorrection::Yates`] shrinks `|p - p_0|` by `0.5 / n`.
+pub fn one_proportion_result_with(
+    sample: CountAndProportion,
+    p_0: NormalizedF64,
+    continuity_correction: ContinuityCorrection,
+) -> TestResult {
     // Normality check
     assert!(sample.is_normally_distributed_enough());
 
     let standard_error = standard_error(&[CountAndProportion {
         count: sample.count,
         proportion: p_0,
+        population: sample.population,
     }]);
-    let z = (sample.proportion.get() - p_0.get()) / standard_error;
+    let mut difference = sample.proportion.get() - p_0.get();
+    if continuity_correction == ContinuityCorrection::Yates {
+        let correction = 0.5 / sample.count as f64;
+        difference = difference.signum() * (difference.abs() - correction).max(0.);
+    }
+    let z = difference / standard_error;
     let z = FiniteF64::new(z).unwrap();
-    Z_SCORE_TABLE.p_value_two_sided(z)
+    TestResult {
+        statistic: z,
+        df: None,
+        p_value: Z_SCORE_TABLE.p_value_two_sided(z),
+        alternative: Alternative::TwoSided,
+        effect_size: Some(cohens_h(sample.proportion, p_0)),
+        confidence_interval: None,
+    }
 }
 
 pub fn difference_of_two_proportions(
@@ -39,6 +122,15 @@ pub fn difference_of_two_proportions(
     sample_2: CountAndProportion,
     p_0: NormalizedF64,
 ) -> NormalizedF64 {
+    difference_of_two_proportions_result(sample_1, sample_2, p_0).p_value
+}
+
+/// [`difference_of_two_proportions`] with the `z` statistic and Cohen's h
+pub fn difference_of_two_proportions_result(
+    sample_1: CountAndProportion,
+    sample_2: CountAndProportion,
+    p_0: NormalizedF64,
+) -> TestResult {
     // Normality check
     assert!(sample_1.is_normally_distributed_enough());
     assert!(sample_2.is_normally_distributed_enough());
@@ -46,7 +138,97 @@ pub fn difference_of_two_proportions(
     let standard_error = standard_error(&[sample_1, sample_2]);
     let z = ((sample_1.proportion.get() - sample_2.proportion.get()) - p_0.get()) / standard_error;
     let z = FiniteF64::new(z).unwrap();
-    Z_SCORE_TABLE.p_value_two_sided(z)
+    TestResult {
+        statistic: z,
+        df: None,
+        p_value: Z_SCORE_TABLE.p_value_two_sided(z),
+        alternative: Alternative::TwoSided,
+        effect_size: Some(cohens_h(sample_1.proportion, sample_2.proportion)),
+        confidence_interval: None,
+    }
+}
+
+/// `2 asin(sqrt(p_1)) - 2 asin(sqrt(p_2))`
+fn cohens_h(p_1: NormalizedF64, p_2: NormalizedF64) -> FiniteF64 {
+    let h = 2. * p_1.get().sqrt().asin() - 2. * p_2.get().sqrt().asin();
+    FiniteF64::new(h).unwrap()
+}
+
+/// Standard error of the difference of two proportions in [`non_inferiority`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DifferenceStandardError {
+    /// From the observed proportions
+    Wald,
+    /// From the maximum likelihood proportions restricted to the null difference
+    ///
+    /// ref: Farrington, C. P., & Manning, G. (1990). Test statistics and sample size formulae for comparative binomial trials with null hypothesis of non-zero risk difference or non-unity relative risk.
+    FarringtonManning,
+}
+
+/// Non-inferiority test of the proportion of `treatment` against that of `reference`.
+///
+/// Null hypothesis: `p_treatment - p_reference <= -margin`, i.e., the treatment is worse by at least the margin.
+///
+/// - Rejecting the null hypothesis establishes non-inferiority.
+/// - The `z` statistic is `(p_treatment - p_reference + margin) / SE` and the p-value is one-sided.
+pub fn non_inferiority(
+    treatment: CountAndProportion,
+    reference: CountAndProportion,
+    margin: NormalizedF64,
+    standard_error: DifferenceStandardError,
+) -> TestResult {
+    assert!(treatment.count > 0);
+    assert!(reference.count > 0);
+    let (p_1, p_2) = (treatment.proportion.get(), reference.proportion.get());
+    let (n_1, n_2) = (treatment.count as f64, reference.count as f64);
+    let null_difference = -margin.get();
+
+    let (p_1_null, p_2_null) = match standard_error {
+        DifferenceStandardError::Wald => (p_1, p_2),
+        DifferenceStandardError::FarringtonManning => {
+            farrington_manning(p_1, n_1, p_2, n_2, null_difference)
+        }
+    };
+    let standard_error =
+        (p_1_null * (1. - p_1_null) / n_1 + p_2_null * (1. - p_2_null) / n_2).sqrt();
+    assert!(standard_error > 0.);
+    let z = (p_1 - p_2 - null_difference) / standard_error;
+    TestResult {
+        statistic: FiniteF64::new(z).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(normal_cdf(-z)).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: Some(cohens_h(treatment.proportion, reference.proportion)),
+        confidence_interval: None,
+    }
+}
+
+/// Maximum likelihood `(p_1, p_2)` subject to `p_1 - p_2 = difference`, the root of a cubic in closed form
+fn farrington_manning(p_1: f64, n_1: f64, p_2: f64, n_2: f64, difference: f64) -> (f64, f64) {
+    let theta = n_2 / n_1;
+    let a = 1. + theta;
+    let b = -(1. + theta + p_1 + theta * p_2 + difference * (theta + 2.));
+    let c = difference.powi(2) + difference * (2. * p_1 + theta + 1.) + p_1 + theta * p_2;
+    let d = -p_1 * difference * (1. + difference);
+    let v = b.powi(3) / (27. * a.powi(3)) - b * c / (6. * a.powi(2)) + d / (2. * a);
+    let u = v.signum() * (b.powi(2) / (9. * a.powi(2)) - c / (3. * a)).sqrt();
+    let w = (PI + (v / u.powi(3)).clamp(-1., 1.).acos()) / 3.;
+    let p_1_null = (2. * u * w.cos() - b / (3. * a)).clamp(0., 1.);
+    let p_2_null = (p_1_null - difference).clamp(0., 1.);
+    (p_1_null, p_2_null)
+}
+
+/// `(N - n) / (N - 1)`, the factor of the variance of a statistic sampled without replacement from `N`
+pub(crate) fn finite_population_correction(count: usize, population: Option<NonZeroUsize>) -> f64 {
+    let Some(population) = population else {
+        return 1.;
+    };
+    assert!(count <= population.get());
+    if population.get() == 1 {
+        return 0.;
+    }
+    (population.get() - count) as f64 / (population.get() - 1) as f64
 }
 
 fn standard_error(samples: &[CountAndProportion]) -> f64 {
@@ -84,9 +266,500 @@ pub fn min_count_of_each_of_two_samples(
     count.ceil() as usize
 }
 
+/// Determine a proper sample size for testing a single proportion against `p_0`.
+///
+/// `power`: probability that the alternative hypothesis is not confused as a null hypothesis
+///
+/// - usually in
+///   ```math
+///   [0.8, 0.9]
+///   ```
+pub fn min_count_one_proportion(
+    p_alt: NormalizedF64,
+    p_0: NormalizedF64,
+    power: NormalizedF64,
+    max_p_value: NormalizedF64,
+    alternative: Alternative,
+) -> usize {
+    let tail_p_value = match alternative {
+        Alternative::TwoSided => max_p_value.get() / 2.,
+        Alternative::Less => {
+            assert!(p_alt < p_0);
+            max_p_value.get()
+        }
+        Alternative::Greater => {
+            assert!(p_alt > p_0);
+            max_p_value.get()
+        }
+    };
+    let tail_p_value = NormalizedF64::new(tail_p_value).unwrap();
+    let power_region_extension = Z_SCORE_TABLE.z(power);
+    let reject_region_extension = Z_SCORE_TABLE.z(tail_p_value);
+    let null_deviation = (p_0.get() * (1. - p_0.get())).sqrt();
+    let alt_deviation = (p_alt.get() * (1. - p_alt.get())).sqrt();
+    let region = reject_region_extension.get() * null_deviation
+        - power_region_extension.get() * alt_deviation;
+    let diff = p_alt.get() - p_0.get();
+    let count = (region / diff).powi(2);
+    count.ceil() as usize
+}
+
+/// Exact test of an event rate.
+///
+/// Null hypothesis: events occur at `rate_0` per unit of exposure, so `count ~ Poisson(rate_0 * exposure)`.
+///
+/// - The two-sided p-value sums the probabilities of all counts no more likely than `count`.
+pub fn poisson_rate_test(
+    count: usize,
+    exposure: PositiveF64,
+    rate_0: PositiveF64,
+) -> NormalizedF64 {
+    poisson_rate_test_result(count, exposure, rate_0).p_value
+}
+
+/// [`poisson_rate_test`] with the count as the statistic
+pub fn poisson_rate_test_result(
+    count: usize,
+    exposure: PositiveF64,
+    rate_0: PositiveF64,
+) -> TestResult {
+    assert!(exposure.get() > 0.);
+    let expected = rate_0.get() * exposure.get();
+    let poisson = Poisson {
+        rate: PositiveF64::new(expected).unwrap(),
+    };
+    let threshold = poisson.pmf(count).get() * RELATIVE_TOLERANCE;
+    let p_value = if (count as f64) < expected {
+        // The first count in the upper tail no more likely than `count`
+        let mut upper = expected.ceil() as usize;
+        while poisson.pmf(upper).get() > threshold {
+            upper += 1;
+        }
+        poisson.cdf(count).get() + poisson.sf(upper - 1).get()
+    } else if (count as f64) > expected {
+        let lower_tail = (0..=expected.floor() as usize)
+            .take_while(|&k| poisson.pmf(k).get() <= threshold)
+            .last();
+        let lower_tail = lower_tail.map_or(0., |k| poisson.cdf(k).get());
+        lower_tail + poisson.sf(count - 1).get()
+    } else {
+        1.
+    };
+    TestResult {
+        statistic: FiniteF64::new(count as f64).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(p_value.min(1.)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// Exact comparison of two event rates, conditioning on the total count.
+///
+/// Null hypothesis: both samples have the same rate per unit of exposure, so `count_1 ~ Binomial(count_1 + count_2, exposure_1 / (exposure_1 + exposure_2))`.
+pub fn two_poisson_rates(
+    count_1: usize,
+    exposure_1: PositiveF64,
+    count_2: usize,
+    exposure_2: PositiveF64,
+) -> NormalizedF64 {
+    two_poisson_rates_result(count_1, exposure_1, count_2, exposure_2).p_value
+}
+
+/// [`two_poisson_rates`] with `count_1` as the statistic
+pub fn two_poisson_rates_result(
+    count_1: usize,
+    exposure_1: PositiveF64,
+    count_2: usize,
+    exposure_2: PositiveF64,
+) -> TestResult {
+    assert!(exposure_1.get() > 0.);
+    assert!(exposure_2.get() > 0.);
+    let binomial = Binomial {
+        trials: count_1 + count_2,
+        probability: NormalizedF64::new(exposure_1.get() / (exposure_1.get() + exposure_2.get()))
+            .unwrap(),
+    };
+    let threshold = binomial.pmf(count_1).get() * RELATIVE_TOLERANCE;
+    let p_value = (0..=binomial.trials)
+        .map(|k| binomial.pmf(k).get())
+        .filter(|&p| p <= threshold)
+        .sum::<f64>();
+    TestResult {
+        statistic: FiniteF64::new(count_1 as f64).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(p_value.min(1.)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// Comparison of two event rates per unit of exposure
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwoRateTest {
+    pub count_1: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub exposure_1: PositiveF64,
+    pub count_2: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub exposure_2: PositiveF64,
+    /// [`two_poisson_rates_result`]: exact test conditioning on the total count
+    pub conditional: TestResult,
+    /// Krishnamoorthy and Thomson's E-test: unconditional test of the standardized difference of the rates
+    ///
+    /// - More powerful than the conditional test.
+    pub e_test: TestResult,
+}
+impl TwoRateTest {
+    /// `(count_1 / exposure_1) / (count_2 / exposure_2)`, absent if `count_2` is zero
+    pub fn rate_ratio(&self) -> Option<PositiveF64> {
+        (self.count_2 > 0).then(|| {
+            let rate_1 = self.count_1 as f64 / self.exposure_1.get();
+            let rate_2 = self.count_2 as f64 / self.exposure_2.get();
+            PositiveF64::new(rate_1 / rate_2).unwrap()
+        })
+    }
+
+    /// Exact interval of the rate ratio from the Clopper-Pearson interval of `count_1` out of the total count
+    ///
+    /// - The upper bound is infinite if `count_2` is zero.
+    pub fn rate_ratio_interval(&self, confidence: NormalizedF64) -> (f64, f64) {
+        let count = self.count_1 + self.count_2;
+        assert!(count > 0);
+        let alpha = 1. - confidence.get();
+        let beta = |a: usize, b: usize, p: f64| {
+            let beta = Beta {
+                a: PositiveF64::new(a as f64).unwrap(),
+                b: PositiveF64::new(b as f64).unwrap(),
+            };
+            beta.quantile(NormalizedF64::new(p).unwrap()).get()
+        };
+        let lower = if self.count_1 == 0 {
+            0.
+        } else {
+            beta(self.count_1, self.count_2 + 1, alpha / 2.)
+        };
+        let upper = if self.count_2 == 0 {
+            1.
+        } else {
+            beta(self.count_1 + 1, self.count_2, 1. - alpha / 2.)
+        };
+        let exposure_ratio = self.exposure_2.get() / self.exposure_1.get();
+        let rate_ratio = |p: f64| {
+            if p == 1. {
+                f64::INFINITY
+            } else {
+                p / (1. - p) * exposure_ratio
+            }
+        };
+        (rate_ratio(lower), rate_ratio(upper))
+    }
+}
+
+/// Comparison of two event rates by both the conditional test and the E-test.
+///
+/// Null hypothesis: both samples have the same rate per unit of exposure.
+///
+/// - Panics unless both exposures are greater than zero.
+pub fn two_rate_test(
+    count_1: usize,
+    exposure_1: PositiveF64,
+    count_2: usize,
+    exposure_2: PositiveF64,
+) -> TwoRateTest {
+    assert!(exposure_1.get() > 0.);
+    assert!(exposure_2.get() > 0.);
+    let conditional = two_poisson_rates_result(count_1, exposure_1, count_2, exposure_2);
+    let e_test = poisson_e_test(count_1, exposure_1.get(), count_2, exposure_2.get());
+    TwoRateTest {
+        count_1,
+        exposure_1,
+        count_2,
+        exposure_2,
+        conditional,
+        e_test,
+    }
+}
+
+/// Sums the probabilities of all pairs of counts whose standardized difference is at least as extreme as the observed one, under the pooled rate
+fn poisson_e_test(count_1: usize, exposure_1: f64, count_2: usize, exposure_2: f64) -> TestResult {
+    let statistic = |k_1: usize, k_2: usize| {
+        let (k_1, k_2) = (k_1 as f64, k_2 as f64);
+        let variance = k_1 / exposure_1.powi(2) + k_2 / exposure_2.powi(2);
+        if variance == 0. {
+            return 0.;
+        }
+        (k_1 / exposure_1 - k_2 / exposure_2) / variance.sqrt()
+    };
+    let observed = statistic(count_1, count_2);
+    let threshold = observed.abs() / RELATIVE_TOLERANCE;
+
+    let pooled_rate = (count_1 + count_2) as f64 / (exposure_1 + exposure_2);
+    let p_value = if pooled_rate == 0. {
+        1.
+    } else {
+        let poisson = |exposure: f64| Poisson {
+            rate: PositiveF64::new(pooled_rate * exposure).unwrap(),
+        };
+        // Counts outside the central `1 - 2e-12` of each distribution are negligible
+        let support = |poisson: &Poisson| {
+            poisson.quantile(NormalizedF64::new(1e-12).unwrap())
+                ..=poisson.quantile(NormalizedF64::new(1. - 1e-12).unwrap())
+        };
+        let (poisson_1, poisson_2) = (poisson(exposure_1), poisson(exposure_2));
+        let pmf_2 = support(&poisson_2)
+            .map(|k_2| (k_2, poisson_2.pmf(k_2).get()))
+            .collect::<Vec<_>>();
+        let mut p_value = 0.;
+        for k_1 in support(&poisson_1) {
+            let pmf_1 = poisson_1.pmf(k_1).get();
+            for &(k_2, pmf_2) in &pmf_2 {
+                if threshold <= statistic(k_1, k_2).abs() {
+                    p_value += pmf_1 * pmf_2;
+                }
+            }
+        }
+        p_value
+    };
+    TestResult {
+        statistic: FiniteF64::new(observed).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(p_value.min(1.)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// Exact binomial test.
+///
+/// Null hypothesis: `successes ~ Binomial(trials, p_0)`.
+///
+/// - The two-sided p-value sums the probabilities of all counts no more likely than `successes`.
+/// - The effect size is Cohen's h of the observed proportion against `p_0`.
+pub fn binomial_test_result(successes: usize, trials: usize, p_0: NormalizedF64) -> TestResult {
+    binomial_test_result_with(successes, trials, p_0, ExactPValue::Exact)
+}
+
+/// How an exact test counts the outcomes as likely as the observed one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExactPValue {
+    /// In full
+    Exact,
+    /// By half, which is less conservative than the exact p-value
+    MidP,
+}
+
+/// [`binomial_test_result`] with a choice of the exact or the mid-p value
+pub fn binomial_test_result_with(
+    successes: usize,
+    trials: usize,
+    p_0: NormalizedF64,
+    p_value_method: ExactPValue,
+) -> TestResult {
+    assert!(trials > 0);
+    assert!(successes <= trials);
+    let binomial = Binomial {
+        trials,
+        probability: p_0,
+    };
+    let p_value = exact_p_value(
+        binomial.pmf(successes).get(),
+        (0..=trials).map(|k| binomial.pmf(k).get()),
+        p_value_method,
+    );
+    let proportion = NormalizedF64::new(successes as f64 / trials as f64).unwrap();
+    TestResult {
+        statistic: FiniteF64::new(successes as f64).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(p_value).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: Some(cohens_h(proportion, p_0)),
+        confidence_interval: None,
+    }
+}
+
+/// Fisher's exact test of a 2 × 2 table.
+///
+/// Null hypothesis: the row and the column variables are independent, so `table[0][0]` is hypergeometric given the margins.
+///
+/// - The two-sided p-value sums the probabilities of all tables no more likely than the observed one.
+/// - The effect size is the sample odds ratio, absent if it is undefined or infinite.
+pub fn fisher_exact_result(table: [[usize; 2]; 2]) -> TestResult {
+    fisher_exact_result_with(table, ExactPValue::Exact)
+}
+
+/// [`fisher_exact_result`] with a choice of the exact or the mid-p value
+pub fn fisher_exact_result_with(table: [[usize; 2]; 2], p_value_method: ExactPValue) -> TestResult {
+    TestResult {
+        statistic: FiniteF64::new(table[0][0] as f64).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(fisher_p_value(table, p_value_method)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: odds_ratio(table),
+        confidence_interval: None,
+    }
+}
+
+fn fisher_p_value(table: [[usize; 2]; 2], p_value_method: ExactPValue) -> f64 {
+    let [[a, b], [c, d]] = table;
+    let hypergeometric = Hypergeometric {
+        population: a + b + c + d,
+        successes: a + b,
+        draws: a + c,
+    };
+    let (min, max) = hypergeometric.support();
+    exact_p_value(
+        hypergeometric.pmf(a).get(),
+        (min..=max).map(|k| hypergeometric.pmf(k).get()),
+        p_value_method,
+    )
+}
+
+/// Sample odds ratio of a 2 × 2 table, absent if it is undefined or infinite
+fn odds_ratio(table: [[usize; 2]; 2]) -> Option<FiniteF64> {
+    let [[a, b], [c, d]] = table;
+    (b * c > 0).then(|| FiniteF64::new((a * d) as f64 / (b * c) as f64).unwrap())
+}
+
+/// Barnard's exact unconditional test of a 2 × 2 table.
+///
+/// Each row is an independent sample of `[successes, failures]`.
+///
+/// Null hypothesis: both rows share the same success probability.
+///
+/// - The statistic is the pooled z score of the difference in proportions.
+/// - The p-value is the largest probability of a z score at least as extreme over the common success probability.
+/// - The effect size is the sample odds ratio as in [`fisher_exact_result`].
+pub fn barnard_exact_result(table: [[usize; 2]; 2]) -> TestResult {
+    let [[a, b], [c, d]] = table;
+    let (n_1, n_2) = (a + b, c + d);
+    let z = |x_1: usize, x_2: usize| pooled_z(x_1, n_1, x_2, n_2);
+    let observed = z(a, c).abs();
+    let p_value = unconditional_p_value(n_1, n_2, |x_1, x_2| {
+        z(x_1, x_2).abs() * RELATIVE_TOLERANCE >= observed
+    });
+    TestResult {
+        statistic: FiniteF64::new(z(a, c)).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(p_value).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: odds_ratio(table),
+        confidence_interval: None,
+    }
+}
+
+/// Boschloo's exact unconditional test of a 2 × 2 table.
+///
+/// Each row is an independent sample of `[successes, failures]`.
+///
+/// Null hypothesis: both rows share the same success probability.
+///
+/// - The statistic is the p-value of [`fisher_exact_result`], and the test is never less powerful than it.
+/// - The p-value is the largest probability of a Fisher p-value at most as large over the common success probability.
+/// - The effect size is the sample odds ratio as in [`fisher_exact_result`].
+pub fn boschloo_exact_result(table: [[usize; 2]; 2]) -> TestResult {
+    let [[a, b], [c, d]] = table;
+    let (n_1, n_2) = (a + b, c + d);
+    let fisher = |x_1: usize, x_2: usize| {
+        fisher_p_value([[x_1, n_1 - x_1], [x_2, n_2 - x_2]], ExactPValue::Exact)
+    };
+    let observed = fisher(a, c);
+    let p_value = unconditional_p_value(n_1, n_2, |x_1, x_2| {
+        fisher(x_1, x_2) <= observed * RELATIVE_TOLERANCE
+    });
+    TestResult {
+        statistic: FiniteF64::new(observed).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(p_value).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: odds_ratio(table),
+        confidence_interval: None,
+    }
+}
+
+/// Pooled z score of `x_1 / n_1 - x_2 / n_2`, zero if every outcome is a success or every one is a failure
+fn pooled_z(x_1: usize, n_1: usize, x_2: usize, n_2: usize) -> f64 {
+    let (n_1, n_2) = (n_1 as f64, n_2 as f64);
+    let (x_1, x_2) = (x_1 as f64, x_2 as f64);
+    let p = (x_1 + x_2) / (n_1 + n_2);
+    let variance = p * (1. - p) * (1. / n_1 + 1. / n_2);
+    if variance == 0. {
+        return 0.;
+    }
+    (x_1 / n_1 - x_2 / n_2) / variance.sqrt()
+}
+
+/// Largest probability of the outcomes `(x_1, x_2)` where `is_extreme` over the common success probability of `Binomial(n_1, π)` and `Binomial(n_2, π)`
+fn unconditional_p_value(n_1: usize, n_2: usize, is_extreme: impl Fn(usize, usize) -> bool) -> f64 {
+    assert!(n_1 > 0);
+    assert!(n_2 > 0);
+    // The probability only depends on the total number of successes through `π`
+    let n = n_1 + n_2;
+    let mut ln_weights = vec![f64::NEG_INFINITY; n + 1];
+    for x_1 in 0..=n_1 {
+        for x_2 in 0..=n_2 {
+            if !is_extreme(x_1, x_2) {
+                continue;
+            }
+            let ln_weight = ln_binomial_coefficient(n_1, x_1) + ln_binomial_coefficient(n_2, x_2);
+            let total = &mut ln_weights[x_1 + x_2];
+            let max = total.max(ln_weight);
+            *total = max + ((*total - max).exp() + (ln_weight - max).exp()).ln();
+        }
+    }
+    let probability = |pi: f64| {
+        ln_weights
+            .iter()
+            .enumerate()
+            .filter(|(_, ln_weight)| ln_weight.is_finite())
+            .map(|(s, ln_weight)| {
+                (ln_weight + s as f64 * pi.ln() + (n - s) as f64 * (1. - pi).ln()).exp()
+            })
+            .sum::<f64>()
+    };
+
+    let step = 1. / NUISANCE_GRID as f64;
+    let best = (1..NUISANCE_GRID)
+        .map(|i| i as f64 * step)
+        .max_by(|a, b| probability(*a).partial_cmp(&probability(*b)).unwrap())
+        .unwrap();
+    let refined = golden_section_min(|pi| -probability(pi), best - step, best + step);
+    let p_value = probability(best).max(probability(refined));
+    p_value.min(1.)
+}
+
+/// Points of the grid over the nuisance success probability
+const NUISANCE_GRID: usize = 1000;
+
+/// Two-sided p-value summing the probabilities of all outcomes no more likely than the observed one
+fn exact_p_value(
+    observed: f64,
+    probabilities: impl Iterator<Item = f64>,
+    p_value_method: ExactPValue,
+) -> f64 {
+    let upper = observed * RELATIVE_TOLERANCE;
+    let lower = observed / RELATIVE_TOLERANCE;
+    let mid_p = p_value_method == ExactPValue::MidP;
+    let p_value = probabilities
+        .filter(|&p| p <= upper)
+        .map(|p| if mid_p && lower <= p { p / 2. } else { p })
+        .sum::<f64>();
+    p_value.min(1.)
+}
+
+/// Slack for outcomes as likely as the observed one despite rounding errors
+const RELATIVE_TOLERANCE: f64 = 1. + 1e-7;
+
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CountAndExpect {
     pub count: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
     pub expect: PositiveF64,
 }
 impl CountAndExpect {
@@ -96,64 +769,1103 @@ impl CountAndExpect {
     }
 }
 
+/// How to compute the p-value of a chi-square statistic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PValueMethod {
+    /// Chi-square distribution, which requires every expected count to be at least 5
+    Asymptotic,
+    /// Share of `n_sims` tables simulated under the null hypothesis with a statistic at least as large as the observed one
+    ///
+    /// - Valid for small expected counts.
+    /// - Panics without the `rand` feature.
+    MonteCarlo { n_sims: usize, seed: u64 },
+}
+
+/// Expected count of each category against [`MIN_EXPECTED_COUNT`] for the chi-square approximation in [`fitness`]
+pub fn fitness_report(catagories: &[CountAndExpect]) -> Vec<Diagnostic> {
+    catagories
+        .iter()
+        .enumerate()
+        .map(|(i, bin)| {
+            Diagnostic::at_least(
+                format!("expected count of category {i}"),
+                bin.expect.get(),
+                MIN_EXPECTED_COUNT,
+            )
+        })
+        .collect()
+}
+
 /// Null hypothesis: counts from each column is equal to their expected counts respectively
 pub fn fitness(catagories: &[CountAndExpect]) -> NormalizedF64 {
+    fitness_result(catagories).p_value
+}
+
+/// [`fitness`] with the chi-square statistic and Cohen's w
+pub fn fitness_result(catagories: &[CountAndExpect]) -> TestResult {
+    fitness_result_with(catagories, PValueMethod::Asymptotic)
+}
+
+/// [`fitness_result`] with a choice of how to compute the p-value
+///
+/// - The simulated counts follow a multinomial distribution with probabilities proportional to the expected counts.
+pub fn fitness_result_with(
+    catagories: &[CountAndExpect],
+    p_value_method: PValueMethod,
+) -> TestResult {
     let df = NonZeroUsize::new(catagories.len() - 1).unwrap();
 
-    // Normality check
-    catagories
+    let chi_square = catagories.iter().map(|bin| bin.z_squared()).sum::<f64>();
+    let total = catagories.iter().map(|bin| bin.count).sum::<usize>();
+    let cohens_w = (chi_square / total as f64).sqrt();
+    let p_value = match p_value_method {
+        PValueMethod::Asymptotic => {
+            // Normality check
+            assert!(fitness_report(catagories)
+                .iter()
+                .all(|diagnostic| diagnostic.is_satisfied()));
+            CHI_SQUARE_TABLE.p_value(df, chi_square)
+        }
+        #[cfg(not(feature = "rand"))]
+        PValueMethod::MonteCarlo { .. } => {
+            panic!("Monte Carlo p-values require the `rand` feature")
+        }
+        #[cfg(feature = "rand")]
+        PValueMethod::MonteCarlo { n_sims, seed } => {
+            simulated_fitness_p_value(catagories, total, chi_square, n_sims, seed)
+        }
+    };
+    TestResult {
+        statistic: FiniteF64::new(chi_square).unwrap(),
+        df: Some(DegreesOfFreedom::one(df.get())),
+        p_value,
+        alternative: Alternative::Greater,
+        effect_size: Some(FiniteF64::new(cohens_w).unwrap()),
+        confidence_interval: None,
+    }
+}
+
+#[cfg(feature = "rand")]
+fn simulated_fitness_p_value(
+    catagories: &[CountAndExpect],
+    total: usize,
+    chi_square: f64,
+    n_sims: usize,
+    seed: u64,
+) -> NormalizedF64 {
+    use rand::{distributions::WeightedIndex, rngs::StdRng, Rng, SeedableRng};
+
+    let expects = catagories
         .iter()
-        .for_each(|bin| assert!(bin.expect.get() >= 5.));
+        .map(|bin| bin.expect.get())
+        .collect::<Vec<_>>();
+    let categories = WeightedIndex::new(&expects).unwrap();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut counts = vec![0; catagories.len()];
+    simulated_p_value(chi_square, n_sims, || {
+        counts.iter_mut().for_each(|count| *count = 0);
+        (0..total).for_each(|_| counts[rng.sample(&categories)] += 1);
+        counts
+            .iter()
+            .zip(&expects)
+            .map(|(&count, expect)| (count as f64 - expect).powi(2) / expect)
+            .sum()
+    })
+}
 
-    let chi_square = catagories.iter().map(|bin| bin.z_squared()).sum();
-    CHI_SQUARE_TABLE.p_value(df, chi_square)
+/// `(1 + hits) / (1 + n_sims)`, counting the simulated statistics at least as large as the observed one
+#[cfg(feature = "rand")]
+fn simulated_p_value(
+    observed: f64,
+    n_sims: usize,
+    mut simulate: impl FnMut() -> f64,
+) -> NormalizedF64 {
+    assert!(n_sims > 0);
+    let hits = (0..n_sims)
+        .filter(|_| simulate() * RELATIVE_TOLERANCE >= observed)
+        .count();
+    NormalizedF64::new((1 + hits) as f64 / (1 + n_sims) as f64).unwrap()
 }
 
 /// Null hypothesis: the two variables are independent of each other
 pub fn two_way_table_independence<const R: usize, const C: usize>(
     matrix: &[[usize; C]; R],
 ) -> NormalizedF64 {
-    assert!(R >= 2);
-    assert!(C >= 2);
+    two_way_table_independence_result(matrix).p_value
+}
+
+/// [`two_way_table_independence`] with the chi-square statistic and Cramér's V
+pub fn two_way_table_independence_result<const R: usize, const C: usize>(
+    matrix: &[[usize; C]; R],
+) -> TestResult {
+    independence(R, C, |r, c| matrix[r][c], PValueMethod::Asymptotic)
+}
+
+/// [`two_way_table_independence_result`] on a table of any shape
+#[cfg(feature = "ndarray")]
+pub fn two_way_table_independence_view(matrix: ndarray::ArrayView2<usize>) -> TestResult {
+    let (rows, cols) = matrix.dim();
+    independence(rows, cols, |r, c| matrix[[r, c]], PValueMethod::Asymptotic)
+}
+
+/// Departure of one cell of a [`ContingencyTable`] from independence
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CellResidual {
+    /// Expected count under independence
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub expected: PositiveF64,
+    /// `(observed - expected) / sqrt(expected)`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub pearson: FiniteF64,
+    /// Pearson residual over its standard error, approximately standard normal under independence
+    ///
+    /// - Beyond about ±2 flags a cell that departs from independence.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub adjusted: FiniteF64,
+    /// Contribution to the chi-square statistic, the squared Pearson residual
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub contribution: PositiveF64,
+}
+
+/// Measure of association of a [`ContingencyTable`] with its asymptotic standard error
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Association {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub estimate: FiniteF64,
+    /// Standard error without assuming the null hypothesis
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub standard_error: PositiveF64,
+    /// `z` of the estimate against its standard error under the null hypothesis, with the confidence interval of the estimate from `standard_error`
+    ///
+    /// Null hypothesis: there is no association.
+    pub test: TestResult,
+}
+impl Association {
+    fn new(
+        estimate: f64,
+        standard_error: f64,
+        null_standard_error: f64,
+        confidence: NormalizedF64,
+    ) -> Self {
+        let z = if estimate == 0. {
+            0.
+        } else {
+            estimate / null_standard_error
+        };
+        let margin = normal_quantile(0.5 + confidence.get() / 2.) * standard_error;
+        let test = TestResult {
+            statistic: FiniteF64::new(z.clamp(f64::MIN, f64::MAX)).unwrap(),
+            df: None,
+            p_value: NormalizedF64::new(2. * normal_cdf(-z.abs())).unwrap(),
+            alternative: Alternative::TwoSided,
+            effect_size: None,
+            confidence_interval: Some((
+                FiniteF64::new(estimate - margin).unwrap(),
+                FiniteF64::new(estimate + margin).unwrap(),
+            )),
+        };
+        Self {
+            estimate: FiniteF64::new(estimate).unwrap(),
+            standard_error: PositiveF64::new(standard_error).unwrap(),
+            test,
+        }
+    }
+}
+
+/// Two-way table of counts of any shape, with a label for each row and column
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContingencyTable {
+    pub row_labels: Vec<String>,
+    pub col_labels: Vec<String>,
+    /// `counts[row][col]`
+    pub counts: Vec<Vec<usize>>,
+}
+impl ContingencyTable {
+    pub fn new(row_labels: Vec<String>, col_labels: Vec<String>, counts: Vec<Vec<usize>>) -> Self {
+        assert_eq!(counts.len(), row_labels.len());
+        counts
+            .iter()
+            .for_each(|row| assert_eq!(row.len(), col_labels.len()));
+        Self {
+            row_labels,
+            col_labels,
+            counts,
+        }
+    }
+
+    /// Tally raw observations of a row category and a column category.
+    ///
+    /// - Rows and columns are in the order their categories first appear.
+    /// - Each label is the category's `to_string`.
+    pub fn from_pairs<A, B>(iter: impl IntoIterator<Item = (A, B)>) -> Self
+    where
+        A: Hash + Eq + ToString,
+        B: Hash + Eq + ToString,
+    {
+        let mut row_indices = HashMap::new();
+        let mut col_indices = HashMap::new();
+        let mut row_labels = vec![];
+        let mut col_labels = vec![];
+        let mut counts: Vec<Vec<usize>> = vec![];
+        for (row, col) in iter {
+            let r = *row_indices.entry(row).or_insert_with_key(|row: &A| {
+                row_labels.push(row.to_string());
+                counts.push(vec![0; col_labels.len()]);
+                row_labels.len() - 1
+            });
+            let c = *col_indices.entry(col).or_insert_with_key(|col: &B| {
+                col_labels.push(col.to_string());
+                counts.iter_mut().for_each(|row| row.push(0));
+                col_labels.len() - 1
+            });
+            counts[r][c] += 1;
+        }
+        Self::new(row_labels, col_labels, counts)
+    }
+
+    /// Index of the row labeled `label`
+    pub fn row_index(&self, label: &str) -> Option<usize> {
+        self.row_labels
+            .iter()
+            .position(|row_label| row_label == label)
+    }
+
+    /// Index of the column labeled `label`
+    pub fn col_index(&self, label: &str) -> Option<usize> {
+        self.col_labels
+            .iter()
+            .position(|col_label| col_label == label)
+    }
+
+    /// Expected count of each cell against [`MIN_EXPECTED_COUNT`] for the chi-square approximation in [`Self::independence`]
+    ///
+    /// - An empty table has no expected counts and fails on its total instead.
+    pub fn expected_count_report(&self) -> Vec<Diagnostic> {
+        if self.total() == 0 {
+            return vec![Diagnostic::at_least("total count", 0., 1.)];
+        }
+        expected_count_report(&self.expected_counts(), |r, c| {
+            format!("({}, {})", self.row_labels[r], self.col_labels[c])
+        })
+    }
+
+    /// Residual of each cell under independence, `residuals[row][col]`, to see which cells drive [`Self::independence`]
+    pub fn residuals(&self) -> Vec<Vec<CellResidual>> {
+        let total = self.total() as f64;
+        let row_totals = self.row_totals();
+        let col_totals = self.col_totals();
+        let expected = self.expected_counts();
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(r, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(c, &count)| {
+                        let expected = expected[r][c];
+                        assert!(expected > 0.);
+                        let deviation = count as f64 - expected;
+                        let pearson = deviation / expected.sqrt();
+                        let variance = expected
+                            * (1. - row_totals[r] as f64 / total)
+                            * (1. - col_totals[c] as f64 / total);
+                        let adjusted = if variance > 0. {
+                            deviation / variance.sqrt()
+                        } else {
+                            0.
+                        };
+                        CellResidual {
+                            expected: PositiveF64::new(expected).unwrap(),
+                            pearson: FiniteF64::new(pearson).unwrap(),
+                            adjusted: FiniteF64::new(adjusted).unwrap(),
+                            contribution: PositiveF64::new(pearson.powi(2)).unwrap(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Sum of all counts
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// Sum of the counts in each row
+    pub fn row_totals(&self) -> Vec<usize> {
+        self.counts.iter().map(|row| row.iter().sum()).collect()
+    }
+
+    /// Sum of the counts in each column
+    pub fn col_totals(&self) -> Vec<usize> {
+        (0..self.col_labels.len())
+            .map(|c| self.counts.iter().map(|row| row[c]).sum())
+            .collect()
+    }
+
+    /// Expected count of each cell under independence, `expected[row][col]`
+    ///
+    /// - `row total * column total / grand total`
+    pub fn expected_counts(&self) -> Vec<Vec<f64>> {
+        let total = self.total() as f64;
+        let col_totals = self.col_totals();
+        self.row_totals()
+            .iter()
+            .map(|&row_total| {
+                col_totals
+                    .iter()
+                    .map(|&col_total| row_total as f64 * col_total as f64 / total)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Distribution of each row across the columns, `profiles[row][col]`
+    pub fn row_profiles(&self) -> Vec<Vec<NormalizedF64>> {
+        self.counts
+            .iter()
+            .zip(self.row_totals())
+            .map(|(row, row_total)| {
+                assert!(row_total > 0);
+                row.iter()
+                    .map(|&count| NormalizedF64::new(count as f64 / row_total as f64).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Distribution of each column across the rows, `profiles[col][row]`
+    pub fn col_profiles(&self) -> Vec<Vec<NormalizedF64>> {
+        self.col_totals()
+            .into_iter()
+            .enumerate()
+            .map(|(c, col_total)| {
+                assert!(col_total > 0);
+                self.counts
+                    .iter()
+                    .map(|row| NormalizedF64::new(row[c] as f64 / col_total as f64).unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Goodman–Kruskal gamma of ordered rows and columns, `(concordant - discordant) / (concordant + discordant)` over pairs untied on both
+    pub fn gamma(&self, confidence: NormalizedF64) -> Association {
+        let (agree, disagree) = self.concordance();
+        let (p, q) = self.weighted_sums(&agree, &disagree);
+        assert!(p + q > 0.);
+        let gamma = (p - q) / (p + q);
+        let sum = self.weighted_sum(|r, c| (q * agree[r][c] - p * disagree[r][c]).powi(2));
+        let standard_error = 4. / (p + q).powi(2) * sum.sqrt();
+        let null_standard_error = 2. / (p + q) * self.null_deviation(&agree, &disagree, p - q);
+        Association::new(gamma, standard_error, null_standard_error, confidence)
+    }
+
+    /// Somers' D of the columns given the rows, both ordered: `(concordant - discordant)` over pairs untied on the rows
+    pub fn somers_d(&self, confidence: NormalizedF64) -> Association {
+        let (agree, disagree) = self.concordance();
+        let (p, q) = self.weighted_sums(&agree, &disagree);
+        let n = self.total() as f64;
+        let row_totals = self.row_totals();
+        let untied = n.powi(2) - row_totals.iter().map(|&t| (t as f64).powi(2)).sum::<f64>();
+        assert!(untied > 0.);
+        let d = (p - q) / untied;
+        let sum = self.weighted_sum(|r, c| {
+            (untied * (agree[r][c] - disagree[r][c]) - (p - q) * (n - row_totals[r] as f64)).powi(2)
+        });
+        let standard_error = 2. / untied.powi(2) * sum.sqrt();
+        let null_standard_error = 2. / untied * self.null_deviation(&agree, &disagree, p - q);
+        Association::new(d, standard_error, null_standard_error, confidence)
+    }
+
+    /// Goodman–Kruskal lambda of the columns given the rows: the proportional reduction in errors predicting the column when the row is known
+    ///
+    /// - Lambda has no standard error under the null hypothesis, so `test` uses `standard_error` as well.
+    pub fn lambda(&self, confidence: NormalizedF64) -> Association {
+        let n = self.total() as f64;
+        let col_totals = self.col_totals();
+        let (max_col, &col_max) = col_totals
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, &total)| total)
+            .unwrap();
+        let col_max = col_max as f64;
+        assert!(col_max < n);
+        let row_maxes = self
+            .counts
+            .iter()
+            .map(|row| {
+                let (c, &max) = row
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, &count)| count)
+                    .unwrap();
+                (c, max as f64)
+            })
+            .collect::<Vec<_>>();
+        let sum_of_maxes = row_maxes.iter().map(|(_, max)| max).sum::<f64>();
+        let sum_in_max_col = row_maxes
+            .iter()
+            .filter(|(c, _)| *c == max_col)
+            .map(|(_, max)| max)
+            .sum::<f64>();
+        let lambda = (sum_of_maxes - col_max) / (n - col_max);
+        let variance = (n - sum_of_maxes) * (sum_of_maxes + col_max - 2. * sum_in_max_col)
+            / (n - col_max).powi(3);
+        let standard_error = variance.max(0.).sqrt();
+        Association::new(lambda, standard_error, standard_error, confidence)
+    }
+
+    /// Counts of the cells concordant and discordant with each cell, `[row][col]`
+    fn concordance(&self) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+        let rows = self.row_labels.len();
+        let cols = self.col_labels.len();
+        let sum = |rows: std::ops::Range<usize>, cols: std::ops::Range<usize>| {
+            self.counts[rows]
+                .iter()
+                .map(|row| row[cols.clone()].iter().sum::<usize>())
+                .sum::<usize>() as f64
+        };
+        let mut agree = vec![vec![0.; cols]; rows];
+        let mut disagree = vec![vec![0.; cols]; rows];
+        for r in 0..rows {
+            for c in 0..cols {
+                agree[r][c] = sum(r + 1..rows, c + 1..cols) + sum(0..r, 0..c);
+                disagree[r][c] = sum(r + 1..rows, 0..c) + sum(0..r, c + 1..cols);
+            }
+        }
+        (agree, disagree)
+    }
+
+    /// `sqrt(sum n (C - D)^2 - (P - Q)^2 / n)`, shared by the null standard errors of [`Self::gamma`] and [`Self::somers_d`]
+    fn null_deviation(&self, agree: &[Vec<f64>], disagree: &[Vec<f64>], difference: f64) -> f64 {
+        let sum = self.weighted_sum(|r, c| (agree[r][c] - disagree[r][c]).powi(2));
+        (sum - difference.powi(2) / self.total() as f64)
+            .max(0.)
+            .sqrt()
+    }
+
+    /// Twice the concordant and twice the discordant pairs
+    fn weighted_sums(&self, agree: &[Vec<f64>], disagree: &[Vec<f64>]) -> (f64, f64) {
+        (
+            self.weighted_sum(|r, c| agree[r][c]),
+            self.weighted_sum(|r, c| disagree[r][c]),
+        )
+    }
+
+    /// Sum of `f(row, col)` weighted by the count of each cell
+    fn weighted_sum(&self, f: impl Fn(usize, usize) -> f64) -> f64 {
+        self.counts
+            .iter()
+            .enumerate()
+            .flat_map(|(r, row)| row.iter().enumerate().map(move |(c, &count)| (r, c, count)))
+            .map(|(r, c, count)| count as f64 * f(r, c))
+            .sum()
+    }
+
+    /// [`two_way_table_independence_result`] on this table
+    pub fn independence(&self) -> TestResult {
+        self.independence_with(PValueMethod::Asymptotic)
+    }
+
+    /// [`Self::independence`] with a choice of how to compute the p-value
+    ///
+    /// - The simulated tables keep the row and column totals.
+    pub fn independence_with(&self, p_value_method: PValueMethod) -> TestResult {
+        independence(
+            self.row_labels.len(),
+            self.col_labels.len(),
+            |r, c| self.counts[r][c],
+            p_value_method,
+        )
+    }
+}
 
-    let mut row_total = [0; R];
-    let mut col_total = [0; C];
+impl CheckAssumptions for ContingencyTable {
+    fn check_assumptions(&self) -> Vec<Diagnostic> {
+        self.expected_count_report()
+    }
+}
+
+/// Expected count of each cell, `expected[row][col]`, against [`MIN_EXPECTED_COUNT`], with the cell named by `cell_name(row, col)`
+fn expected_count_report(
+    expected: &[Vec<f64>],
+    cell_name: impl Fn(usize, usize) -> String,
+) -> Vec<Diagnostic> {
+    let mut report = vec![];
+    for (r, row) in expected.iter().enumerate() {
+        for (c, &expected) in row.iter().enumerate() {
+            report.push(Diagnostic::at_least(
+                format!("expected count of cell {}", cell_name(r, c)),
+                expected,
+                MIN_EXPECTED_COUNT,
+            ));
+        }
+    }
+    report
+}
+
+/// Chi-square test of independence on a `rows` by `cols` table with counts from `cell`
+pub(crate) fn independence(
+    rows: usize,
+    cols: usize,
+    cell: impl Fn(usize, usize) -> usize,
+    p_value_method: PValueMethod,
+) -> TestResult {
+    assert!(rows >= 2);
+    assert!(cols >= 2);
+
+    let mut row_total = vec![0; rows];
+    let mut col_total = vec![0; cols];
     let mut table_total = 0;
-    (0..R).for_each(|r| {
-        (0..C).for_each(|c| {
-            let cell = matrix[r][c];
+    (0..rows).for_each(|r| {
+        (0..cols).for_each(|c| {
+            let cell = cell(r, c);
             row_total[r] += cell;
             col_total[c] += cell;
             table_total += cell;
         });
     });
+    assert!(table_total > 0);
 
-    let mut expect = [[PositiveF64::new(0.).unwrap(); C]; R];
-    (0..R).for_each(|r| {
-        (0..C).for_each(|c| {
-            let cell_expect = (row_total[r] * col_total[c]) as f64 / table_total as f64;
+    let df = NonZeroUsize::new((rows - 1) * (cols - 1)).unwrap();
+    let expect =
+        |r: usize, c: usize| row_total[r] as f64 * col_total[c] as f64 / table_total as f64;
+    let statistic = |cell: &dyn Fn(usize, usize) -> usize| {
+        let mut chi_square = 0.;
+        (0..rows).for_each(|r| {
+            (0..cols).for_each(|c| {
+                let bin = CountAndExpect {
+                    count: cell(r, c),
+                    expect: PositiveF64::new(expect(r, c)).unwrap(),
+                };
+                chi_square += bin.z_squared();
+            });
+        });
+        chi_square
+    };
+    let chi_square = statistic(&cell);
 
+    let p_value = match p_value_method {
+        PValueMethod::Asymptotic => {
             // Normality check
-            assert!(cell_expect >= 5.);
+            let expected = (0..rows)
+                .map(|r| (0..cols).map(|c| expect(r, c)).collect())
+                .collect::<Vec<_>>();
+            assert!(
+                expected_count_report(&expected, |r, c| format!("({r}, {c})"))
+                    .iter()
+                    .all(|diagnostic| diagnostic.is_satisfied())
+            );
+            CHI_SQUARE_TABLE.p_value(df, chi_square)
+        }
+        #[cfg(not(feature = "rand"))]
+        PValueMethod::MonteCarlo { .. } => {
+            panic!("Monte Carlo p-values require the `rand` feature")
+        }
+        #[cfg(feature = "rand")]
+        PValueMethod::MonteCarlo { n_sims, seed } => {
+            use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 
-            expect[r][c] = PositiveF64::new(cell_expect).unwrap();
-        });
-    });
+            // Shuffling the column of each unit against the fixed rows keeps both margins
+            let units_rows = (0..rows)
+                .flat_map(|r| std::iter::repeat_n(r, row_total[r]))
+                .collect::<Vec<_>>();
+            let mut units_cols = (0..cols)
+                .flat_map(|c| std::iter::repeat_n(c, col_total[c]))
+                .collect::<Vec<_>>();
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut table = vec![vec![0; cols]; rows];
+            simulated_p_value(chi_square, n_sims, || {
+                units_cols.shuffle(&mut rng);
+                table.iter_mut().flatten().for_each(|cell| *cell = 0);
+                units_rows
+                    .iter()
+                    .zip(&units_cols)
+                    .for_each(|(&r, &c)| table[r][c] += 1);
+                statistic(&|r, c| table[r][c])
+            })
+        }
+    };
+
+    let cramers_v = (chi_square / (table_total * (rows.min(cols) - 1)) as f64).sqrt();
+    TestResult {
+        statistic: FiniteF64::new(chi_square).unwrap(),
+        df: Some(DegreesOfFreedom::one(df.get())),
+        p_value,
+        alternative: Alternative::Greater,
+        effect_size: Some(FiniteF64::new(cramers_v).unwrap()),
+        confidence_interval: None,
+    }
+}
 
-    let df = NonZeroUsize::new((R - 1) * (C - 1)).unwrap();
+/// Cochran–Armitage test for a trend in proportions across ordered groups.
+///
+/// Null hypothesis: the proportion of cases is the same in every group.
+///
+/// - `counts[i]`: `[cases, non-cases]` of group `i`
+/// - `scores[i]`: the dose or level of group `i`, e.g., `0, 1, 2, ...`
+/// - The `z` statistic is positive when the proportion of cases increases with the scores; the p-value is two-sided.
+pub fn trend_test(counts: &[[usize; 2]], scores: &[f64]) -> TestResult {
+    let trend = Trend::new(counts, scores);
+    let z = trend.z();
+    TestResult {
+        statistic: FiniteF64::new(z).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(2. * normal_cdf(-z.abs())).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// [`trend_test`] with the exact permutation p-value, conditional on the group sizes and the total count of cases.
+///
+/// - The p-value sums the probabilities of allocations of the cases to groups whose `sum scores[i] cases[i]` is at least as far from its mean as the observed one.
+pub fn trend_test_exact(counts: &[[usize; 2]], scores: &[f64]) -> TestResult {
+    let trend = Trend::new(counts, scores);
+    let total = trend.sizes.iter().sum::<usize>();
+    // Allocating the non-cases instead only reflects the score sum, and is faster when they are fewer
+    let allocated = if trend.cases.iter().sum::<usize>() * 2 <= total {
+        trend.cases.clone()
+    } else {
+        counts.iter().map(|[_, non_cases]| *non_cases).collect()
+    };
+    let total_allocated = allocated.iter().sum::<usize>();
+
+    // Distribution of `(allocated so far, score sum so far)` over the groups so far, weighted by the count of ways
+    let mut ln_ways = HashMap::from([((0, 0), (0., 0.))]);
+    for (&size, &score) in trend.sizes.iter().zip(scores) {
+        let ln_choose = (0..=size.min(total_allocated))
+            .map(|k| ln_binomial_coefficient(size, k))
+            .collect::<Vec<_>>();
+        let mut next = HashMap::new();
+        for (&(cases, _), &(sum, ln_weight)) in &ln_ways {
+            for (k, ln_choose) in ln_choose
+                .iter()
+                .enumerate()
+                .take(total_allocated - cases + 1)
+            {
+                let sum = sum + score * k as f64;
+                let ln_weight = ln_weight + ln_choose;
+                let key = (cases + k, (sum * SCORE_SUM_RESOLUTION).round() as i64);
+                let entry = next.entry(key).or_insert((sum, f64::NEG_INFINITY));
+                entry.1 = ln_add_exp(entry.1, ln_weight);
+            }
+        }
+        ln_ways = next;
+    }
+    let ln_total_ways = ln_binomial_coefficient(total, total_allocated);
+    let distribution = ln_ways
+        .into_iter()
+        .filter(|((cases, _), _)| *cases == total_allocated)
+        .map(|(_, (sum, ln_weight))| (sum, (ln_weight - ln_total_ways).exp()))
+        .collect::<Vec<_>>();
+
+    let mean = distribution.iter().map(|(sum, p)| sum * p).sum::<f64>();
+    let observed = allocated
+        .iter()
+        .zip(scores)
+        .map(|(&cases, score)| cases as f64 * score)
+        .sum::<f64>();
+    let p_value = distribution
+        .iter()
+        .filter(|(sum, _)| (sum - mean).abs() * RELATIVE_TOLERANCE >= (observed - mean).abs())
+        .map(|(_, p)| p)
+        .sum::<f64>();
+    TestResult {
+        statistic: FiniteF64::new(trend.z()).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(p_value.min(1.)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
 
-    let mut chi_square = 0.;
-    (0..R).for_each(|r| {
-        (0..C).for_each(|c| {
-            let bin = CountAndExpect {
-                count: matrix[r][c],
-                expect: expect[r][c],
+/// Score sums closer than its reciprocal are taken as equal in [`trend_test_exact`]
+const SCORE_SUM_RESOLUTION: f64 = 1e9;
+
+struct Trend<'a> {
+    cases: Vec<usize>,
+    sizes: Vec<usize>,
+    scores: &'a [f64],
+}
+impl<'a> Trend<'a> {
+    fn new(counts: &[[usize; 2]], scores: &'a [f64]) -> Self {
+        assert!(counts.len() >= 2);
+        assert_eq!(counts.len(), scores.len());
+        Self {
+            cases: counts.iter().map(|[cases, _]| *cases).collect(),
+            sizes: counts
+                .iter()
+                .map(|[cases, non_cases]| cases + non_cases)
+                .collect(),
+            scores,
+        }
+    }
+
+    /// `sum s_i (r_i - n_i p) / sqrt(p (1 - p) (sum n_i s_i^2 - (sum n_i s_i)^2 / N))`
+    fn z(&self) -> f64 {
+        let total = self.sizes.iter().sum::<usize>() as f64;
+        let p = self.cases.iter().sum::<usize>() as f64 / total;
+        let mut statistic = 0.;
+        let mut sum_of_squares = 0.;
+        let mut sum = 0.;
+        for ((&cases, &size), &score) in self.cases.iter().zip(&self.sizes).zip(self.scores) {
+            statistic += score * (cases as f64 - size as f64 * p);
+            sum_of_squares += size as f64 * score.powi(2);
+            sum += size as f64 * score;
+        }
+        let variance = p * (1. - p) * (sum_of_squares - sum.powi(2) / total);
+        assert!(variance > 0.);
+        statistic / variance.sqrt()
+    }
+}
+
+/// `ln(e^a + e^b)`
+fn ln_add_exp(a: f64, b: f64) -> f64 {
+    let max = a.max(b);
+    if max == f64::NEG_INFINITY {
+        return max;
+    }
+    max + ((a - max).exp() + (b - max).exp()).ln()
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MantelHaenszel {
+    /// Mantel–Haenszel estimate of the odds ratio common to all strata
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub odds_ratio: PositiveF64,
+    /// Confidence interval of the common odds ratio from the Robins–Breslow–Greenland variance of its logarithm
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub odds_ratio_interval: (PositiveF64, PositiveF64),
+    /// Mantel–Haenszel chi-square test without continuity correction
+    ///
+    /// Null hypothesis: the common odds ratio is 1.
+    pub test: TestResult,
+    /// Breslow–Day test
+    ///
+    /// Null hypothesis: the odds ratio is the same in every stratum.
+    pub homogeneity: TestResult,
+}
+
+/// Mantel–Haenszel analysis of a series of 2×2 tables.
+///
+/// - `strata[k]`: `[[a, b], [c, d]]` of stratum `k`, where the odds ratio is `ad / bc`
+/// - `confidence`: of the interval of the common odds ratio, e.g. 0.95
+/// - The Breslow–Day test leaves out strata with a zero margin, along with their degree of freedom.
+pub fn mantel_haenszel(strata: &[[[usize; 2]; 2]], confidence: NormalizedF64) -> MantelHaenszel {
+    assert!(!strata.is_empty());
+    let strata = strata
+        .iter()
+        .map(|&[[a, b], [c, d]]| {
+            let n = a + b + c + d;
+            assert!(n >= 2);
+            [a, b, c, d, n].map(|x| x as f64)
+        })
+        .collect::<Vec<_>>();
+
+    // ref: Robins, J., Breslow, N., & Greenland, S. (1986). Estimators of the Mantel-Haenszel variance consistent in both sparse data and large-strata limiting models.
+    let (mut r_sum, mut s_sum) = (0., 0.);
+    let (mut pr, mut ps_qr, mut qs) = (0., 0., 0.);
+    for &[a, b, c, d, n] in &strata {
+        let (p, q) = ((a + d) / n, (b + c) / n);
+        let (r, s) = (a * d / n, b * c / n);
+        r_sum += r;
+        s_sum += s;
+        pr += p * r;
+        ps_qr += p * s + q * r;
+        qs += q * s;
+    }
+    assert!(r_sum > 0. && s_sum > 0.);
+    let odds_ratio = r_sum / s_sum;
+    let ln_variance =
+        pr / (2. * r_sum.powi(2)) + ps_qr / (2. * r_sum * s_sum) + qs / (2. * s_sum.powi(2));
+    let z = normal_quantile(0.5 + confidence.get() / 2.);
+    let margin = z * ln_variance.sqrt();
+    let odds_ratio_interval = (
+        PositiveF64::new(odds_ratio * (-margin).exp()).unwrap(),
+        PositiveF64::new(odds_ratio * margin.exp()).unwrap(),
+    );
+
+    let (mut observed, mut expected, mut variance) = (0., 0., 0.);
+    for &[a, b, c, d, n] in &strata {
+        observed += a;
+        expected += (a + b) * (a + c) / n;
+        variance += (a + b) * (c + d) * (a + c) * (b + d) / (n.powi(2) * (n - 1.));
+    }
+    let chi_square = (observed - expected).powi(2) / variance;
+    let test = chi_square_result(chi_square, 1);
+
+    let breslow_day = strata
+        .iter()
+        .filter_map(|&[a, b, c, _, n]| {
+            let (row, col) = (a + b, a + c);
+            // A zero margin fixes the table
+            if [row, col, n - row, n - col].contains(&0.) {
+                return None;
+            }
+            // `A (n - row - col + A) = odds_ratio (row - A) (col - A)`, with `A` within the margins
+            let quadratic = 1. - odds_ratio;
+            let linear = n - row - col + odds_ratio * (row + col);
+            let constant = -odds_ratio * row * col;
+            let lower = (row + col - n).max(0.);
+            let upper = row.min(col);
+            let expected_a = if quadratic.abs() < f64::EPSILON {
+                -constant / linear
+            } else {
+                let root = (linear.powi(2) - 4. * quadratic * constant).max(0.).sqrt();
+                let outside = |x: f64| (lower - x).max(x - upper).max(0.);
+                let [first, second] = [
+                    (-linear + root) / (2. * quadratic),
+                    (-linear - root) / (2. * quadratic),
+                ];
+                // Rounding can leave the right root just outside the margins
+                if outside(first) <= outside(second) {
+                    first
+                } else {
+                    second
+                }
             };
-            chi_square += bin.z_squared();
-        });
-    });
+            let expected_a = expected_a.clamp(lower, upper);
+            let cells = [
+                expected_a,
+                row - expected_a,
+                col - expected_a,
+                n - row - col + expected_a,
+            ];
+            let variance = 1. / cells.iter().map(|x| 1. / x).sum::<f64>();
+            (variance > 0.).then(|| (a - expected_a).powi(2) / variance)
+        })
+        .collect::<Vec<_>>();
+    let homogeneity = match breslow_day.len().saturating_sub(1) {
+        0 => TestResult {
+            statistic: FiniteF64::new(0.).unwrap(),
+            df: None,
+            p_value: NormalizedF64::new(1.).unwrap(),
+            alternative: Alternative::Greater,
+            effect_size: None,
+            confidence_interval: None,
+        },
+        df => chi_square_result(breslow_day.iter().sum(), df),
+    };
+
+    MantelHaenszel {
+        odds_ratio: PositiveF64::new(odds_ratio).unwrap(),
+        odds_ratio_interval,
+        test,
+        homogeneity,
+    }
+}
+
+/// McNemar test of paired binary outcomes, without continuity correction.
+///
+/// Null hypothesis: the two outcomes are equally likely to be positive.
+///
+/// - `discordant_1`: pairs positive only on the first outcome
+/// - `discordant_2`: pairs positive only on the second outcome
+pub fn mcnemar(discordant_1: usize, discordant_2: usize) -> TestResult {
+    let discordant = discordant_1 + discordant_2;
+    assert!(discordant > 0);
+    let chi_square = (discordant_1 as f64 - discordant_2 as f64).powi(2) / discordant as f64;
+    chi_square_result(chi_square, 1)
+}
+
+/// Exact McNemar test: [`binomial_test_result_with`] on `discordant_1` out of all discordant pairs with probability one half.
+pub fn mcnemar_exact(
+    discordant_1: usize,
+    discordant_2: usize,
+    p_value_method: ExactPValue,
+) -> TestResult {
+    let discordant = discordant_1 + discordant_2;
+    assert!(discordant > 0);
+    let mut result = binomial_test_result_with(
+        discordant_1,
+        discordant,
+        NormalizedF64::new(0.5).unwrap(),
+        p_value_method,
+    );
+    result.effect_size = None;
+    result
+}
+
+/// Stuart–Maxwell test of marginal homogeneity of paired outcomes with `k` categories, generalizing [`mcnemar`].
+///
+/// Null hypothesis: the first and the second outcome share the same distribution over the categories.
+///
+/// - `table[i][j]`: pairs with category `i` on the first outcome and `j` on the second
+/// - The statistic is chi-square with `k - 1` degrees of freedom.
+/// - Categories without discordant pairs are left out, along with their degree of freedom.
+/// - Groups of categories without discordant pairs between them each drop one category, so the degrees of freedom are the rank of the covariance of the marginal differences.
+pub fn stuart_maxwell(table: &[Vec<usize>]) -> TestResult {
+    let k = square_size(table);
+    let row_total = |i: usize| table[i].iter().sum::<usize>() as f64;
+    let col_total = |i: usize| table.iter().map(|row| row[i]).sum::<usize>() as f64;
+    let variance = |i: usize| row_total(i) + col_total(i) - 2. * table[i][i] as f64;
+    let discordant = (0..k).filter(|&i| variance(i) > 0.).collect::<Vec<_>>();
+    assert!(!discordant.is_empty());
+    let categories = all_but_one_per_linked_group(table, &discordant);
+    let difference = categories
+        .iter()
+        .map(|&i| row_total(i) - col_total(i))
+        .collect::<Vec<_>>();
+    let covariance = categories
+        .iter()
+        .map(|&i| {
+            categories
+                .iter()
+                .map(|&j| {
+                    if i == j {
+                        variance(i)
+                    } else {
+                        -((table[i][j] + table[j][i]) as f64)
+                    }
+                })
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    let solution = solve_symmetric(&covariance, &difference)
+        .expect("leaving out one category of each linked group should remove the singularity");
+    let chi_square = difference
+        .iter()
+        .zip(&solution)
+        .map(|(d, x)| d * x)
+        .sum::<f64>();
+    chi_square_result(chi_square, categories.len())
+}
+
+/// `categories` without the last of each group linked by discordant pairs, whose marginal difference is redundant given the rest of its group
+fn all_but_one_per_linked_group(table: &[Vec<usize>], categories: &[usize]) -> Vec<usize> {
+    let linked = |i: usize, j: usize| table[i][j] + table[j][i] > 0;
+    let mut group = vec![None; table.len()];
+    let mut groups = 0;
+    for &start in categories {
+        if group[start].is_some() {
+            continue;
+        }
+        group[start] = Some(groups);
+        let mut stack = vec![start];
+        while let Some(i) = stack.pop() {
+            for &j in categories {
+                if group[j].is_none() && linked(i, j) {
+                    group[j] = Some(groups);
+                    stack.push(j);
+                }
+            }
+        }
+        groups += 1;
+    }
+    categories
+        .iter()
+        .copied()
+        .filter(|&i| categories.iter().any(|&j| j > i && group[j] == group[i]))
+        .collect()
+}
+
+/// Bowker's test of symmetry of paired outcomes with `k` categories, generalizing [`mcnemar`].
+///
+/// Null hypothesis: `table[i][j]` and `table[j][i]` are equally likely for every `i != j`.
+///
+/// - `table[i][j]`: pairs with category `i` on the first outcome and `j` on the second
+/// - Pairs of cells without any count are left out, along with their degree of freedom.
+pub fn bowker(table: &[Vec<usize>]) -> TestResult {
+    let k = square_size(table);
+    let discordant = (0..k)
+        .flat_map(|i| (i + 1..k).map(move |j| (table[i][j], table[j][i])))
+        .filter(|(upper, lower)| upper + lower > 0)
+        .collect::<Vec<_>>();
+    assert!(!discordant.is_empty());
+    let chi_square = discordant
+        .iter()
+        .map(|&(upper, lower)| (upper as f64 - lower as f64).powi(2) / (upper + lower) as f64)
+        .sum::<f64>();
+    chi_square_result(chi_square, discordant.len())
+}
+
+fn square_size(table: &[Vec<usize>]) -> usize {
+    let k = table.len();
+    assert!(k >= 2);
+    assert!(table.iter().all(|row| row.len() == k));
+    k
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CochranQ {
+    /// Null hypothesis: every treatment has the same probability of a positive outcome.
+    pub test: TestResult,
+    /// [`mcnemar`] on every pair of treatments, without adjustment for multiple comparisons
+    pub pairwise: Vec<PairwiseComparison>,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PairwiseComparison {
+    /// Indices of the two compared groups
+    pub groups: (usize, usize),
+    pub test: TestResult,
+}
+
+/// Cochran's Q test for binary outcomes of the same subjects under several treatments.
+///
+/// - `matrix[i][j]`: whether the outcome of subject `i` under treatment `j` is positive
+/// - The p-value is from the chi-square approximation with `k - 1` degrees of freedom.
+pub fn cochran_q(matrix: &[Vec<bool>]) -> CochranQ {
+    let k = matrix.first().unwrap().len();
+    assert!(k >= 2);
+    matrix.iter().for_each(|row| assert_eq!(row.len(), k));
+
+    let col_totals = (0..k)
+        .map(|j| matrix.iter().filter(|row| row[j]).count() as f64)
+        .collect::<Vec<_>>();
+    let row_totals = matrix
+        .iter()
+        .map(|row| row.iter().filter(|&&x| x).count() as f64)
+        .collect::<Vec<_>>();
+    let total = row_totals.iter().sum::<f64>();
+    let k_f64 = k as f64;
+    let numerator =
+        (k_f64 - 1.) * (k_f64 * col_totals.iter().map(|c| c.powi(2)).sum::<f64>() - total.powi(2));
+    let denominator = k_f64 * total - row_totals.iter().map(|r| r.powi(2)).sum::<f64>();
+    assert!(denominator > 0.);
+    let test = chi_square_result(numerator / denominator, k - 1);
+
+    let mut pairwise = vec![];
+    for j in 0..k {
+        for l in j + 1..k {
+            let discordant_1 = matrix.iter().filter(|row| row[j] && !row[l]).count();
+            let discordant_2 = matrix.iter().filter(|row| !row[j] && row[l]).count();
+            let test = if discordant_1 + discordant_2 == 0 {
+                chi_square_result(0., 1)
+            } else {
+                mcnemar(discordant_1, discordant_2)
+            };
+            pairwise.push(PairwiseComparison {
+                groups: (j, l),
+                test,
+            });
+        }
+    }
+    CochranQ { test, pairwise }
+}
 
-    CHI_SQUARE_TABLE.p_value(df, chi_square)
+/// Upper tail of the chi-square distribution at `chi_square`
+fn chi_square_result(chi_square: f64, df: usize) -> TestResult {
+    let p_value = gammainc_upper_regularized(df as f64 / 2., chi_square / 2.);
+    TestResult {
+        statistic: FiniteF64::new(chi_square).unwrap(),
+        df: Some(DegreesOfFreedom::one(df)),
+        p_value: NormalizedF64::new(p_value).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: None,
+        confidence_interval: None,
+    }
 }
 
 #[cfg(test)]
@@ -165,9 +1877,107 @@ mod tests {
         let sample = CountAndProportion {
             count: 1000,
             proportion: NormalizedF64::new(0.37).unwrap(),
+            population: None,
         };
         let p_0 = NormalizedF64::new(0.5).unwrap();
         assert!(one_proportion(sample, p_0).get() < 0.05);
+
+        // Sampling 1000 of 1200 shrinks the standard error by `sqrt(200 / 1199)`
+        let finite = CountAndProportion {
+            population: NonZeroUsize::new(1200),
+            ..sample
+        };
+        let z = one_proportion_result(sample, p_0).statistic.get();
+        let z_finite = one_proportion_result(finite, p_0).statistic.get();
+        assert!((z_finite - z / (200_f64 / 1199.).sqrt()).abs() < 1e-9);
+
+        let result = one_proportion_result(sample, p_0);
+        assert!((result.statistic.get() - -8.2219).abs() < 1e-3);
+        assert!((result.effect_size.unwrap().get() - -0.263_022).abs() < 1e-6);
+
+        // 32 of 50
+        let small = CountAndProportion {
+            count: 50,
+            proportion: NormalizedF64::new(0.64).unwrap(),
+            population: None,
+        };
+        let z = one_proportion_result(small, p_0).statistic.get();
+        assert!((z - 1.979_898_987).abs() < 1e-9);
+        let z = one_proportion_result_with(small, p_0, ContinuityCorrection::Yates)
+            .statistic
+            .get();
+        assert!((z - 1.838_477_631).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_binomial_test() {
+        let result = binomial_test_result(7, 20, NormalizedF64::new(0.5).unwrap());
+        assert!((result.p_value.get() - 0.263_175_964).abs() < 1e-9);
+        let result = binomial_test_result(3, 20, NormalizedF64::new(0.3).unwrap());
+        assert!((result.p_value.get() - 0.220_418_267).abs() < 1e-9);
+
+        // Both 7 and 13 successes are as likely as the observed outcome
+        let result =
+            binomial_test_result_with(7, 20, NormalizedF64::new(0.5).unwrap(), ExactPValue::MidP);
+        assert!((result.p_value.get() - 0.189_247_131).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mcnemar_exact() {
+        let result = mcnemar_exact(10, 2, ExactPValue::Exact);
+        assert!((result.p_value.get() - 0.038_574_219).abs() < 1e-9);
+        let result = mcnemar_exact(10, 2, ExactPValue::MidP);
+        assert!((result.p_value.get() - 0.022_460_938).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fisher_exact() {
+        // Lady tasting tea
+        let result = fisher_exact_result([[3, 1], [1, 3]]);
+        assert!((result.p_value.get() - 34. / 70.).abs() < 1e-12);
+        assert_eq!(result.effect_size.unwrap().get(), 9.);
+        let result = fisher_exact_result([[10, 2], [3, 15]]);
+        assert!((result.p_value.get() - 0.000_536_724).abs() < 1e-9);
+        assert_eq!(fisher_exact_result([[5, 0], [0, 5]]).effect_size, None);
+
+        let result = fisher_exact_result_with([[3, 1], [1, 3]], ExactPValue::MidP);
+        assert!((result.p_value.get() - 18. / 70.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_unconditional_exact() {
+        let result = barnard_exact_result([[7, 12], [8, 3]]);
+        assert!((result.statistic.get() - -1.894_338_076).abs() < 1e-9);
+        assert!((result.p_value.get() - 0.067_514_107).abs() < 1e-8);
+        let result = boschloo_exact_result([[7, 12], [8, 3]]);
+        assert!((result.statistic.get() - 0.128_135_932).abs() < 1e-9);
+        assert!((result.p_value.get() - 0.085_725_612).abs() < 1e-8);
+
+        let fisher = fisher_exact_result([[10, 2], [3, 15]]);
+        let result = barnard_exact_result([[10, 2], [3, 15]]);
+        assert!((result.p_value.get() - 0.000_280_200).abs() < 1e-9);
+        assert!(result.p_value < fisher.p_value);
+        let result = boschloo_exact_result([[10, 2], [3, 15]]);
+        assert!((result.p_value.get() - 0.000_280_200).abs() < 1e-9);
+        assert_eq!(result.effect_size, fisher.effect_size);
+
+        assert_eq!(barnard_exact_result([[3, 3], [3, 3]]).p_value.get(), 1.);
+    }
+
+    #[test]
+    fn test_normality_report() {
+        let sample = CountAndProportion {
+            count: 20,
+            proportion: NormalizedF64::new(0.35).unwrap(),
+            population: None,
+        };
+        let report = sample.normality_report();
+        assert!(!report[0].is_satisfied());
+        assert!((report[0].observed.get() - 7.).abs() < 1e-12);
+        assert_eq!(report[0].required.get(), 10.);
+        assert!(report[1].is_satisfied());
+        assert!(!sample.satisfies_assumptions());
+        assert!(!sample.is_normally_distributed_enough());
     }
 
     #[test]
@@ -175,10 +1985,12 @@ mod tests {
         let sample_1 = CountAndProportion {
             count: 500 + 44425,
             proportion: NormalizedF64::new(500. / (500 + 44425) as f64).unwrap(),
+            population: None,
         };
         let sample_2 = CountAndProportion {
             count: 505 + 44405,
             proportion: NormalizedF64::new(505. / (505 + 44405) as f64).unwrap(),
+            population: None,
         };
         let p_0 = NormalizedF64::new(0.).unwrap();
         assert!(difference_of_two_proportions(sample_1, sample_2, p_0).get() > 0.05);
@@ -186,15 +1998,49 @@ mod tests {
         let sample_1 = CountAndProportion {
             count: 1000,
             proportion: NormalizedF64::new(0.958).unwrap(),
+            population: None,
         };
         let sample_2 = CountAndProportion {
             count: 1000,
             proportion: NormalizedF64::new(0.899).unwrap(),
+            population: None,
         };
         let p_0 = NormalizedF64::new(0.03).unwrap();
         assert!(difference_of_two_proportions(sample_1, sample_2, p_0).get() < 0.05);
     }
 
+    #[test]
+    fn test_non_inferiority() {
+        let treatment = CountAndProportion {
+            count: 100,
+            proportion: NormalizedF64::new(0.82).unwrap(),
+            population: None,
+        };
+        let reference = CountAndProportion {
+            count: 100,
+            proportion: NormalizedF64::new(0.85).unwrap(),
+            population: None,
+        };
+        let margin = NormalizedF64::new(0.1).unwrap();
+
+        let wald = non_inferiority(treatment, reference, margin, DifferenceStandardError::Wald);
+        assert!((wald.statistic.get() - 1.334_605).abs() < 1e-6);
+        assert!((wald.p_value.get() - 0.091_003).abs() < 1e-6);
+
+        // Checked against a grid search of the restricted likelihood
+        let (p_1, p_2) = farrington_manning(0.82, 100., 0.85, 100., -0.1);
+        assert!((p_1 - 0.776_867).abs() < 1e-6);
+        assert!((p_2 - 0.876_867).abs() < 1e-6);
+        let score = non_inferiority(
+            treatment,
+            reference,
+            margin,
+            DifferenceStandardError::FarringtonManning,
+        );
+        assert!((score.statistic.get() - 1.319_777).abs() < 1e-6);
+        assert!((score.p_value.get() - 0.093_455).abs() < 1e-6);
+    }
+
     #[test]
     fn test_proper_sample_size() {
         let proportion_1 = 500. / (500 + 44425) as f64;
@@ -222,6 +2068,18 @@ mod tests {
         println!("{count}");
     }
 
+    #[test]
+    fn test_min_count_one_proportion() {
+        let p_alt = NormalizedF64::new(0.6).unwrap();
+        let p_0 = NormalizedF64::new(0.5).unwrap();
+        let power = NormalizedF64::new(0.8).unwrap();
+        let max_p_value = NormalizedF64::new(0.05).unwrap();
+        let count = min_count_one_proportion(p_alt, p_0, power, max_p_value, Alternative::TwoSided);
+        assert_eq!(count, 194);
+        let count = min_count_one_proportion(p_alt, p_0, power, max_p_value, Alternative::Greater);
+        assert_eq!(count, 152);
+    }
+
     #[test]
     fn test_fitness() {
         let bins = [
@@ -252,5 +2110,353 @@ mod tests {
             [71, 50, 37], //
         ];
         assert!(two_way_table_independence(&matrix).get() < 0.05);
+
+        let result = two_way_table_independence_result(&matrix);
+        assert!((result.statistic.get() - 40.128_035).abs() < 1e-5);
+        assert!((result.effect_size.unwrap().get() - 0.428_057).abs() < 1e-6);
+        assert!(matches!(result.alternative, Alternative::Greater));
+    }
+
+    #[test]
+    fn test_contingency_table() {
+        let labels = |labels: &[&str]| labels.iter().map(|label| label.to_string()).collect();
+        let table = ContingencyTable::new(
+            labels(&["a", "b"]),
+            labels(&["x", "y", "z"]),
+            vec![vec![2, 23, 36], vec![71, 50, 37]],
+        );
+        let result = table.independence();
+        assert!((result.statistic.get() - 40.128_035).abs() < 1e-5);
+
+        let empty = ContingencyTable::new(
+            labels(&["a", "b"]),
+            labels(&["x", "y"]),
+            vec![vec![0; 2]; 2],
+        );
+        assert!(!empty.check_assumptions()[0].is_satisfied());
+        let large = ContingencyTable::new(
+            labels(&["a", "b"]),
+            labels(&["x", "y"]),
+            vec![vec![1 << 33; 2]; 2],
+        );
+        assert_eq!(large.expected_counts()[0][0], (1_u64 << 33) as f64);
+
+        let report = table.expected_count_report();
+        assert_eq!(report.len(), 6);
+        assert_eq!(report[0].condition, "expected count of cell (a, x)");
+        assert!((report[0].observed.get() - 73. * 61. / 219.).abs() < 1e-12);
+        assert!(report.iter().all(|diagnostic| diagnostic.is_satisfied()));
+        assert_eq!(table.check_assumptions().len(), report.len());
+
+        let residuals = table.residuals();
+        let chi_square = residuals
+            .iter()
+            .flatten()
+            .map(|cell| cell.contribution.get())
+            .sum::<f64>();
+        assert!((chi_square - result.statistic.get()).abs() < 1e-9);
+        let cell = residuals[0][0];
+        let expected: f64 = 73. * 61. / 219.;
+        assert!((cell.pearson.get() - (2. - expected) / expected.sqrt()).abs() < 1e-12);
+        let variance = expected * (1. - 61. / 219.) * (1. - 73. / 219.);
+        assert!((cell.adjusted.get() - (2. - expected) / variance.sqrt()).abs() < 1e-12);
+        assert!(cell.adjusted.get() < -2.);
+
+        assert_eq!(table.total(), 219);
+        assert_eq!(table.row_totals(), [61, 158]);
+        assert_eq!(table.col_totals(), [73, 73, 73]);
+        assert!((table.expected_counts()[1][2] - 158. / 3.).abs() < 1e-12);
+        assert!((table.row_profiles()[0][0].get() - 2. / 61.).abs() < 1e-15);
+        assert!((table.col_profiles()[2][1].get() - 37. / 73.).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_contingency_table_from_pairs() {
+        let observations = [
+            ("a", 'y'),
+            ("b", 'x'),
+            ("a", 'z'),
+            ("b", 'y'),
+            ("a", 'y'),
+            ("b", 'x'),
+        ];
+        let table = ContingencyTable::from_pairs(observations);
+        assert_eq!(table.row_labels, ["a", "b"]);
+        assert_eq!(table.col_labels, ["y", "x", "z"]);
+        assert_eq!(table.counts, [vec![2, 0, 1], vec![1, 2, 0]]);
+        assert_eq!(table.row_index("b"), Some(1));
+        assert_eq!(table.col_index("z"), Some(2));
+        assert_eq!(table.col_index("w"), None);
+
+        // The table of `test_contingency_table` from raw observations
+        let cells = [("a", "x", 2), ("a", "y", 23), ("a", "z", 36)]
+            .into_iter()
+            .chain([("b", "x", 71), ("b", "y", 50), ("b", "z", 37)]);
+        let observations =
+            cells.flat_map(|(row, col, count)| std::iter::repeat_n((row, col), count));
+        let table = ContingencyTable::from_pairs(observations);
+        let result = table.independence();
+        assert!((result.statistic.get() - 40.128_035).abs() < 1e-5);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_monte_carlo_fitness() {
+        let bins = [8, 3, 2, 7].map(|count| CountAndExpect {
+            count,
+            expect: PositiveF64::new(5.).unwrap(),
+        });
+        let method = PValueMethod::MonteCarlo {
+            n_sims: 10_000,
+            seed: 0,
+        };
+        let result = fitness_result_with(&bins, method);
+        assert!((result.statistic.get() - 5.2).abs() < 1e-12);
+        // Exact multinomial probability of a statistic at least 5.2
+        assert!((result.p_value.get() - 0.183_600).abs() < 0.02);
+        assert_eq!(fitness_result_with(&bins, method).p_value, result.p_value);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_monte_carlo_independence() {
+        // Expected counts of 2 are too small for the chi-square approximation
+        let labels = |labels: &[&str]| labels.iter().map(|label| label.to_string()).collect();
+        let table = ContingencyTable::new(
+            labels(&["a", "b"]),
+            labels(&["x", "y"]),
+            vec![vec![3, 1], vec![1, 3]],
+        );
+        let result = table.independence_with(PValueMethod::MonteCarlo {
+            n_sims: 10_000,
+            seed: 0,
+        });
+        assert!((result.statistic.get() - 2.).abs() < 1e-12);
+        // Exactly 34 / 70 of the tables with these margins are as extreme
+        assert!((result.p_value.get() - 34. / 70.).abs() < 0.02);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_two_way_table_independence_view() {
+        let matrix = ndarray::arr2(&[
+            [2, 23, 36],  //
+            [71, 50, 37], //
+        ]);
+        let result = two_way_table_independence_view(matrix.view());
+        assert!((result.statistic.get() - 40.128_035).abs() < 1e-5);
+        // Columns as rows
+        let result = two_way_table_independence_view(matrix.t());
+        assert!((result.statistic.get() - 40.128_035).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_poisson_rate_test() {
+        // ref: `poisson.test(10, 2, 2)` in R
+        let exposure = PositiveF64::new(2.).unwrap();
+        let rate_0 = PositiveF64::new(2.).unwrap();
+        let p = poisson_rate_test(10, exposure, rate_0);
+        assert!((p.get() - 0.008_132_243).abs() < 1e-9);
+
+        let rate_0 = PositiveF64::new(5.).unwrap();
+        let p = poisson_rate_test(3, exposure, rate_0);
+        assert!((p.get() - 0.024_613_664).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_two_poisson_rates() {
+        // ref: `poisson.test(c(10, 3), c(2, 2))` in R
+        let exposure = PositiveF64::new(2.).unwrap();
+        let p = two_poisson_rates(10, exposure, 3, exposure);
+        assert!((p.get() - 0.092_285_156_25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_two_rate_test() {
+        let exposure = PositiveF64::new(2.).unwrap();
+        let result = two_rate_test(10, exposure, 3, exposure);
+        assert!((result.conditional.p_value.get() - 0.092_285_156_25).abs() < 1e-12);
+        assert!((result.e_test.statistic.get() - 1.941_450_687).abs() < 1e-9);
+        assert!((result.e_test.p_value.get() - 0.052_776_516).abs() < 1e-9);
+        assert!((result.rate_ratio().unwrap().get() - 10. / 3.).abs() < 1e-12);
+        // ref: `poisson.test(c(10, 3), c(2, 2))` in R
+        let (lower, upper) = result.rate_ratio_interval(NormalizedF64::new(0.95).unwrap());
+        assert!((lower - 0.858_281_716).abs() < 1e-6);
+        assert!((upper - 18.848_723_552).abs() < 1e-4);
+
+        let result = two_rate_test(
+            20,
+            PositiveF64::new(10.).unwrap(),
+            5,
+            PositiveF64::new(8.).unwrap(),
+        );
+        assert!((result.e_test.p_value.get() - 0.008_891_324).abs() < 1e-9);
+        let (lower, upper) = result.rate_ratio_interval(NormalizedF64::new(0.95).unwrap());
+        assert!((lower - 1.165_421_203).abs() < 1e-6);
+        assert!((upper - 10.911_065_069).abs() < 1e-5);
+
+        let result = two_rate_test(
+            0,
+            PositiveF64::new(3.).unwrap(),
+            4,
+            PositiveF64::new(5.).unwrap(),
+        );
+        assert!((result.e_test.p_value.get() - 0.061_057_342).abs() < 1e-9);
+        assert_eq!(result.rate_ratio().unwrap().get(), 0.);
+
+        // Only the central counts are summed
+        let result = two_rate_test(10_000, exposure, 10_200, exposure);
+        let z = result.e_test.statistic.get();
+        assert!((result.e_test.p_value.get() - 2. * normal_cdf(-z.abs())).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_trend_test() {
+        // Smokers among lung cancer patients by age group
+        let counts = [[83, 3], [90, 3], [129, 7], [70, 12]];
+        let scores = [1., 2., 3., 4.];
+        let result = trend_test(&counts, &scores);
+        assert!((result.statistic.get() - -2.867_913).abs() < 1e-6);
+        assert!((result.p_value.get() - 0.004_132).abs() < 1e-6);
+        let result = trend_test_exact(&counts, &scores);
+        assert!((result.p_value.get() - 0.003_873).abs() < 1e-6);
+
+        let counts = [[2, 8], [4, 6], [7, 3]];
+        let scores = [0., 1., 2.];
+        let result = trend_test_exact(&counts, &scores);
+        assert!((result.statistic.get() - 2.256_213).abs() < 1e-6);
+        assert!((result.p_value.get() - 0.043_096).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mantel_haenszel() {
+        // UC Berkeley admissions by department: admitted / rejected by male / female
+        let strata = [
+            [[512, 89], [313, 19]],
+            [[353, 17], [207, 8]],
+            [[120, 202], [205, 391]],
+            [[138, 131], [279, 244]],
+            [[53, 94], [138, 299]],
+            [[22, 24], [351, 317]],
+        ];
+        let confidence = NormalizedF64::new(0.95).unwrap();
+        let result = mantel_haenszel(&strata, confidence);
+        assert!((result.odds_ratio.get() - 0.904_696_8).abs() < 1e-7);
+        let (lower, upper) = result.odds_ratio_interval;
+        assert!((lower.get() - 0.771_907_4).abs() < 1e-7);
+        assert!((upper.get() - 1.060_329_8).abs() < 1e-7);
+        assert!((result.test.statistic.get() - 1.524_607).abs() < 1e-6);
+        assert!((result.homogeneity.statistic.get() - 18.825_514).abs() < 1e-6);
+        assert!((result.homogeneity.p_value.get() - 0.002_071_4).abs() < 1e-7);
+
+        // The second stratum has an empty row
+        let result = mantel_haenszel(&[[[10, 5], [3, 8]], [[5, 5], [0, 0]]], confidence);
+        assert!((result.odds_ratio.get() - 80. / 15.).abs() < 1e-12);
+        assert_eq!(result.homogeneity.statistic.get(), 0.);
+        assert!(result.homogeneity.df.is_none());
+    }
+
+    #[test]
+    fn test_association() {
+        let confidence = NormalizedF64::new(0.95).unwrap();
+        let labels = |n: usize| (0..n).map(|i| i.to_string()).collect();
+        // Agresti: income by job satisfaction
+        let table = ContingencyTable::new(
+            labels(4),
+            labels(4),
+            vec![
+                vec![1, 3, 10, 6],
+                vec![2, 3, 10, 7],
+                vec![1, 6, 14, 12],
+                vec![0, 1, 9, 11],
+            ],
+        );
+        let gamma = table.gamma(confidence);
+        assert!((gamma.estimate.get() - 0.221_100_917).abs() < 1e-9);
+        assert!((gamma.standard_error.get() - 0.117_162_829).abs() < 1e-8);
+        let (lower, upper) = gamma.test.confidence_interval.unwrap();
+        assert!((upper.get() - lower.get() - 2. * 1.959_964 * 0.117_162_829).abs() < 1e-5);
+        // Against the null standard error 0.119_419_303
+        assert!((gamma.test.statistic.get() - 1.851_467_159).abs() < 1e-8);
+        let somers_d = table.somers_d(confidence);
+        assert!((somers_d.estimate.get() - 0.141_723_023).abs() < 1e-9);
+        assert!((somers_d.standard_error.get() - 0.076_395_113).abs() < 1e-8);
+
+        let table = ContingencyTable::new(
+            labels(3),
+            labels(3),
+            vec![vec![10, 2, 3], vec![4, 12, 5], vec![1, 3, 14]],
+        );
+        let lambda = table.lambda(confidence);
+        assert_eq!(lambda.estimate.get(), 0.4375);
+        assert!((lambda.standard_error.get() - 0.128_372_474).abs() < 1e-8);
+        assert!(lambda.test.p_value.get() < 0.01);
+
+        // Perfect association, where even the null standard error vanishes
+        let table = ContingencyTable::new(labels(2), labels(2), vec![vec![10, 0], vec![0, 10]]);
+        let gamma = table.gamma(confidence);
+        assert_eq!(gamma.estimate.get(), 1.);
+        assert_eq!(gamma.standard_error.get(), 0.);
+        assert_eq!(gamma.test.p_value.get(), 0.);
+        let somers_d = table.somers_d(confidence);
+        assert_eq!(somers_d.estimate.get(), 1.);
+        assert_eq!(somers_d.test.p_value.get(), 0.);
+    }
+
+    #[test]
+    fn test_square_table() {
+        let table = [vec![20, 10, 5], vec![3, 30, 15], vec![0, 5, 40]];
+        let result = stuart_maxwell(&table);
+        assert!((result.statistic.get() - 13.764_705_882).abs() < 1e-9);
+        assert!((result.p_value.get() - 0.001_025_728).abs() < 1e-9);
+        // The first category is perfectly concordant
+        let result = stuart_maxwell(&[vec![10, 0, 0], vec![0, 5, 3], vec![0, 1, 6]]);
+        assert!((result.statistic.get() - 1.).abs() < 1e-12);
+        assert!(matches!(result.df, Some(DegreesOfFreedom::One(df)) if df.get() == 1.));
+        // Two groups of categories without discordant pairs between them
+        let result = stuart_maxwell(&[
+            vec![5, 2, 0, 0],
+            vec![1, 5, 0, 0],
+            vec![0, 0, 5, 3],
+            vec![0, 0, 1, 5],
+        ]);
+        assert!((result.statistic.get() - 4. / 3.).abs() < 1e-12);
+        assert!(matches!(result.df, Some(DegreesOfFreedom::One(df)) if df.get() == 2.));
+        let result = bowker(&table);
+        assert!((result.statistic.get() - 13.769_230_769).abs() < 1e-9);
+        assert!((result.p_value.get() - 0.003_236_707).abs() < 1e-9);
+
+        // 2 × 2 reduces to McNemar
+        let table = [vec![5, 10], vec![2, 7]];
+        let result = mcnemar(10, 2);
+        assert!((stuart_maxwell(&table).statistic.get() - result.statistic.get()).abs() < 1e-12);
+        assert!((bowker(&table).statistic.get() - result.statistic.get()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cochran_q() {
+        let matrix = [
+            [1, 1, 0],
+            [1, 0, 0],
+            [1, 1, 1],
+            [0, 0, 0],
+            [1, 0, 1],
+            [1, 1, 0],
+            [1, 0, 0],
+            [1, 1, 0],
+            [0, 1, 0],
+            [1, 0, 0],
+            [1, 1, 1],
+            [1, 0, 0],
+        ]
+        .map(|row| row.map(|x| x == 1).to_vec());
+        let result = cochran_q(&matrix);
+        assert!((result.test.statistic.get() - 8.222_222).abs() < 1e-6);
+        assert!((result.test.p_value.get() - 0.016_389_6).abs() < 1e-7);
+        assert_eq!(result.pairwise.len(), 3);
+        let first_and_third = result.pairwise[1];
+        assert_eq!(first_and_third.groups, (0, 2));
+        assert!((first_and_third.test.statistic.get() - 7.).abs() < 1e-12);
+        assert!((first_and_third.test.p_value.get() - 0.008_151).abs() < 1e-6);
     }
 }
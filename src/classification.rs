@@ -0,0 +1,234 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    correlation::ranks,
+    special::{normal_cdf, normal_quantile},
+    Alternative, TestResult,
+};
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RocPoint {
+    /// Scores at or above it are classified as positive; `f64::INFINITY` at the origin
+    pub threshold: f64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub false_positive_rate: NormalizedF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub true_positive_rate: NormalizedF64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Roc {
+    /// From `(0, 0)` to `(1, 1)` by descending threshold
+    pub points: Vec<RocPoint>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub auc: NormalizedF64,
+    /// DeLong variance of the AUC
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub variance: PositiveF64,
+    components: Components,
+}
+impl Roc {
+    /// Normal interval of the AUC, clamped to `[0, 1]`
+    pub fn confidence_interval(&self, confidence: NormalizedF64) -> (NormalizedF64, NormalizedF64) {
+        let margin = normal_quantile(0.5 + confidence.get() / 2.) * self.variance.get().sqrt();
+        let bound = |x: f64| NormalizedF64::new(x.clamp(0., 1.)).unwrap();
+        (
+            bound(self.auc.get() - margin),
+            bound(self.auc.get() + margin),
+        )
+    }
+}
+
+/// DeLong placement values: the AUC of each positive against all negatives and vice versa
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Components {
+    positives: Vec<f64>,
+    negatives: Vec<f64>,
+}
+impl Components {
+    fn new(scores: &[f64], labels: &[bool]) -> Self {
+        let combined = ranks(scores);
+        let split = |label: bool| {
+            let values = labels
+                .iter()
+                .zip(scores)
+                .filter(|(&l, _)| l == label)
+                .map(|(_, &s)| s)
+                .collect::<Vec<_>>();
+            let combined_ranks = labels
+                .iter()
+                .zip(&combined)
+                .filter(|(&l, _)| l == label)
+                .map(|(_, &r)| r)
+                .collect::<Vec<_>>();
+            (combined_ranks, ranks(&values))
+        };
+        let (positive_ranks, positive_own_ranks) = split(true);
+        let (negative_ranks, negative_own_ranks) = split(false);
+        let (m, n) = (positive_ranks.len() as f64, negative_ranks.len() as f64);
+        // The combined rank less the rank among its own class counts the other class below it
+        let positives = positive_ranks
+            .iter()
+            .zip(&positive_own_ranks)
+            .map(|(r, own)| (r - own) / n)
+            .collect();
+        let negatives = negative_ranks
+            .iter()
+            .zip(&negative_own_ranks)
+            .map(|(r, own)| 1. - (r - own) / m)
+            .collect();
+        Self {
+            positives,
+            negatives,
+        }
+    }
+
+    fn auc(&self) -> f64 {
+        mean(&self.positives)
+    }
+
+    fn covariance(&self, other: &Self) -> f64 {
+        covariance(&self.positives, &other.positives) / self.positives.len() as f64
+            + covariance(&self.negatives, &other.negatives) / self.negatives.len() as f64
+    }
+}
+
+/// ROC curve and the area under it.
+///
+/// - `labels[i]`: whether the observation with `scores[i]` is positive; both classes need at least two observations
+///
+/// ref: DeLong, E. R., DeLong, D. M., & Clarke-Pearson, D. L. (1988). Comparing the areas under two or more correlated receiver operating characteristic curves: a nonparametric approach.
+pub fn roc(scores: &[f64], labels: &[bool]) -> Roc {
+    assert_eq!(scores.len(), labels.len());
+    assert!(scores.iter().all(|score| score.is_finite()));
+    let positives = labels.iter().filter(|&&label| label).count();
+    let negatives = labels.len() - positives;
+    assert!(positives >= 2);
+    assert!(negatives >= 2);
+
+    let mut order = (0..scores.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| scores[b].partial_cmp(&scores[a]).unwrap());
+    let point = |threshold: f64, false_positives: usize, true_positives: usize| RocPoint {
+        threshold,
+        false_positive_rate: NormalizedF64::new(false_positives as f64 / negatives as f64).unwrap(),
+        true_positive_rate: NormalizedF64::new(true_positives as f64 / positives as f64).unwrap(),
+    };
+    let mut points = vec![point(f64::INFINITY, 0, 0)];
+    let (mut false_positives, mut true_positives) = (0, 0);
+    for (i, &index) in order.iter().enumerate() {
+        if labels[index] {
+            true_positives += 1;
+        } else {
+            false_positives += 1;
+        }
+        let is_last_of_tie = order
+            .get(i + 1)
+            .is_none_or(|&next| scores[next] != scores[index]);
+        if is_last_of_tie {
+            points.push(point(scores[index], false_positives, true_positives));
+        }
+    }
+
+    let components = Components::new(scores, labels);
+    let variance = components.covariance(&components);
+    Roc {
+        points,
+        auc: NormalizedF64::new(components.auc()).unwrap(),
+        variance: PositiveF64::new(variance).unwrap(),
+        components,
+    }
+}
+
+/// DeLong test of two AUCs from scores on the same observations.
+///
+/// Null hypothesis: the two AUCs are equal.
+///
+/// - The statistic is `z` of the difference `auc_1 - auc_2`, which is the effect size.
+/// - Equal AUCs give `z = 0` and a p-value of `1`, even if the curves are identical and the variance is zero.
+pub fn compare_roc(roc_1: &Roc, roc_2: &Roc) -> TestResult {
+    let (a, b) = (&roc_1.components, &roc_2.components);
+    assert_eq!(a.positives.len(), b.positives.len());
+    assert_eq!(a.negatives.len(), b.negatives.len());
+    let difference = a.auc() - b.auc();
+    let variance = a.covariance(a) + b.covariance(b) - 2. * a.covariance(b);
+    let z = if difference == 0. {
+        0.
+    } else if variance <= 0. {
+        difference.signum() * f64::MAX
+    } else {
+        difference / variance.sqrt()
+    };
+    TestResult {
+        statistic: FiniteF64::new(z).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(2. * normal_cdf(-z.abs())).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: Some(FiniteF64::new(difference).unwrap()),
+        confidence_interval: None,
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn covariance(x: &[f64], y: &[f64]) -> f64 {
+    let (mean_x, mean_y) = (mean(x), mean(y));
+    x.iter()
+        .zip(y)
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>()
+        / (x.len() - 1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LABELS: [bool; 15] = [
+        true, true, false, true, true, true, false, false, true, false, true, false, false, false,
+        false,
+    ];
+
+    #[test]
+    fn test_roc() {
+        let scores = [
+            0.9, 0.8, 0.7, 0.6, 0.55, 0.54, 0.53, 0.52, 0.51, 0.505, 0.4, 0.39, 0.38, 0.37, 0.36,
+        ];
+        let roc = roc(&scores, &LABELS);
+        assert!((roc.auc.get() - 0.821_428_571).abs() < 1e-9);
+        assert!((roc.variance.get() - 0.012_937_318).abs() < 1e-9);
+        let (lower, upper) = roc.confidence_interval(NormalizedF64::new(0.95).unwrap());
+        assert!((lower.get() - 0.598_497_700).abs() < 1e-6);
+        assert_eq!(upper.get(), 1.);
+        assert_eq!(roc.points.len(), scores.len() + 1);
+        let last = roc.points.last().unwrap();
+        assert_eq!(last.false_positive_rate.get(), 1.);
+        assert_eq!(last.true_positive_rate.get(), 1.);
+    }
+
+    #[test]
+    fn test_compare_roc() {
+        let scores_1 = [
+            0.9, 0.8, 0.7, 0.6, 0.55, 0.54, 0.53, 0.52, 0.51, 0.505, 0.4, 0.39, 0.38, 0.37, 0.36,
+        ];
+        let scores_2 = [
+            0.7, 0.9, 0.6, 0.6, 0.3, 0.5, 0.55, 0.2, 0.8, 0.1, 0.45, 0.35, 0.6, 0.2, 0.25,
+        ];
+        let roc_2 = roc(&scores_2, &LABELS);
+        assert!((roc_2.auc.get() - 0.803_571_429).abs() < 1e-9);
+        assert!((roc_2.variance.get() - 0.013_650_996).abs() < 1e-9);
+        // Tied scores collapse into one point
+        assert_eq!(roc_2.points.len(), 13);
+        let test = compare_roc(&roc(&scores_1, &LABELS), &roc_2);
+        assert!((test.statistic.get() - 0.139_897_446).abs() < 1e-8);
+        assert!((test.p_value.get() - 0.888_741_019).abs() < 1e-8);
+
+        let test = compare_roc(&roc_2, &roc_2);
+        assert_eq!(test.statistic.get(), 0.);
+        assert_eq!(test.p_value.get(), 1.);
+    }
+}
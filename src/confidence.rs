@@ -0,0 +1,63 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{distributions::t::StudentT, numerical::NumericalSample};
+
+/// Welch confidence interval of `sample_1.mean - sample_2.mean` without assuming equal variances.
+///
+/// - The degrees of freedom are from the Welch–Satterthwaite equation.
+/// - `level`: e.g. 0.95
+pub fn mean_difference_interval(
+    sample_1: NumericalSample,
+    sample_2: NumericalSample,
+    level: NormalizedF64,
+) -> (FiniteF64, FiniteF64) {
+    let df_1 = (sample_1.count.get() - 1) as f64;
+    let df_2 = (sample_2.count.get() - 1) as f64;
+    assert!(df_1 > 0.);
+    assert!(df_2 > 0.);
+    let se2_1 = sample_1.standard_error_squared();
+    let se2_2 = sample_2.standard_error_squared();
+    let standard_error_squared = se2_1 + se2_2;
+    assert!(standard_error_squared > 0.);
+    let df = standard_error_squared.powi(2) / (se2_1.powi(2) / df_1 + se2_2.powi(2) / df_2);
+
+    let t = StudentT {
+        df: PositiveF64::new(df).unwrap(),
+    };
+    let tail = (1. - level.get()) / 2.;
+    let critical = t.quantile(NormalizedF64::new(1. - tail).unwrap()).get();
+    let margin = critical * standard_error_squared.sqrt();
+    let difference = sample_1.mean.get() - sample_2.mean.get();
+    (
+        FiniteF64::new(difference - margin).unwrap(),
+        FiniteF64::new(difference + margin).unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+
+    #[test]
+    fn test_mean_difference_interval() {
+        let sample_1 = NumericalSample {
+            mean: FiniteF64::new(5.).unwrap(),
+            variance: PositiveF64::new(4.).unwrap(),
+            count: NonZeroUsize::new(10).unwrap(),
+            population: None,
+        };
+        let sample_2 = NumericalSample {
+            mean: FiniteF64::new(3.).unwrap(),
+            variance: PositiveF64::new(9.).unwrap(),
+            count: NonZeroUsize::new(15).unwrap(),
+            population: None,
+        };
+        let level = NormalizedF64::new(0.95).unwrap();
+        let (lower, upper) = mean_difference_interval(sample_1, sample_2, level);
+        // The Welch–Satterthwaite degrees of freedom are about 22.99
+        assert!((lower.get() - -0.068_693_950).abs() < 1e-6);
+        assert!((upper.get() - 4.068_693_950).abs() < 1e-6);
+    }
+}
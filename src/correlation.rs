@@ -0,0 +1,565 @@
+use std::num::NonZeroUsize;
+
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    distributions::{
+        normal::Z_SCORE_TABLE,
+        t::{StudentT, T_SCORE_TABLE},
+    },
+    Alternative, DegreesOfFreedom, TestResult,
+};
+
+/// Spearman's rank correlation coefficient.
+///
+/// Null hypothesis: the ranks of `x` and `y` are uncorrelated.
+///
+/// - Tied values receive the average of the ranks they span.
+/// - The p-value comes from the t-approximation with `n - 2` degrees of freedom.
+pub fn spearman(x: &[f64], y: &[f64]) -> (FiniteF64, NormalizedF64) {
+    assert_eq!(x.len(), y.len());
+    assert!(x.len() >= 3);
+
+    let rho = spearman_rho(x, y);
+    let p_value = correlation_t_test(rho, x.len());
+    (FiniteF64::new(rho).unwrap(), p_value)
+}
+
+/// Spearman's rank correlation coefficient with the exact permutation p-value.
+///
+/// Every permutation of `y` is enumerated, so this is only for small samples.
+pub fn spearman_exact(x: &[f64], y: &[f64]) -> (FiniteF64, NormalizedF64) {
+    assert_eq!(x.len(), y.len());
+    assert!(x.len() >= 3);
+    assert!(x.len() <= MAX_EXACT_COUNT);
+
+    let rank_x = ranks(x);
+    let mut rank_y = ranks(y);
+    let rho = pearson(&rank_x, &rank_y);
+
+    let mut extreme = 0_usize;
+    let mut total = 0_usize;
+    for_each_permutation(&mut rank_y, &mut |permuted| {
+        let permuted_rho = pearson(&rank_x, permuted);
+        if permuted_rho.abs() >= rho.abs() - EXACT_TOLERANCE {
+            extreme += 1;
+        }
+        total += 1;
+    });
+    let p_value = extreme as f64 / total as f64;
+    (
+        FiniteF64::new(rho).unwrap(),
+        NormalizedF64::new(p_value).unwrap(),
+    )
+}
+
+const MAX_EXACT_COUNT: usize = 10;
+const EXACT_TOLERANCE: f64 = 1e-12;
+
+/// Kendall's tau-b rank correlation coefficient.
+///
+/// Null hypothesis: `x` and `y` are independent.
+///
+/// - Ties in either variable are corrected for in both the coefficient and the variance of the statistic.
+/// - The p-value comes from the normal approximation.
+/// - Runs in `O(n log n)` by counting discordant pairs with a merge sort (Knight's algorithm).
+pub fn kendall_tau(x: &[f64], y: &[f64]) -> (FiniteF64, NormalizedF64) {
+    assert_eq!(x.len(), y.len());
+    assert!(x.len() >= 3);
+    let n = x.len();
+
+    let mut pairs = x.iter().copied().zip(y.iter().copied()).collect::<Vec<_>>();
+    pairs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    // Pairs tied in `x`, and pairs tied in both `x` and `y`
+    let x_ties = tie_group_sizes(pairs.iter().map(|(x, _)| *x));
+    let joint_ties = tie_group_sizes(pairs.iter().copied());
+
+    // Every inversion left in `y` after sorting by `x` is a discordant pair
+    let mut ys = pairs.iter().map(|(_, y)| *y).collect::<Vec<_>>();
+    let mut buffer = vec![0.; n];
+    let discordant = merge_sort_count_inversions(&mut ys, &mut buffer);
+    let y_ties = tie_group_sizes(ys.iter().copied());
+
+    let pairs_of = |groups: &[usize]| groups.iter().map(|&t| t * (t - 1) / 2).sum::<usize>();
+    let n_0 = n * (n - 1) / 2;
+    let n_1 = pairs_of(&x_ties);
+    let n_2 = pairs_of(&y_ties);
+    let n_3 = pairs_of(&joint_ties);
+    let s = (n_0 + n_3) as f64 - (n_1 + n_2) as f64 - 2. * discordant as f64;
+
+    let denominator = (((n_0 - n_1) * (n_0 - n_2)) as f64).sqrt();
+    assert!(denominator > 0.);
+    let tau = (s / denominator).clamp(-1., 1.);
+
+    // ref: <https://en.wikipedia.org/wiki/Kendall_rank_correlation_coefficient#Significance_tests>
+    let n = n as f64;
+    let sum =
+        |groups: &[usize], f: fn(f64) -> f64| groups.iter().map(|&t| f(t as f64)).sum::<f64>();
+    let v_0 = n * (n - 1.) * (2. * n + 5.);
+    let v_t = sum(&x_ties, |t| t * (t - 1.) * (2. * t + 5.));
+    let v_u = sum(&y_ties, |t| t * (t - 1.) * (2. * t + 5.));
+    let v_1 = sum(&x_ties, |t| t * (t - 1.)) * sum(&y_ties, |t| t * (t - 1.));
+    let v_2 = sum(&x_ties, |t| t * (t - 1.) * (t - 2.)) * sum(&y_ties, |t| t * (t - 1.) * (t - 2.));
+    let variance =
+        (v_0 - v_t - v_u) / 18. + v_1 / (2. * n * (n - 1.)) + v_2 / (9. * n * (n - 1.) * (n - 2.));
+    let z = s / variance.sqrt();
+    let z = FiniteF64::new(z).unwrap();
+    (
+        FiniteF64::new(tau).unwrap(),
+        Z_SCORE_TABLE.p_value_two_sided(z),
+    )
+}
+
+fn spearman_rho(x: &[f64], y: &[f64]) -> f64 {
+    pearson(&ranks(x), &ranks(y))
+}
+
+/// Two-sided p-value of a correlation coefficient `r` from `n` pairs via `t = r sqrt((n - 2) / (1 - r^2))`.
+fn correlation_t_test(r: f64, n: usize) -> NormalizedF64 {
+    let denominator = 1. - r.powi(2);
+    if denominator <= 0. {
+        return NormalizedF64::new(0.).unwrap();
+    }
+    let t = r * ((n - 2) as f64 / denominator).sqrt();
+    let t = FiniteF64::new(t).unwrap();
+    let df = NonZeroUsize::new(n - 2).unwrap();
+    T_SCORE_TABLE.p_value_two_sided(df, t)
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PartialCorrelation {
+    /// Pearson correlation of `x` and `y` after removing the linear effects of the controls from both
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub partial: FiniteF64,
+    /// Pearson correlation of `y` with `x` after removing the linear effects of the controls from `x` only
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub semi_partial: FiniteF64,
+    /// `t = r sqrt(df / (1 - r^2))` of the partial correlation with `n - 2 - k` degrees of freedom for `k` controls
+    ///
+    /// Null hypothesis: `x` and `y` are uncorrelated given the controls, which is also the null hypothesis of the semi-partial correlation.
+    pub test: TestResult,
+}
+
+/// Partial and semi-partial correlations of `x` and `y` controlling for each of `controls`, every one as long as `x`.
+///
+/// - A control that is a linear combination of the intercept and the earlier controls is left out, along with its degree of freedom.
+/// - If the controls fully explain `x` or `y`, no variation is left to correlate: both correlations are `0` and the p-value is `1`.
+pub fn partial(x: &[f64], y: &[f64], controls: &[Vec<f64>]) -> PartialCorrelation {
+    assert_eq!(x.len(), y.len());
+    assert!(controls.iter().all(|control| control.len() == x.len()));
+    let basis = orthonormal_basis(controls, x.len());
+    // The intercept is one of the basis vectors
+    let df = x.len() as isize - 1 - basis.len() as isize;
+    assert!(df >= 1);
+    let df = PositiveF64::new(df as f64).unwrap();
+    let residual_x = residuals(x, &basis);
+    let residual_y = residuals(y, &basis);
+    if is_explained(x, &residual_x) || is_explained(y, &residual_y) {
+        let zero = FiniteF64::new(0.).unwrap();
+        return PartialCorrelation {
+            partial: zero,
+            semi_partial: zero,
+            test: TestResult {
+                statistic: zero,
+                df: Some(DegreesOfFreedom::One(df)),
+                p_value: NormalizedF64::new(1.).unwrap(),
+                alternative: Alternative::TwoSided,
+                effect_size: None,
+                confidence_interval: None,
+            },
+        };
+    }
+    let r = pearson(&residual_x, &residual_y);
+    let semi_partial = pearson(y, &residual_x);
+
+    let t = r * (df.get() / (1. - r.powi(2))).sqrt();
+    let p_value = if t.is_finite() {
+        2. * StudentT { df }.cdf(FiniteF64::new(-t.abs()).unwrap()).get()
+    } else {
+        0.
+    };
+    let test = TestResult {
+        statistic: FiniteF64::new(t.clamp(f64::MIN, f64::MAX)).unwrap(),
+        df: Some(DegreesOfFreedom::One(df)),
+        p_value: NormalizedF64::new(p_value.min(1.)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    };
+    PartialCorrelation {
+        partial: FiniteF64::new(r).unwrap(),
+        semi_partial: FiniteF64::new(semi_partial).unwrap(),
+        test,
+    }
+}
+
+/// Relative norm below which a vector counts as a linear combination of others
+const RANK_TOLERANCE: f64 = 1e-10;
+
+/// Orthonormal basis, by modified Gram–Schmidt, of an intercept and the `predictors` that are not linear combinations of the earlier ones
+fn orthonormal_basis(predictors: &[Vec<f64>], n: usize) -> Vec<Vec<f64>> {
+    let norm = |v: &[f64]| v.iter().map(|v| v.powi(2)).sum::<f64>().sqrt();
+    let mut basis: Vec<Vec<f64>> = vec![];
+    for column in std::iter::once(&vec![1.; n]).chain(predictors) {
+        let original = norm(column);
+        let mut column = column.clone();
+        for unit in &basis {
+            let dot = column.iter().zip(unit).map(|(c, u)| c * u).sum::<f64>();
+            column.iter_mut().zip(unit).for_each(|(c, u)| *c -= dot * u);
+        }
+        let remaining = norm(&column);
+        if remaining <= RANK_TOLERANCE * original {
+            continue;
+        }
+        column.iter_mut().for_each(|c| *c /= remaining);
+        basis.push(column);
+    }
+    basis
+}
+
+/// Residuals of the least-squares fit of `values` on the orthonormal `basis`
+fn residuals(values: &[f64], basis: &[Vec<f64>]) -> Vec<f64> {
+    let mut residuals = values.to_vec();
+    for unit in basis {
+        let dot = residuals.iter().zip(unit).map(|(r, u)| r * u).sum::<f64>();
+        residuals
+            .iter_mut()
+            .zip(unit)
+            .for_each(|(r, u)| *r -= dot * u);
+    }
+    residuals
+}
+
+/// Whether the fit leaves no variation of `values` beyond rounding
+fn is_explained(values: &[f64], residuals: &[f64]) -> bool {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let total = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>();
+    let residual = residuals.iter().map(|r| r.powi(2)).sum::<f64>();
+    residual <= RANK_TOLERANCE.powi(2) * total
+}
+
+/// Bias-corrected squared distance correlation, zero in expectation if and only if `x` and `y` are independent.
+///
+/// - Detects nonlinear dependence, e.g. `y = x^2` on a symmetric `x`, that Pearson's and Spearman's coefficients miss.
+/// - Can be slightly negative under independence.
+/// - Zero if `x` or `y` is constant.
+///
+/// ref: Székely, G. J., & Rizzo, M. L. (2013). The distance correlation t-test of independence in high dimension.
+pub fn distance_correlation(x: &[f64], y: &[f64]) -> FiniteF64 {
+    let (a, b) = u_centered_pair(x, y);
+    let r = match u_scale(&a, &b) {
+        Some(scale) => u_inner_product(&a, &b, |i| i) / scale,
+        None => 0.,
+    };
+    FiniteF64::new(r).unwrap()
+}
+
+/// [`distance_correlation`] with a permutation p-value.
+///
+/// Null hypothesis: `x` and `y` are independent.
+///
+/// - The p-value is the proportion of permutations of `y` whose statistic is at least the observed one, counting the observed pairing itself.
+#[cfg(feature = "rand")]
+pub fn distance_correlation_test(
+    x: &[f64],
+    y: &[f64],
+    n_perms: usize,
+    rng: &mut impl rand::Rng,
+) -> crate::resampling::PermutationTest {
+    use rand::seq::SliceRandom;
+
+    assert!(n_perms >= 1);
+    let (a, b) = u_centered_pair(x, y);
+    let scale = u_scale(&a, &b);
+    let correlation = |product: f64| scale.map_or(0., |scale| product / scale);
+    // U-centering commutes with permuting the observations
+    let observed = correlation(u_inner_product(&a, &b, |i| i));
+    let mut permutation = (0..y.len()).collect::<Vec<_>>();
+    let distribution = (0..n_perms)
+        .map(|_| {
+            permutation.shuffle(rng);
+            correlation(u_inner_product(&a, &b, |i| permutation[i]))
+        })
+        .collect::<Vec<_>>();
+    let extreme = distribution.iter().filter(|&&r| r >= observed).count();
+    crate::resampling::PermutationTest {
+        statistic: FiniteF64::new(observed).unwrap(),
+        p_value: NormalizedF64::new((1 + extreme) as f64 / (1 + n_perms) as f64).unwrap(),
+        distribution,
+    }
+}
+
+/// `sqrt(dVar^2(x) dVar^2(y))`, or `None` if either distance variance is not positive
+fn u_scale(a: &[Vec<f64>], b: &[Vec<f64>]) -> Option<f64> {
+    let variance_x = u_inner_product(a, a, |i| i);
+    let variance_y = u_inner_product(b, b, |i| i);
+    (variance_x > 0. && variance_y > 0.).then(|| (variance_x * variance_y).sqrt())
+}
+
+fn u_centered_pair(x: &[f64], y: &[f64]) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+    assert_eq!(x.len(), y.len());
+    assert!(x.len() >= 4);
+    (u_centered(x), u_centered(y))
+}
+
+/// Distance matrix with the U-centering `a_ij - a_i. / (n - 2) - a_.j / (n - 2) + a_.. / ((n - 1)(n - 2))` off the diagonal and zeros on it
+fn u_centered(values: &[f64]) -> Vec<Vec<f64>> {
+    let n = values.len() as f64;
+    let distances = values
+        .iter()
+        .map(|a| values.iter().map(|b| (a - b).abs()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let row_sums = distances
+        .iter()
+        .map(|row| row.iter().sum::<f64>())
+        .collect::<Vec<_>>();
+    let total = row_sums.iter().sum::<f64>();
+    distances
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(j, d)| {
+                    if i == j {
+                        return 0.;
+                    }
+                    d - (row_sums[i] + row_sums[j]) / (n - 2.) + total / ((n - 1.) * (n - 2.))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// `sum_{i != j} a_ij b_{π(i) π(j)} / (n (n - 3))`
+fn u_inner_product(a: &[Vec<f64>], b: &[Vec<f64>], permutation: impl Fn(usize) -> usize) -> f64 {
+    let n = a.len();
+    let sum = (0..n)
+        .flat_map(|i| (0..n).map(move |j| (i, j)))
+        .map(|(i, j)| a[i][j] * b[permutation(i)][permutation(j)])
+        .sum::<f64>();
+    sum / (n * (n - 3)) as f64
+}
+
+/// Ranks starting from 1; ties get the average of their ranks.
+pub(crate) fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order = (0..values.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap());
+
+    let mut ranks = vec![0.; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        // Ranks `start + 1..=end` are shared
+        let average_rank = (start + 1 + end) as f64 / 2.;
+        for &index in &order[start..end] {
+            ranks[index] = average_rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Sizes of the groups of equal consecutive values in a sorted sequence
+pub(crate) fn tie_group_sizes<T: PartialEq>(sorted: impl Iterator<Item = T>) -> Vec<usize> {
+    let mut groups = vec![];
+    let mut previous = None;
+    for value in sorted {
+        match &previous {
+            Some(previous) if *previous == value => *groups.last_mut().unwrap() += 1,
+            _ => groups.push(1),
+        }
+        previous = Some(value);
+    }
+    groups
+}
+
+/// Stable merge sort returning the number of strict inversions
+fn merge_sort_count_inversions(values: &mut [f64], buffer: &mut [f64]) -> usize {
+    let n = values.len();
+    if n < 2 {
+        return 0;
+    }
+    let middle = n / 2;
+    let mut inversions = merge_sort_count_inversions(&mut values[..middle], &mut buffer[..middle])
+        + merge_sort_count_inversions(&mut values[middle..], &mut buffer[middle..]);
+
+    let (mut left, mut right) = (0, middle);
+    for slot in buffer[..n].iter_mut() {
+        if right == n || (left < middle && values[left] <= values[right]) {
+            *slot = values[left];
+            left += 1;
+        } else {
+            *slot = values[right];
+            // Every remaining value on the left is strictly greater
+            inversions += middle - left;
+            right += 1;
+        }
+    }
+    values.copy_from_slice(&buffer[..n]);
+    inversions
+}
+
+pub(crate) fn pearson(x: &[f64], y: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let mean_x = x.iter().sum::<f64>() / n;
+    let mean_y = y.iter().sum::<f64>() / n;
+    let mut covariance = 0.;
+    let mut variance_x = 0.;
+    let mut variance_y = 0.;
+    x.iter().zip(y).for_each(|(x, y)| {
+        let dx = x - mean_x;
+        let dy = y - mean_y;
+        covariance += dx * dy;
+        variance_x += dx.powi(2);
+        variance_y += dy.powi(2);
+    });
+    assert!(variance_x > 0.);
+    assert!(variance_y > 0.);
+    (covariance / (variance_x * variance_y).sqrt()).clamp(-1., 1.)
+}
+
+/// Heap's algorithm
+pub(crate) fn for_each_permutation(values: &mut [f64], visit: &mut impl FnMut(&[f64])) {
+    let n = values.len();
+    let mut counters = vec![0; n];
+    visit(values);
+    let mut i = 0;
+    while i < n {
+        if counters[i] < i {
+            if i % 2 == 0 {
+                values.swap(0, i);
+            } else {
+                values.swap(counters[i], i);
+            }
+            visit(values);
+            counters[i] += 1;
+            i = 0;
+        } else {
+            counters[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranks() {
+        assert_eq!(ranks(&[3., 1., 4., 1., 5.]), [3., 1.5, 4., 1.5, 5.]);
+    }
+
+    #[test]
+    fn test_spearman() {
+        // ref: <https://en.wikipedia.org/wiki/Spearman%27s_rank_correlation_coefficient#Example>
+        let iq = [106., 100., 86., 101., 99., 103., 97., 113., 112., 110.];
+        let hours_of_tv = [7., 27., 2., 50., 28., 29., 20., 12., 6., 17.];
+        let (rho, p) = spearman(&iq, &hours_of_tv);
+        assert!((rho.get() - -0.1758).abs() < 0.0001);
+        assert!(p.get() > 0.05);
+    }
+
+    #[test]
+    fn test_spearman_exact() {
+        let x = [1., 2., 3., 4., 5., 6., 7., 8.];
+        let y = [2., 1., 4., 3., 6., 5., 8., 7.];
+        let (rho, p) = spearman_exact(&x, &y);
+        assert!((rho.get() - 0.9048).abs() < 0.0001);
+        assert!(p.get() < 0.05);
+
+        let (_, p) = spearman_exact(&[1., 2., 3.], &[1., 2., 3.]);
+        assert!((p.get() - 2. / 6.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_kendall_tau() {
+        // ref: `scipy.stats.kendalltau`
+        let x = [12., 2., 1., 12., 2.];
+        let y = [1., 4., 7., 1., 0.];
+        let (tau, p) = kendall_tau(&x, &y);
+        assert!((tau.get() - -0.4714).abs() < 0.0001);
+        assert!(p.get() > 0.05);
+
+        let x = (0..100).map(|i| i as f64).collect::<Vec<_>>();
+        let y = x
+            .iter()
+            .map(|x| (x * 7.) % 13. + x / 10.)
+            .collect::<Vec<_>>();
+        let (tau, p) = kendall_tau(&x, &y);
+        assert!((tau.get() - naive_tau_b(&x, &y)).abs() < 1e-12);
+        assert!(p.get() < 0.05);
+    }
+
+    fn naive_tau_b(x: &[f64], y: &[f64]) -> f64 {
+        let (mut s, mut x_untied, mut y_untied) = (0., 0., 0.);
+        for i in 0..x.len() {
+            for j in i + 1..x.len() {
+                let dx = (x[i] - x[j]).signum() * (x[i] != x[j]) as u8 as f64;
+                let dy = (y[i] - y[j]).signum() * (y[i] != y[j]) as u8 as f64;
+                s += dx * dy;
+                x_untied += dx.abs();
+                y_untied += dy.abs();
+            }
+        }
+        s / (x_untied * y_untied).sqrt()
+    }
+
+    #[test]
+    fn test_distance_correlation() {
+        let x = [1., 2., 3., 4., 5., 6.];
+        let y = [2., 1., 4., 3., 6., 5.];
+        assert!((distance_correlation(&x, &y).get() - 5. / 7.).abs() < 1e-12);
+
+        let x = (0..40)
+            .map(|i| -1. + 2. * i as f64 / 39.)
+            .collect::<Vec<_>>();
+        let y = x.iter().map(|x| x * x).collect::<Vec<_>>();
+        assert!(pearson(&x, &y).abs() < 1e-12);
+        assert!((distance_correlation(&x, &y).get() - 0.182_305_648).abs() < 1e-9);
+        assert_eq!(distance_correlation(&x, &[1.; 40]).get(), 0.);
+
+        #[cfg(feature = "rand")]
+        {
+            use rand::{rngs::StdRng, SeedableRng};
+
+            let mut rng = StdRng::seed_from_u64(0);
+            let test = distance_correlation_test(&x, &y, 2000, &mut rng);
+            assert!((test.statistic.get() - 0.182_305_648).abs() < 1e-9);
+            assert!(test.p_value.get() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_partial() {
+        let x = [2.1, 3.4, 1.9, 5.6, 4.2, 6.1, 3.3, 7.0, 5.5, 6.4];
+        let y = [1.0, 2.2, 1.5, 3.9, 2.8, 4.4, 2.0, 5.1, 4.8, 4.0];
+        let z = vec![1., 2., 1., 4., 3., 5., 2., 6., 5., 5.];
+        let result = partial(&x, &y, std::slice::from_ref(&z));
+        assert!((result.partial.get() - -0.348_509_744).abs() < 1e-9);
+        assert!((result.semi_partial.get() - -0.059_369_944).abs() < 1e-9);
+        assert!((result.test.statistic.get() - -0.983_745_906).abs() < 1e-9);
+        assert!((result.test.p_value.get() - 0.358_012_646).abs() < 1e-9);
+
+        let result = partial(&x, &y, &[]);
+        assert!((result.partial.get() - pearson(&x, &y)).abs() < 1e-12);
+        assert!((result.semi_partial.get() - pearson(&x, &y)).abs() < 1e-12);
+
+        // A collinear control changes nothing
+        let doubled = z.iter().map(|z| 2. * z + 1.).collect::<Vec<_>>();
+        let result = partial(&x, &y, &[z.clone(), doubled]);
+        assert!((result.partial.get() - -0.348_509_744).abs() < 1e-9);
+        assert!((result.test.p_value.get() - 0.358_012_646).abs() < 1e-9);
+
+        // `x` fully explained by the control
+        let result = partial(&z, &y, std::slice::from_ref(&z));
+        assert_eq!(result.partial.get(), 0.);
+        assert_eq!(result.test.p_value.get(), 1.);
+    }
+}
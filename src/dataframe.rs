@@ -0,0 +1,158 @@
+//! Front-end over Polars `DataFrame`s, returning each test as a one-row frame.
+
+use polars::prelude::*;
+
+use crate::{
+    categorical::ContingencyTable,
+    numerical::{difference_of_two_means_result, NumericalSample},
+    DegreesOfFreedom, TestResult,
+};
+
+/// Two-sample t test of `value_col` between the two groups in `group_col`, as by [`difference_of_two_means_result`] with a null difference of zero.
+///
+/// - Rows with a null value or group are dropped.
+/// - The groups are in order of first appearance.
+/// - Errors unless each group has at least 2 values and the values are not all equal within both.
+/// - Columns: `group_1`, `group_2`, `statistic`, `df`, `p_value`, `effect_size`
+pub fn t_test_by(df: &DataFrame, value_col: &str, group_col: &str) -> PolarsResult<DataFrame> {
+    let values = df.column(value_col)?.cast(&DataType::Float64)?;
+    let values = values.f64()?;
+    let groups = df.column(group_col)?.cast(&DataType::String)?;
+    let groups = groups.str()?;
+
+    let mut labels: Vec<&str> = vec![];
+    let mut samples: Vec<Vec<f64>> = vec![];
+    for (value, group) in values.into_iter().zip(groups) {
+        let (Some(value), Some(group)) = (value, group) else {
+            continue;
+        };
+        match labels.iter().position(|&label| label == group) {
+            Some(i) => samples[i].push(value),
+            None => {
+                labels.push(group);
+                samples.push(vec![value]);
+            }
+        }
+    }
+    polars_ensure!(
+        labels.len() == 2,
+        ComputeError: "expected 2 groups in `{}`, found {}", group_col, labels.len()
+    );
+    polars_ensure!(
+        samples.iter().all(|sample| sample.len() >= 2),
+        ComputeError: "expected at least 2 values of `{}` in each group", value_col
+    );
+
+    let (sample_1, sample_2) = (
+        NumericalSample::from_values(&samples[0]),
+        NumericalSample::from_values(&samples[1]),
+    );
+    polars_ensure!(
+        sample_1.variance.get() + sample_2.variance.get() > 0.,
+        ComputeError: "expected values of `{}` that are not all equal within the groups", value_col
+    );
+    let result =
+        difference_of_two_means_result(sample_1, sample_2, strict_num::FiniteF64::new(0.).unwrap());
+    let mut frame = df!(
+        "group_1" => [labels[0]],
+        "group_2" => [labels[1]],
+    )?;
+    frame.hstack_mut(tidy(&result)?.get_columns())?;
+    Ok(frame)
+}
+
+/// Chi-square test of independence on the cross tabulation of `col_a` against `col_b`, as by [`crate::categorical::two_way_table_independence_result`].
+///
+/// - Rows with a null in either column are dropped.
+/// - Errors if an expected count is below [`crate::categorical::MIN_EXPECTED_COUNT`].
+/// - Columns: `statistic`, `df`, `p_value`, `effect_size` (Cramér's V)
+pub fn chi_square_crosstab(df: &DataFrame, col_a: &str, col_b: &str) -> PolarsResult<DataFrame> {
+    let a = df.column(col_a)?.cast(&DataType::String)?;
+    let a = a.str()?;
+    let b = df.column(col_b)?.cast(&DataType::String)?;
+    let b = b.str()?;
+
+    let table =
+        ContingencyTable::from_pairs(a.into_iter().zip(b).filter_map(|(a, b)| Some((a?, b?))));
+    polars_ensure!(
+        table.row_labels.len() >= 2 && table.col_labels.len() >= 2,
+        ComputeError: "expected at least 2 levels in each of `{}` and `{}`", col_a, col_b
+    );
+    if let Some(diagnostic) = table
+        .expected_count_report()
+        .into_iter()
+        .find(|diagnostic| !diagnostic.is_satisfied())
+    {
+        polars_bail!(
+            ComputeError: "{} is {}, less than {}",
+            diagnostic.condition, diagnostic.observed.get(), diagnostic.required.get()
+        );
+    }
+    tidy(&table.independence())
+}
+
+/// One-row frame of `statistic`, `df`, `p_value`, and `effect_size`
+fn tidy(result: &TestResult) -> PolarsResult<DataFrame> {
+    let df = match result.df {
+        Some(DegreesOfFreedom::One(df)) => Some(df.get()),
+        Some(DegreesOfFreedom::Two(..)) | None => None,
+    };
+    df!(
+        "statistic" => [result.statistic.get()],
+        "df" => [df],
+        "p_value" => [result.p_value.get()],
+        "effect_size" => [result.effect_size.map(|effect_size| effect_size.get())],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_t_test_by() {
+        let df = df!(
+            "value" => [7.1, 6.8, 7.4, 7.9, 6.2, 6.9, 6.5, 6.1],
+            "group" => ["a", "a", "a", "a", "b", "b", "b", "b"],
+        )
+        .unwrap();
+        let result = t_test_by(&df, "value", "group").unwrap();
+        assert_eq!(result.shape(), (1, 6));
+        let statistic = result.column("statistic").unwrap().f64().unwrap().get(0);
+        let expected = difference_of_two_means_result(
+            NumericalSample::from_values(&[7.1, 6.8, 7.4, 7.9]),
+            NumericalSample::from_values(&[6.2, 6.9, 6.5, 6.1]),
+            strict_num::FiniteF64::new(0.).unwrap(),
+        );
+        assert_eq!(statistic, Some(expected.statistic.get()));
+
+        let df = df!("value" => [1., 2., 3.], "group" => ["a", "b", "c"]).unwrap();
+        assert!(t_test_by(&df, "value", "group").is_err());
+        let df = df!("value" => [1., 2., 3.], "group" => ["a", "a", "b"]).unwrap();
+        assert!(t_test_by(&df, "value", "group").is_err());
+        let df = df!("value" => [1., 1., 2., 2.], "group" => ["a", "a", "b", "b"]).unwrap();
+        assert!(t_test_by(&df, "value", "group").is_err());
+    }
+
+    #[test]
+    fn test_chi_square_crosstab() {
+        let matrix = [[2, 23, 36], [71, 50, 37]];
+        let mut a = vec![];
+        let mut b = vec![];
+        for (r, row) in matrix.iter().enumerate() {
+            for (c, &count) in row.iter().enumerate() {
+                a.extend(std::iter::repeat_n(r as i32, count));
+                b.extend(std::iter::repeat_n(["x", "y", "z"][c], count));
+            }
+        }
+        let df = df!("a" => a, "b" => b).unwrap();
+        let result = chi_square_crosstab(&df, "a", "b").unwrap();
+        let statistic = result.column("statistic").unwrap().f64().unwrap().get(0);
+        assert!((statistic.unwrap() - 40.128_035).abs() < 1e-5);
+        let df = result.column("df").unwrap().f64().unwrap().get(0);
+        assert_eq!(df, Some(2.));
+
+        let df = df!("a" => [1, 1, 2, 2], "b" => ["x", "y", "x", "y"]).unwrap();
+        assert!(chi_square_crosstab(&df, "a", "b").is_err());
+    }
+}
@@ -0,0 +1,427 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    categorical::CountAndExpect, correlation::pearson, distributions::ContinuousDistribution,
+};
+#[cfg(feature = "rand")]
+use crate::{Alternative, TestResult};
+
+/// One point of a Q–Q plot
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QqPoint {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub theoretical: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub sample: FiniteF64,
+}
+
+/// Quantiles of `distribution` against the sorted `data`.
+///
+/// - The plotting positions are Filliben's estimates of the medians of the uniform order statistics.
+pub fn qq_points(data: &[f64], distribution: &dyn ContinuousDistribution) -> Vec<QqPoint> {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    filliben_positions(sorted.len())
+        .into_iter()
+        .zip(sorted)
+        .map(|(p, x)| QqPoint {
+            theoretical: distribution.quantile(NormalizedF64::new(p).unwrap()),
+            sample: FiniteF64::new(x).unwrap(),
+        })
+        .collect()
+}
+
+/// Pearson correlation of the points of a Q–Q plot, the probability plot correlation coefficient
+pub fn probability_plot_correlation(points: &[QqPoint]) -> FiniteF64 {
+    let theoretical = points
+        .iter()
+        .map(|p| p.theoretical.get())
+        .collect::<Vec<_>>();
+    let sample = points.iter().map(|p| p.sample.get()).collect::<Vec<_>>();
+    FiniteF64::new(pearson(&theoretical, &sample)).unwrap()
+}
+
+/// Filliben's probability plot correlation test of normality.
+///
+/// Null hypothesis: `data` come from a normal distribution.
+///
+/// - The statistic is the correlation of the normal Q–Q plot; small values reject.
+/// - The p-value is the share of `n_sims` normal samples of the same size with a correlation at most as large.
+#[cfg(feature = "rand")]
+pub fn filliben_test(data: &[f64], n_sims: usize, seed: u64) -> TestResult {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::distributions::normal::{standard_normal_sample, Normal};
+
+    assert!(data.len() >= 3);
+    assert!(n_sims > 0);
+    let normal = Normal::standard();
+    let correlation = |data: &[f64]| probability_plot_correlation(&qq_points(data, &normal)).get();
+    let observed = correlation(data);
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut sample = vec![0.; data.len()];
+    let hits = (0..n_sims)
+        .filter(|_| {
+            sample
+                .iter_mut()
+                .for_each(|x| *x = standard_normal_sample(&mut rng));
+            correlation(&sample) <= observed
+        })
+        .count();
+    TestResult {
+        statistic: FiniteF64::new(observed).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new((1 + hits) as f64 / (1 + n_sims) as f64).unwrap(),
+        alternative: Alternative::Less,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// How to choose the bins of a histogram
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinRule {
+    /// `ceil(log2(n)) + 1` bins
+    Sturges,
+    /// Width `2 IQR / n^(1/3)`, or [`BinRule::Sturges`] if the IQR is zero
+    FreedmanDiaconis,
+    /// Width `3.49 s / n^(1/3)`
+    Scott,
+    /// Bins of this width from the smallest observation on
+    FixedWidth(f64),
+}
+
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    /// `bins.len() + 1` ascending edges from the smallest to the largest observation, or beyond it for [`BinRule::FixedWidth`]
+    pub edges: Vec<f64>,
+    /// Observed counts and the counts expected under the distribution
+    ///
+    /// - The first and the last bins extend to the ends of the support in their expected counts, so the expected counts add up to `n`.
+    pub bins: Vec<CountAndExpect>,
+}
+
+/// Histogram of `data` with the counts expected under `distribution`, ready for [`crate::categorical::fitness`]
+///
+/// - Each bin includes its lower edge; the last bin also includes its upper edge.
+/// - Merge bins with small expected counts before the asymptotic chi-square test.
+pub fn histogram(
+    data: &[f64],
+    rule: BinRule,
+    distribution: &dyn ContinuousDistribution,
+) -> Histogram {
+    assert!(data.len() >= 2);
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+    assert!(min < max);
+    let range = max - min;
+
+    let n = sorted.len() as f64;
+    let bins_of_width = |width: f64| {
+        assert!(width > 0.);
+        (range / width).ceil().max(1.) as usize
+    };
+    let sturges = n.log2().ceil() as usize + 1;
+    let count = match rule {
+        BinRule::Sturges => sturges,
+        BinRule::FreedmanDiaconis => {
+            let iqr = quantile_of_sorted(&sorted, 0.75) - quantile_of_sorted(&sorted, 0.25);
+            if iqr == 0. {
+                sturges
+            } else {
+                bins_of_width(2. * iqr / n.cbrt())
+            }
+        }
+        BinRule::Scott => {
+            let mean = sorted.iter().sum::<f64>() / n;
+            let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.);
+            bins_of_width(3.49 * variance.sqrt() / n.cbrt())
+        }
+        BinRule::FixedWidth(width) => bins_of_width(width),
+    };
+    let (width, last_edge) = match rule {
+        BinRule::FixedWidth(width) => (width, min + width * count as f64),
+        _ => (range / count as f64, max),
+    };
+    let edges = (0..=count)
+        .map(|i| {
+            if i == count {
+                last_edge
+            } else {
+                min + width * i as f64
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut counts = vec![0; count];
+    for &x in &sorted {
+        let bin = (((x - min) / width) as usize).min(count - 1);
+        counts[bin] += 1;
+    }
+    let cdf = |i: usize| match i {
+        0 => 0.,
+        _ if i == count => 1.,
+        _ => distribution.cdf(FiniteF64::new(edges[i]).unwrap()).get(),
+    };
+    let bins = counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| CountAndExpect {
+            count,
+            expect: PositiveF64::new(n * (cdf(i + 1) - cdf(i)).max(0.)).unwrap(),
+        })
+        .collect();
+    Histogram { edges, bins }
+}
+
+/// Empirical cumulative distribution function of a sample
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ecdf {
+    sorted: Vec<f64>,
+}
+impl Ecdf {
+    pub fn new(data: &[f64]) -> Self {
+        assert!(!data.is_empty());
+        let mut sorted = data.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Self { sorted }
+    }
+
+    /// The sample in ascending order
+    pub fn values(&self) -> &[f64] {
+        &self.sorted
+    }
+
+    /// Proportion of the sample less than or equal to `x`
+    pub fn eval(&self, x: f64) -> NormalizedF64 {
+        let count = self.sorted.partition_point(|&value| value <= x);
+        NormalizedF64::new(count as f64 / self.sorted.len() as f64).unwrap()
+    }
+
+    /// The smallest sample value whose [`Self::eval`] is at least `p`
+    pub fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        let n = self.sorted.len();
+        let rank = ((p.get() * n as f64).ceil() as usize).clamp(1, n);
+        FiniteF64::new(self.sorted[rank - 1]).unwrap()
+    }
+
+    /// Half width `sqrt(ln(2 / alpha) / (2 n))` of the band that covers the true CDF everywhere with probability at least `confidence`, by the Dvoretzky–Kiefer–Wolfowitz inequality
+    pub fn band_width(&self, confidence: NormalizedF64) -> PositiveF64 {
+        let alpha = 1. - confidence.get();
+        assert!(alpha > 0.);
+        let n = self.sorted.len() as f64;
+        PositiveF64::new(((2. / alpha).ln() / (2. * n)).sqrt()).unwrap()
+    }
+
+    /// The DKW confidence band at `x`, clamped to `[0, 1]`
+    pub fn confidence_band(
+        &self,
+        x: f64,
+        confidence: NormalizedF64,
+    ) -> (NormalizedF64, NormalizedF64) {
+        let cdf = self.eval(x).get();
+        let width = self.band_width(confidence).get();
+        (
+            NormalizedF64::new((cdf - width).max(0.)).unwrap(),
+            NormalizedF64::new((cdf + width).min(1.)).unwrap(),
+        )
+    }
+}
+
+/// `p`-Wasserstein distance between the empirical distributions of `x` and `y`, the earth mover's distance for `p = 1`
+///
+/// - `(∫_0^1 |F^-1(u) - G^-1(u)|^p du)^(1 / p)` over the quantile functions of the two samples
+/// - `p`: at least 1
+pub fn wasserstein_1d(x: &[f64], y: &[f64], p: PositiveF64) -> PositiveF64 {
+    assert!(!x.is_empty());
+    assert!(!y.is_empty());
+    let p = p.get();
+    assert!(p >= 1.);
+    let x = Ecdf::new(x);
+    let y = Ecdf::new(y);
+    let (x, y) = (x.values(), y.values());
+    let (n, m) = (x.len(), y.len());
+
+    // The quantile functions are constant between the breakpoints `i / n` and `j / m`; compared over a common denominator `n m`
+    let (mut i, mut j) = (0, 0);
+    let mut position = 0;
+    let mut integral = 0.;
+    while i < n && j < m {
+        let next = ((i + 1) * m).min((j + 1) * n);
+        let width = (next - position) as f64 / (n * m) as f64;
+        integral += width * (x[i] - y[j]).abs().powf(p);
+        position = next;
+        if (i + 1) * m == next {
+            i += 1;
+        }
+        if (j + 1) * n == next {
+            j += 1;
+        }
+    }
+    PositiveF64::new(integral.powf(1. / p)).unwrap()
+}
+
+/// [`crate::resampling::permutation_test`] of [`wasserstein_1d`].
+///
+/// Null hypothesis: `x` and `y` come from the same distribution.
+#[cfg(feature = "rand")]
+pub fn wasserstein_test(
+    x: &[f64],
+    y: &[f64],
+    p: PositiveF64,
+    n_perms: usize,
+    rng: &mut impl rand::Rng,
+) -> crate::resampling::PermutationTest {
+    crate::resampling::permutation_test(x, y, |x, y| wasserstein_1d(x, y, p).get(), n_perms, rng)
+}
+
+/// Quantile of ascending values by linear interpolation between the closest ranks, with `q` clamped to `[0, 1]`
+pub(crate) fn quantile_of_sorted(sorted: &[f64], q: f64) -> f64 {
+    let position = q.clamp(0., 1.) * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (position - lower as f64) * (sorted[upper] - sorted[lower])
+}
+
+/// `m_1 = 1 - m_n`, `m_i = (i - 0.3175) / (n + 0.365)`, `m_n = 0.5^(1 / n)`
+fn filliben_positions(n: usize) -> Vec<f64> {
+    assert!(n >= 1);
+    let last = 0.5_f64.powf(1. / n as f64);
+    (1..=n)
+        .map(|i| match i {
+            _ if i == n => last,
+            1 => 1. - last,
+            _ => (i as f64 - 0.3175) / (n as f64 + 0.365),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::normal::Normal;
+
+    const DATA: [f64; 20] = [
+        1.2, 2.3, 0.8, 3.1, 1.7, 2.9, 0.5, 1.1, 4.2, 2.0, 1.5, 0.9, 2.6, 3.8, 1.3, 0.7, 1.9, 2.4,
+        5.1, 1.6,
+    ];
+
+    #[test]
+    fn test_ecdf() {
+        let ecdf = Ecdf::new(&DATA);
+        assert_eq!(ecdf.values()[0], 0.5);
+        assert_eq!(ecdf.eval(0.), NormalizedF64::ZERO);
+        assert_eq!(ecdf.eval(1.5).get(), 0.4);
+        assert_eq!(ecdf.eval(1.55).get(), 0.4);
+        assert_eq!(ecdf.eval(10.).get(), 1.);
+
+        assert_eq!(ecdf.quantile(NormalizedF64::new(0.4).unwrap()).get(), 1.5);
+        assert_eq!(ecdf.quantile(NormalizedF64::new(0.41).unwrap()).get(), 1.6);
+        assert_eq!(ecdf.quantile(NormalizedF64::ZERO).get(), 0.5);
+        assert_eq!(ecdf.quantile(NormalizedF64::ONE).get(), 5.1);
+
+        // `sqrt(ln(40) / 40)`
+        let confidence = NormalizedF64::new(0.95).unwrap();
+        let width = ecdf.band_width(confidence).get();
+        assert!((width - 0.303_680_731).abs() < 1e-9);
+        let (lower, upper) = ecdf.confidence_band(1.5, confidence);
+        assert!((lower.get() - (0.4 - width)).abs() < 1e-12);
+        assert!((upper.get() - (0.4 + width)).abs() < 1e-12);
+        let (lower, _) = ecdf.confidence_band(0., confidence);
+        assert_eq!(lower.get(), 0.);
+    }
+
+    #[test]
+    fn test_qq_points() {
+        let points = qq_points(&DATA, &Normal::standard());
+        assert_eq!(points.len(), DATA.len());
+        assert!((points[0].theoretical.get() - -1.824_163_604).abs() < 1e-9);
+        assert!((points[1].theoretical.get() - -1.387_680_124).abs() < 1e-9);
+        assert!((points[19].theoretical.get() - 1.824_163_604).abs() < 1e-9);
+        assert_eq!(points[0].sample.get(), 0.5);
+        assert_eq!(points[19].sample.get(), 5.1);
+        let correlation = probability_plot_correlation(&points);
+        assert!((correlation.get() - 0.964_974_399).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let normal = Normal::fit(&DATA);
+        let sturges = histogram(&DATA, BinRule::Sturges, &normal);
+        assert_eq!(sturges.bins.len(), 6);
+        assert_eq!(sturges.edges.len(), 7);
+        assert_eq!(sturges.edges[0], 0.5);
+        assert_eq!(sturges.edges[6], 5.1);
+        let counts = sturges.bins.iter().map(|bin| bin.count).collect::<Vec<_>>();
+        assert_eq!(counts, [6, 6, 3, 2, 2, 1]);
+        let expected = sturges.bins.iter().map(|bin| bin.expect.get()).sum::<f64>();
+        assert!((expected - 20.).abs() < 1e-12);
+
+        // IQR of 1.5 gives a width of about 1.1
+        let freedman_diaconis = histogram(&DATA, BinRule::FreedmanDiaconis, &normal);
+        assert_eq!(freedman_diaconis.bins.len(), 5);
+        let fixed_width = histogram(&DATA, BinRule::FixedWidth(1.), &normal);
+        assert_eq!(fixed_width.bins.len(), 5);
+        assert_eq!(fixed_width.edges, [0.5, 1.5, 2.5, 3.5, 4.5, 5.5]);
+        let counts = fixed_width.bins.iter().map(|bin| bin.count).sum::<usize>();
+        assert_eq!(counts, DATA.len());
+
+        // Zero IQR falls back to Sturges
+        let tied = [1., 2., 2., 2., 2., 2., 2., 2., 3.];
+        let freedman_diaconis = histogram(&tied, BinRule::FreedmanDiaconis, &normal);
+        assert_eq!(freedman_diaconis.bins.len(), 5);
+    }
+
+    #[test]
+    fn test_quantile_of_sorted() {
+        let sorted = [1., 2., 3., 4., 5.];
+        assert_eq!(quantile_of_sorted(&sorted, 0.), 1.);
+        assert_eq!(quantile_of_sorted(&sorted, 0.5), 3.);
+        assert_eq!(quantile_of_sorted(&sorted, 0.625), 3.5);
+        assert_eq!(quantile_of_sorted(&sorted, 1.), 5.);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_filliben_test() {
+        let test = filliben_test(&DATA, 2_000, 0);
+        assert!((test.statistic.get() - 0.964_974_399).abs() < 1e-9);
+        assert!(test.p_value.get() > 0.05);
+
+        // Evenly spaced exponential quantiles are far from normal
+        let exponential = (1..=30)
+            .map(|i| -(1. - i as f64 / 31.).ln())
+            .collect::<Vec<_>>();
+        assert!(filliben_test(&exponential, 2_000, 0).p_value.get() < 0.05);
+    }
+
+    #[test]
+    fn test_wasserstein_1d() {
+        let x = [6., 1., 3.];
+        let y = [2., 9., 2., 5.];
+        let one = PositiveF64::new(1.).unwrap();
+        let two = PositiveF64::new(2.).unwrap();
+        assert!((wasserstein_1d(&x, &y, one).get() - 5. / 3.).abs() < 1e-12);
+        assert!((wasserstein_1d(&x, &y, two).get() - 3.5_f64.sqrt()).abs() < 1e-12);
+        // Shifting moves every unit of mass by the same distance
+        let shifted = x.map(|v| v + 2.5);
+        assert!((wasserstein_1d(&x, &shifted, two).get() - 2.5).abs() < 1e-12);
+        assert_eq!(wasserstein_1d(&x, &x, one).get(), 0.);
+
+        #[cfg(feature = "rand")]
+        {
+            use rand::{rngs::StdRng, SeedableRng};
+
+            let mut rng = StdRng::seed_from_u64(0);
+            let x = [1.2, 0.8, 1.1, 0.9, 1.0, 1.3, 0.7, 1.05];
+            let y = x.map(|v| v + 1.);
+            let test = wasserstein_test(&x, &y, one, 2000, &mut rng);
+            assert!((test.statistic.get() - 1.).abs() < 1e-12);
+            assert!(test.p_value.get() < 0.01);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+#[cfg(feature = "rand")]
+use rand::{distributions::Distribution, Rng};
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+#[cfg(feature = "rand")]
+use super::gamma::standard_gamma_sample;
+use super::ContinuousDistribution;
+use crate::special::{betainc_regularized, bisect, ln_gamma};
+
+/// Beta distribution on `[0, 1]` with shape parameters `a > 0` and `b > 0`
+#[derive(Debug, Clone, Copy)]
+pub struct Beta {
+    pub a: PositiveF64,
+    pub b: PositiveF64,
+}
+impl Beta {
+    /// Probability density at `x`
+    ///
+    /// - Unbounded at 0 if `a < 1` and at 1 if `b < 1`, which panics.
+    pub fn pdf(&self, x: NormalizedF64) -> PositiveF64 {
+        let (a, b) = self.shape();
+        let x = x.get();
+        let ln_kernel = |shape: f64, x: f64| {
+            if shape == 1. {
+                0.
+            } else {
+                (shape - 1.) * x.ln()
+            }
+        };
+        let ln_beta = ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b);
+        let pdf = (ln_kernel(a, x) + ln_kernel(b, 1. - x) - ln_beta).exp();
+        PositiveF64::new(pdf).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: NormalizedF64) -> NormalizedF64 {
+        let (a, b) = self.shape();
+        let cdf = betainc_regularized(a, b, x.get());
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`
+    pub fn quantile(&self, p: NormalizedF64) -> NormalizedF64 {
+        let (a, b) = self.shape();
+        let x = bisect(|x| betainc_regularized(a, b, x) - p.get(), 0., 1.);
+        NormalizedF64::new(x).unwrap()
+    }
+
+    pub fn mean(&self) -> NormalizedF64 {
+        let (a, b) = self.shape();
+        NormalizedF64::new(a / (a + b)).unwrap()
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        let (a, b) = self.shape();
+        let total = a + b;
+        PositiveF64::new(a * b / (total.powi(2) * (total + 1.))).unwrap()
+    }
+
+    fn shape(&self) -> (f64, f64) {
+        let (a, b) = (self.a.get(), self.b.get());
+        assert!(a > 0.);
+        assert!(b > 0.);
+        (a, b)
+    }
+}
+impl ContinuousDistribution for Beta {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        NormalizedF64::new(x.get()).map_or(PositiveF64::ZERO, |x| Beta::pdf(self, x))
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        match NormalizedF64::new(x.get()) {
+            Some(x) => Beta::cdf(self, x),
+            None if x.get() < 0. => NormalizedF64::ZERO,
+            None => NormalizedF64::ONE,
+        }
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        FiniteF64::new(Beta::quantile(self, p).get()).unwrap()
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(Beta::mean(self).get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        Beta::variance(self)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<f64> for Beta {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let (a, b) = self.shape();
+        let x = standard_gamma_sample(a, rng);
+        let y = standard_gamma_sample(b, rng);
+        x / (x + y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn beta_2_5() -> Beta {
+        Beta {
+            a: PositiveF64::new(2.).unwrap(),
+            b: PositiveF64::new(5.).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_pdf_cdf() {
+        let beta = beta_2_5();
+        let x = NormalizedF64::new(0.3).unwrap();
+        // `30 x (1 - x)^4`
+        assert!((beta.pdf(x).get() - 2.1609).abs() < 1e-12);
+        // `1 - (1 - x)^6 - 6 x (1 - x)^5`
+        assert!((beta.cdf(x).get() - 0.579_825).abs() < 1e-12);
+        assert_eq!(beta.pdf(NormalizedF64::new(0.).unwrap()).get(), 0.);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let beta = beta_2_5();
+        let p = NormalizedF64::new(0.579_825).unwrap();
+        assert!((beta.quantile(p).get() - 0.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_moments() {
+        let beta = beta_2_5();
+        assert!((beta.mean().get() - 2. / 7.).abs() < 1e-15);
+        assert!((beta.variance().get() - 10. / 392.).abs() < 1e-15);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let beta = beta_2_5();
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples = beta.sample_iter(&mut rng).take(20_000).collect::<Vec<_>>();
+        let (mean, variance) = crate::distributions::tests::moments(&samples);
+        assert!((mean - 2. / 7.).abs() < 0.005);
+        assert!((variance - 10. / 392.).abs() < 0.001);
+    }
+}
@@ -0,0 +1,164 @@
+#[cfg(feature = "rand")]
+use rand::{distributions::Distribution, Rng};
+use strict_num::{NormalizedF64, PositiveF64};
+
+use super::DiscreteDistribution;
+use crate::special::{betainc_regularized, ln_binomial_coefficient};
+
+/// Number of successes in `trials` independent trials each succeeding with `probability`
+#[derive(Debug, Clone, Copy)]
+pub struct Binomial {
+    pub trials: usize,
+    pub probability: NormalizedF64,
+}
+impl Binomial {
+    /// Probability of exactly `k` successes
+    pub fn pmf(&self, k: usize) -> NormalizedF64 {
+        let pmf = binomial_ln_pmf(k, self.trials, self.probability.get()).exp();
+        NormalizedF64::new(pmf.clamp(0., 1.)).unwrap()
+    }
+
+    /// Probability of at most `k` successes
+    pub fn cdf(&self, k: usize) -> NormalizedF64 {
+        if k >= self.trials {
+            return NormalizedF64::ONE;
+        }
+        // `P(X <= k) = I_{1 - p}(n - k, k + 1)`
+        let cdf = betainc_regularized(
+            (self.trials - k) as f64,
+            (k + 1) as f64,
+            1. - self.probability.get(),
+        );
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The smallest `k` whose CDF is at least `p`
+    pub fn quantile(&self, p: NormalizedF64) -> usize {
+        let (mut lower, mut upper) = (0, self.trials);
+        while lower < upper {
+            let middle = (lower + upper) / 2;
+            if self.cdf(middle).get() < p.get() {
+                lower = middle + 1;
+            } else {
+                upper = middle;
+            }
+        }
+        lower
+    }
+
+    pub fn mean(&self) -> PositiveF64 {
+        PositiveF64::new(self.trials as f64 * self.probability.get()).unwrap()
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        let p = self.probability.get();
+        PositiveF64::new(self.trials as f64 * p * (1. - p)).unwrap()
+    }
+
+    /// Whether the normal approximation applies, i.e., at least 10 expected successes and failures
+    pub fn is_normally_distributed_enough(&self) -> bool {
+        let a = 10. <= self.trials as f64 * self.probability.get();
+        let b = 10. <= self.trials as f64 * (1. - self.probability.get());
+        a && b
+    }
+}
+impl DiscreteDistribution for Binomial {
+    fn pmf(&self, k: usize) -> NormalizedF64 {
+        Binomial::pmf(self, k)
+    }
+
+    fn cdf(&self, k: usize) -> NormalizedF64 {
+        Binomial::cdf(self, k)
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> usize {
+        Binomial::quantile(self, p)
+    }
+
+    fn mean(&self) -> PositiveF64 {
+        Binomial::mean(self)
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        Binomial::variance(self)
+    }
+}
+
+/// Sampling by inversion of the CDF
+#[cfg(feature = "rand")]
+impl Distribution<usize> for Binomial {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        self.quantile(NormalizedF64::new(rng.gen::<f64>()).unwrap())
+    }
+}
+
+fn binomial_ln_pmf(k: usize, n: usize, p: f64) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    // Avoid `0 ln 0` at the degenerate probabilities
+    let ln_term = |count: usize, probability: f64| {
+        if count == 0 {
+            0.
+        } else {
+            count as f64 * probability.ln()
+        }
+    };
+    ln_binomial_coefficient(n, k) + ln_term(k, p) + ln_term(n - k, 1. - p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binomial_10_03() -> Binomial {
+        Binomial {
+            trials: 10,
+            probability: NormalizedF64::new(0.3).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_pmf_cdf() {
+        let binomial = binomial_10_03();
+        assert!((binomial.pmf(3).get() - 0.266_827_932_0).abs() < 1e-10);
+        assert!((binomial.cdf(3).get() - 0.649_610_718_4).abs() < 1e-10);
+        assert_eq!(binomial.cdf(10).get(), 1.);
+        let sum = (0..=10).map(|k| binomial.pmf(k).get()).sum::<f64>();
+        assert!((sum - 1.).abs() < 1e-12);
+
+        let certain = Binomial {
+            trials: 5,
+            probability: NormalizedF64::ONE,
+        };
+        assert_eq!(certain.pmf(5).get(), 1.);
+        assert_eq!(certain.pmf(4).get(), 0.);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let binomial = binomial_10_03();
+        assert_eq!(binomial.quantile(NormalizedF64::new(0.5).unwrap()), 3);
+        assert_eq!(binomial.quantile(NormalizedF64::new(0.649).unwrap()), 3);
+        assert_eq!(binomial.quantile(NormalizedF64::new(0.65).unwrap()), 4);
+        assert_eq!(binomial.quantile(NormalizedF64::ZERO), 0);
+        assert!(!binomial.is_normally_distributed_enough());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let binomial = binomial_10_03();
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples = binomial
+            .sample_iter(&mut rng)
+            .take(20_000)
+            .map(|k| k as f64)
+            .collect::<Vec<_>>();
+        let (mean, variance) = crate::distributions::tests::moments(&samples);
+        assert!((mean - 3.).abs() < 0.05);
+        assert!((variance - 2.1).abs() < 0.1);
+    }
+}
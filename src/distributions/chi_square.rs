@@ -1,7 +1,14 @@
 use std::num::NonZeroUsize;
 
 use once_cell::sync::Lazy;
-use strict_num::NormalizedF64;
+#[cfg(feature = "rand")]
+use rand::{distributions::Distribution, Rng};
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+#[cfg(feature = "rand")]
+use super::gamma::standard_gamma_sample;
+use super::{gamma::Gamma, normal::Z_SCORE_TABLE};
+use super::{non_negative, ContinuousDistribution};
 
 pub static CHI_SQUARE_TABLE: Lazy<ChiSquareTable> = Lazy::new(Default::default);
 
@@ -76,7 +83,9 @@ impl ChiSquareTable {
     }
 
     pub fn p_value(&self, df: NonZeroUsize, chi_square: f64) -> NormalizedF64 {
-        assert!(df.get() <= MAX_DEGREES_OF_FREEDOM);
+        if df.get() > MAX_DEGREES_OF_FREEDOM {
+            return wilson_hilferty_p_value(df, chi_square);
+        }
         let row = &self.chi_square_values[df.get() - 1];
         let mut i = 0;
         for col in row {
@@ -91,12 +100,86 @@ impl ChiSquareTable {
         NormalizedF64::new(P_VALUE_SEQUENCE[i]).unwrap()
     }
 }
+
+/// Normal approximation of the cube root of `chi_square / df`
+fn wilson_hilferty_p_value(df: NonZeroUsize, chi_square: f64) -> NormalizedF64 {
+    let df = df.get() as f64;
+    let variance = 2. / (9. * df);
+    let z = ((chi_square / df).cbrt() - (1. - variance)) / variance.sqrt();
+    let z = FiniteF64::new(z).unwrap();
+    NormalizedF64::new(1. - Z_SCORE_TABLE.area_up_to(z).get()).unwrap()
+}
+
 impl Default for ChiSquareTable {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Chi-square distribution with `df` degrees of freedom
+#[derive(Debug, Clone, Copy)]
+pub struct ChiSquare {
+    pub df: PositiveF64,
+}
+impl ChiSquare {
+    /// Probability density at `x`
+    ///
+    /// - Unbounded at 0 if `df < 2`, which panics.
+    pub fn pdf(&self, x: PositiveF64) -> PositiveF64 {
+        self.gamma().pdf(x)
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: PositiveF64) -> NormalizedF64 {
+        self.gamma().cdf(x)
+    }
+
+    /// The `x` whose CDF is `p`, for `0 <= p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> PositiveF64 {
+        self.gamma().quantile(p)
+    }
+
+    pub fn mean(&self) -> PositiveF64 {
+        self.df
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        PositiveF64::new(2. * self.df.get()).unwrap()
+    }
+
+    fn gamma(&self) -> Gamma {
+        Gamma::chi_square(self.df)
+    }
+}
+impl ContinuousDistribution for ChiSquare {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        non_negative(x).map_or(PositiveF64::ZERO, |x| ChiSquare::pdf(self, x))
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        non_negative(x).map_or(NormalizedF64::ZERO, |x| ChiSquare::cdf(self, x))
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        FiniteF64::new(ChiSquare::quantile(self, p).get()).unwrap()
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(ChiSquare::mean(self).get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        ChiSquare::variance(self)
+    }
+}
+#[cfg(feature = "rand")]
+impl Distribution<f64> for ChiSquare {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        assert!(self.df.get() > 0.);
+        2. * standard_gamma_sample(self.df.get() / 2., rng)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,4 +213,39 @@ mod tests {
                 < 0.001
         );
     }
+
+    #[test]
+    fn df_100_chi_square_124() {
+        let p = CHI_SQUARE_TABLE.p_value(NonZeroUsize::new(100).unwrap(), 124.342);
+        assert!((p.get() - 0.05).abs() < 0.005);
+        let p = CHI_SQUARE_TABLE.p_value(NonZeroUsize::new(100).unwrap(), 77.929);
+        assert!((p.get() - 0.95).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_chi_square() {
+        let chi_square = ChiSquare {
+            df: PositiveF64::new(5.).unwrap(),
+        };
+        let x = PositiveF64::new(11.070_497_693_516).unwrap();
+        assert!((chi_square.cdf(x).get() - 0.95).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let chi_square = ChiSquare {
+            df: PositiveF64::new(5.).unwrap(),
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples = chi_square
+            .sample_iter(&mut rng)
+            .take(20_000)
+            .collect::<Vec<_>>();
+        let (mean, variance) = crate::distributions::tests::moments(&samples);
+        assert!((mean - 5.).abs() < 0.1);
+        assert!((variance - 10.).abs() < 0.5);
+    }
 }
@@ -0,0 +1,92 @@
+use super::{non_negative, ContinuousDistribution};
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+/// Waiting time until the first event when events occur at `rate`
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exponential {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub rate: PositiveF64,
+}
+impl Exponential {
+    /// Maximum likelihood estimate from non-negative observations, the reciprocal of the mean
+    pub fn fit(data: &[f64]) -> Self {
+        assert!(!data.is_empty());
+        assert!(data.iter().all(|&x| x >= 0.));
+        let mean = data.iter().sum::<f64>() / data.len() as f64;
+        Self {
+            rate: PositiveF64::new(1. / mean).unwrap(),
+        }
+    }
+
+    /// Probability density at `x`
+    pub fn pdf(&self, x: PositiveF64) -> PositiveF64 {
+        let rate = self.rate();
+        PositiveF64::new(rate * (-rate * x.get()).exp()).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: PositiveF64) -> NormalizedF64 {
+        let cdf = -(-self.rate() * x.get()).exp_m1();
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`, for `0 <= p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> PositiveF64 {
+        assert!(p.get() < 1.);
+        PositiveF64::new(-(-p.get()).ln_1p() / self.rate()).unwrap()
+    }
+
+    pub fn mean(&self) -> PositiveF64 {
+        PositiveF64::new(1. / self.rate()).unwrap()
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        PositiveF64::new(1. / self.rate().powi(2)).unwrap()
+    }
+
+    fn rate(&self) -> f64 {
+        assert!(self.rate.get() > 0.);
+        self.rate.get()
+    }
+}
+impl ContinuousDistribution for Exponential {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        non_negative(x).map_or(PositiveF64::ZERO, |x| Exponential::pdf(self, x))
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        non_negative(x).map_or(NormalizedF64::ZERO, |x| Exponential::cdf(self, x))
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        FiniteF64::new(Exponential::quantile(self, p).get()).unwrap()
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(Exponential::mean(self).get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        Exponential::variance(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential() {
+        let exponential = Exponential {
+            rate: PositiveF64::new(2.).unwrap(),
+        };
+        let x = PositiveF64::new(0.5).unwrap();
+        assert!((exponential.pdf(x).get() - 2. * (-1_f64).exp()).abs() < 1e-15);
+        assert!((exponential.cdf(x).get() - (1. - (-1_f64).exp())).abs() < 1e-15);
+        let median = exponential.quantile(NormalizedF64::new(0.5).unwrap());
+        assert!((median.get() - 2_f64.ln() / 2.).abs() < 1e-15);
+        assert!((exponential.mean().get() - 0.5).abs() < 1e-15);
+        assert!((exponential.variance().get() - 0.25).abs() < 1e-15);
+    }
+}
@@ -1,10 +1,14 @@
-use std::{num::NonZeroUsize, rc::Rc};
+use std::num::NonZeroUsize;
 
 use once_cell::sync::Lazy;
-use reikna::func;
-use reikna::func::Function;
-use reikna::integral::integrate_wp;
-use strict_num::{NormalizedF64, PositiveF64};
+#[cfg(feature = "rand")]
+use rand::{distributions::Distribution, Rng};
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+#[cfg(feature = "rand")]
+use super::gamma::standard_gamma_sample;
+use super::{non_negative, ContinuousDistribution};
+use crate::special::{betainc_regularized, bisect, ln_gamma};
 
 pub static F_CDF: Lazy<FCdf> = Lazy::new(Default::default);
 
@@ -22,10 +26,10 @@ impl FCdf {
         let df_1 = params.df_1.get() as f64;
         let df_2 = params.df_2.get() as f64;
         let x = params.x.get();
-        let x = (df_1 * x) / (df_1 * x + df_2);
-        let x = NormalizedF64::new(x).unwrap();
-        let i = incomplete_beta_function(x, df_1 / 2., df_2 / 2.);
-        NormalizedF64::new(1. - i.get()).unwrap()
+        // `1 - I_y(df_1 / 2, df_2 / 2) = I_{1 - y}(df_2 / 2, df_1 / 2)` keeps small p-values accurate
+        let y = df_2 / (df_1 * x + df_2);
+        let p = betainc_regularized(df_2 / 2., df_1 / 2., y);
+        NormalizedF64::new(p.clamp(0., 1.)).unwrap()
     }
 }
 impl Default for FCdf {
@@ -35,16 +39,163 @@ impl Default for FCdf {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FParams {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
     pub x: PositiveF64,
     pub df_1: NonZeroUsize,
     pub df_2: NonZeroUsize,
 }
 
-fn incomplete_beta_function(x: NormalizedF64, a: f64, b: f64) -> NormalizedF64 {
-    let f = func!(move |t: f64| t.powf(a - 1.) * (1. - t).powf(b - 1.));
+/// F distribution with `df_1` and `df_2` degrees of freedom
+#[derive(Debug, Clone, Copy)]
+pub struct F {
+    pub df_1: PositiveF64,
+    pub df_2: PositiveF64,
+}
+impl F {
+    /// Probability density at `x`
+    ///
+    /// - Unbounded at 0 if `df_1 < 2`, which panics.
+    pub fn pdf(&self, x: PositiveF64) -> PositiveF64 {
+        let (df_1, df_2) = self.df();
+        let pdf = df_1 / df_2 * beta_prime_pdf(df_1 * x.get() / df_2, df_1 / 2., df_2 / 2.);
+        PositiveF64::new(pdf).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: PositiveF64) -> NormalizedF64 {
+        let (df_1, df_2) = self.df();
+        let cdf = f_cdf(x.get(), df_1, df_2);
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`, for `0 <= p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> PositiveF64 {
+        assert!(p.get() < 1.);
+        let (df_1, df_2) = self.df();
+        let f = |x: f64| f_cdf(x, df_1, df_2) - p.get();
+        let mut upper = 1.;
+        while f(upper) < 0. {
+            upper *= 2.;
+        }
+        PositiveF64::new(bisect(f, 0., upper)).unwrap()
+    }
+
+    /// Defined for `df_2 > 2`
+    pub fn mean(&self) -> PositiveF64 {
+        let (_, df_2) = self.df();
+        assert!(df_2 > 2.);
+        PositiveF64::new(df_2 / (df_2 - 2.)).unwrap()
+    }
+
+    /// Defined for `df_2 > 4`
+    pub fn variance(&self) -> PositiveF64 {
+        let (df_1, df_2) = self.df();
+        assert!(df_2 > 4.);
+        let variance =
+            2. * df_2.powi(2) * (df_1 + df_2 - 2.) / (df_1 * (df_2 - 2.).powi(2) * (df_2 - 4.));
+        PositiveF64::new(variance).unwrap()
+    }
+
+    fn df(&self) -> (f64, f64) {
+        let (df_1, df_2) = (self.df_1.get(), self.df_2.get());
+        assert!(df_1 > 0.);
+        assert!(df_2 > 0.);
+        (df_1, df_2)
+    }
+}
+impl ContinuousDistribution for F {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        non_negative(x).map_or(PositiveF64::ZERO, |x| F::pdf(self, x))
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        non_negative(x).map_or(NormalizedF64::ZERO, |x| F::cdf(self, x))
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        FiniteF64::new(F::quantile(self, p).get()).unwrap()
+    }
 
-    let numerator = integrate_wp(&f, 0., x.get(), 10);
-    let denominator = integrate_wp(&f, 0., 1., 10);
-    NormalizedF64::new(numerator / denominator).unwrap()
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(F::mean(self).get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        F::variance(self)
+    }
+}
+#[cfg(feature = "rand")]
+impl Distribution<f64> for F {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let (df_1, df_2) = self.df();
+        let chi_square_1 = 2. * standard_gamma_sample(df_1 / 2., rng);
+        let chi_square_2 = 2. * standard_gamma_sample(df_2 / 2., rng);
+        (chi_square_1 / df_1) / (chi_square_2 / df_2)
+    }
+}
+
+/// Density of `X / Y` where `X ~ Gamma(a)` and `Y ~ Gamma(b)`: `y^(a - 1) (1 + y)^(-a - b) / B(a, b)`
+pub(crate) fn beta_prime_pdf(y: f64, a: f64, b: f64) -> f64 {
+    let ln_kernel = if a == 1. { 0. } else { (a - 1.) * y.ln() };
+    let ln_beta = ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b);
+    (ln_kernel - (a + b) * y.ln_1p() - ln_beta).exp()
+}
+
+fn f_cdf(x: f64, df_1: f64, df_2: f64) -> f64 {
+    betainc_regularized(df_1 / 2., df_2 / 2., df_1 * x / (df_1 * x + df_2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_p_value() {
+        let params = FParams {
+            x: PositiveF64::new(1.).unwrap(),
+            df_1: NonZeroUsize::new(5).unwrap(),
+            df_2: NonZeroUsize::new(20).unwrap(),
+        };
+        assert!((F_CDF.p_value(params).get() - (1. - 0.556_974_815)).abs() < 1e-9);
+
+        // Far tail with large degrees of freedom
+        let params = FParams {
+            x: PositiveF64::new(10.).unwrap(),
+            df_1: NonZeroUsize::new(10).unwrap(),
+            df_2: NonZeroUsize::new(1000).unwrap(),
+        };
+        let p = F_CDF.p_value(params).get();
+        assert!(0. < p && p < 1e-14);
+    }
+
+    #[test]
+    fn test_f() {
+        let f = F {
+            df_1: PositiveF64::new(5.).unwrap(),
+            df_2: PositiveF64::new(20.).unwrap(),
+        };
+        let x = PositiveF64::new(1.).unwrap();
+        assert!((f.cdf(x).get() - 0.556_974_815).abs() < 1e-7);
+        let p = NormalizedF64::new(0.556_974_815).unwrap();
+        assert!((f.quantile(p).get() - 1.).abs() < 1e-6);
+        assert!((f.mean().get() - 20. / 18.).abs() < 1e-15);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let f = F {
+            df_1: PositiveF64::new(5.).unwrap(),
+            df_2: PositiveF64::new(20.).unwrap(),
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples = f.sample_iter(&mut rng).take(20_000).collect::<Vec<_>>();
+        let (mean, variance) = crate::distributions::tests::moments(&samples);
+        assert!((mean - f.mean().get()).abs() < 0.02);
+        assert!((variance - f.variance().get()).abs() < 0.1);
+    }
 }
@@ -0,0 +1,217 @@
+#[cfg(feature = "rand")]
+use rand::{distributions::Distribution, Rng};
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+#[cfg(feature = "rand")]
+use super::normal::standard_normal_sample;
+use super::{non_negative, ContinuousDistribution};
+use crate::special::{bisect, digamma, gammainc_regularized, ln_gamma};
+
+/// Gamma distribution on `[0, ∞)` with `shape > 0` and `rate > 0`
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gamma {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub shape: PositiveF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub rate: PositiveF64,
+}
+impl Gamma {
+    /// Chi-square distribution with `df` degrees of freedom, i.e., `Gamma(df / 2, 1 / 2)`
+    pub fn chi_square(df: PositiveF64) -> Self {
+        Self {
+            shape: PositiveF64::new(df.get() / 2.).unwrap(),
+            rate: PositiveF64::new(0.5).unwrap(),
+        }
+    }
+
+    /// Maximum likelihood estimate from positive observations
+    ///
+    /// - The shape solves `ln k - ψ(k) = ln(mean(x)) - mean(ln x)`, whose left side decreases in `k`.
+    pub fn fit(data: &[f64]) -> Self {
+        assert!(data.len() >= 2);
+        assert!(data.iter().all(|&x| x > 0.));
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let mean_ln = data.iter().map(|x| x.ln()).sum::<f64>() / n;
+        let s = mean.ln() - mean_ln;
+        assert!(s > 0.);
+        let score = |k: f64| s - (k.ln() - digamma(k));
+        let mut upper = 1.;
+        while score(upper) < 0. {
+            upper *= 2.;
+        }
+        let mut lower = 1.;
+        while score(lower) > 0. {
+            lower /= 2.;
+        }
+        let shape = bisect(score, lower, upper);
+        Self {
+            shape: PositiveF64::new(shape).unwrap(),
+            rate: PositiveF64::new(shape / mean).unwrap(),
+        }
+    }
+
+    /// Probability density at `x`
+    ///
+    /// - Unbounded at 0 if `shape < 1`, which panics.
+    pub fn pdf(&self, x: PositiveF64) -> PositiveF64 {
+        let (shape, rate) = self.parameters();
+        let x = x.get();
+        let ln_kernel = if shape == 1. {
+            0.
+        } else {
+            (shape - 1.) * x.ln()
+        };
+        let pdf = (shape * rate.ln() + ln_kernel - rate * x - ln_gamma(shape)).exp();
+        PositiveF64::new(pdf).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: PositiveF64) -> NormalizedF64 {
+        let (shape, rate) = self.parameters();
+        let cdf = gammainc_regularized(shape, rate * x.get());
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`, for `0 <= p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> PositiveF64 {
+        assert!(p.get() < 1.);
+        let (shape, rate) = self.parameters();
+        // Solve on the standard scale `rate * x`
+        let f = |y: f64| gammainc_regularized(shape, y) - p.get();
+        let mut upper = shape + 1.;
+        while f(upper) < 0. {
+            upper *= 2.;
+        }
+        PositiveF64::new(bisect(f, 0., upper) / rate).unwrap()
+    }
+
+    pub fn mean(&self) -> PositiveF64 {
+        let (shape, rate) = self.parameters();
+        PositiveF64::new(shape / rate).unwrap()
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        let (shape, rate) = self.parameters();
+        PositiveF64::new(shape / rate.powi(2)).unwrap()
+    }
+
+    fn parameters(&self) -> (f64, f64) {
+        let (shape, rate) = (self.shape.get(), self.rate.get());
+        assert!(shape > 0.);
+        assert!(rate > 0.);
+        (shape, rate)
+    }
+}
+impl ContinuousDistribution for Gamma {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        non_negative(x).map_or(PositiveF64::ZERO, |x| Gamma::pdf(self, x))
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        non_negative(x).map_or(NormalizedF64::ZERO, |x| Gamma::cdf(self, x))
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        FiniteF64::new(Gamma::quantile(self, p).get()).unwrap()
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(Gamma::mean(self).get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        Gamma::variance(self)
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Distribution<f64> for Gamma {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let (shape, rate) = self.parameters();
+        standard_gamma_sample(shape, rng) / rate
+    }
+}
+
+/// Sample of `Gamma(shape, 1)` by Marsaglia and Tsang's method
+#[cfg(feature = "rand")]
+pub(crate) fn standard_gamma_sample<R: Rng + ?Sized>(shape: f64, rng: &mut R) -> f64 {
+    assert!(shape > 0.);
+    if shape < 1. {
+        // `Gamma(a) = Gamma(a + 1) U^(1 / a)`
+        let u = 1. - rng.gen::<f64>();
+        return standard_gamma_sample(shape + 1., rng) * u.powf(1. / shape);
+    }
+    let d = shape - 1. / 3.;
+    let c = 1. / (9. * d).sqrt();
+    loop {
+        let x = standard_normal_sample(rng);
+        let v = (1. + c * x).powi(3);
+        if v <= 0. {
+            continue;
+        }
+        let u = 1. - rng.gen::<f64>();
+        if u.ln() < x * x / 2. + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit() {
+        let data = [
+            1.2, 2.3, 0.8, 3.1, 1.7, 2.9, 0.5, 1.1, 4.2, 2.0, 1.5, 0.9, 2.6, 3.8, 1.3, 0.7, 1.9,
+            2.4, 5.1, 1.6,
+        ];
+        let gamma = Gamma::fit(&data);
+        assert!((gamma.shape.get() - 3.025_630_660).abs() < 1e-8);
+        assert!((gamma.rate.get() - 1.454_630_125).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_pdf_cdf() {
+        let gamma = Gamma {
+            shape: PositiveF64::new(2.).unwrap(),
+            rate: PositiveF64::new(0.5).unwrap(),
+        };
+        let x = PositiveF64::new(3.).unwrap();
+        // `x e^(-x / 2) / 4`
+        let expected = 3. * (-1.5_f64).exp() / 4.;
+        assert!((gamma.pdf(x).get() - expected).abs() < 1e-14);
+        // `1 - (1 + x / 2) e^(-x / 2)`
+        let expected = 1. - 2.5 * (-1.5_f64).exp();
+        assert!((gamma.cdf(x).get() - expected).abs() < 1e-14);
+        assert!((gamma.mean().get() - 4.).abs() < 1e-15);
+        assert!((gamma.variance().get() - 8.).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_chi_square() {
+        let chi_square = Gamma::chi_square(PositiveF64::new(5.).unwrap());
+        let x = PositiveF64::new(11.070_497_693_516).unwrap();
+        assert!((chi_square.cdf(x).get() - 0.95).abs() < 1e-12);
+        let p = NormalizedF64::new(0.95).unwrap();
+        assert!((chi_square.quantile(p).get() - x.get()).abs() < 1e-9);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let gamma = Gamma {
+            shape: PositiveF64::new(0.5).unwrap(),
+            rate: PositiveF64::new(2.).unwrap(),
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples = gamma.sample_iter(&mut rng).take(20_000).collect::<Vec<_>>();
+        let (mean, variance) = crate::distributions::tests::moments(&samples);
+        assert!((mean - 0.25).abs() < 0.01);
+        assert!((variance - 0.125).abs() < 0.01);
+    }
+}
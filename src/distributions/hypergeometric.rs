@@ -0,0 +1,143 @@
+use strict_num::{NormalizedF64, PositiveF64};
+
+use super::DiscreteDistribution;
+use crate::special::ln_binomial_coefficient;
+
+/// Number of successes in `draws` draws without replacement from `population` items of which `successes` are successes
+#[derive(Debug, Clone, Copy)]
+pub struct Hypergeometric {
+    pub population: usize,
+    pub successes: usize,
+    pub draws: usize,
+}
+impl Hypergeometric {
+    /// Smallest and largest possible numbers of successes drawn
+    pub fn support(&self) -> (usize, usize) {
+        self.validate();
+        let failures = self.population - self.successes;
+        let min = self.draws.saturating_sub(failures);
+        let max = self.draws.min(self.successes);
+        (min, max)
+    }
+
+    /// Probability of drawing exactly `k` successes
+    pub fn pmf(&self, k: usize) -> NormalizedF64 {
+        let (min, max) = self.support();
+        if k < min || max < k {
+            return NormalizedF64::ZERO;
+        }
+        let ln_pmf = ln_binomial_coefficient(self.successes, k)
+            + ln_binomial_coefficient(self.population - self.successes, self.draws - k)
+            - ln_binomial_coefficient(self.population, self.draws);
+        NormalizedF64::new(ln_pmf.exp().clamp(0., 1.)).unwrap()
+    }
+
+    /// Probability of drawing at most `k` successes
+    pub fn cdf(&self, k: usize) -> NormalizedF64 {
+        let (min, max) = self.support();
+        if k < min {
+            return NormalizedF64::ZERO;
+        }
+        if max <= k {
+            return NormalizedF64::ONE;
+        }
+        // Sum the shorter tail to limit accumulated rounding
+        let cdf = if k - min <= max - k {
+            (min..=k).map(|i| self.pmf(i).get()).sum::<f64>()
+        } else {
+            1. - (k + 1..=max).map(|i| self.pmf(i).get()).sum::<f64>()
+        };
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The smallest `k` whose CDF is at least `p`
+    pub fn quantile(&self, p: NormalizedF64) -> usize {
+        let (min, max) = self.support();
+        let mut cdf = 0.;
+        for k in min..max {
+            cdf += self.pmf(k).get();
+            if cdf >= p.get() {
+                return k;
+            }
+        }
+        max
+    }
+
+    pub fn mean(&self) -> PositiveF64 {
+        self.validate();
+        let mean = self.draws as f64 * self.successes as f64 / self.population as f64;
+        PositiveF64::new(mean).unwrap()
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        self.validate();
+        let population = self.population as f64;
+        let p = self.successes as f64 / population;
+        let draws = self.draws as f64;
+        let correction = if self.population == 1 {
+            0.
+        } else {
+            (population - draws) / (population - 1.)
+        };
+        PositiveF64::new(draws * p * (1. - p) * correction).unwrap()
+    }
+
+    fn validate(&self) {
+        assert!(self.population > 0);
+        assert!(self.successes <= self.population);
+        assert!(self.draws <= self.population);
+    }
+}
+impl DiscreteDistribution for Hypergeometric {
+    fn pmf(&self, k: usize) -> NormalizedF64 {
+        Hypergeometric::pmf(self, k)
+    }
+
+    fn cdf(&self, k: usize) -> NormalizedF64 {
+        Hypergeometric::cdf(self, k)
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> usize {
+        Hypergeometric::quantile(self, p)
+    }
+
+    fn mean(&self) -> PositiveF64 {
+        Hypergeometric::mean(self)
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        Hypergeometric::variance(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pmf_cdf() {
+        let hypergeometric = Hypergeometric {
+            population: 50,
+            successes: 5,
+            draws: 10,
+        };
+        assert_eq!(hypergeometric.support(), (0, 5));
+        assert!((hypergeometric.pmf(1).get() - 0.431_337_197_229).abs() < 1e-12);
+        assert!((hypergeometric.cdf(1).get() - 0.741_899_979_233).abs() < 1e-12);
+        assert!((hypergeometric.cdf(4).get() + hypergeometric.pmf(5).get() - 1.).abs() < 1e-14);
+        assert!((hypergeometric.mean().get() - 1.).abs() < 1e-15);
+        assert!((hypergeometric.variance().get() - 0.734_693_877_551).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_support() {
+        let hypergeometric = Hypergeometric {
+            population: 10,
+            successes: 7,
+            draws: 5,
+        };
+        assert_eq!(hypergeometric.support(), (2, 5));
+        assert_eq!(hypergeometric.pmf(1).get(), 0.);
+        assert_eq!(hypergeometric.cdf(1).get(), 0.);
+    }
+}
@@ -0,0 +1,129 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use super::{non_negative, ContinuousDistribution};
+use crate::special::{normal_cdf, normal_quantile};
+
+/// Distribution of `e^X` where `X ~ N(location, scale^2)`
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogNormal {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub location: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub scale: PositiveF64,
+}
+impl LogNormal {
+    /// Maximum likelihood estimate from positive observations
+    pub fn fit(data: &[f64]) -> Self {
+        assert!(data.len() >= 2);
+        assert!(data.iter().all(|&x| x > 0.));
+        let n = data.len() as f64;
+        let location = data.iter().map(|x| x.ln()).sum::<f64>() / n;
+        let variance = data
+            .iter()
+            .map(|x| (x.ln() - location).powi(2))
+            .sum::<f64>()
+            / n;
+        Self {
+            location: FiniteF64::new(location).unwrap(),
+            scale: PositiveF64::new(variance.sqrt()).unwrap(),
+        }
+    }
+
+    /// Probability density at `x`
+    pub fn pdf(&self, x: PositiveF64) -> PositiveF64 {
+        let scale = self.scale();
+        let x = x.get();
+        if x == 0. {
+            return PositiveF64::ZERO;
+        }
+        let z = (x.ln() - self.location.get()) / scale;
+        let pdf = (-z.powi(2) / 2.).exp() / (x * scale * (2. * std::f64::consts::PI).sqrt());
+        PositiveF64::new(pdf).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: PositiveF64) -> NormalizedF64 {
+        let scale = self.scale();
+        if x.get() == 0. {
+            return NormalizedF64::ZERO;
+        }
+        let z = (x.get().ln() - self.location.get()) / scale;
+        NormalizedF64::new(normal_cdf(z).clamp(0., 1.)).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`, for `0 < p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> PositiveF64 {
+        let z = normal_quantile(p.get());
+        PositiveF64::new((self.location.get() + self.scale() * z).exp()).unwrap()
+    }
+
+    pub fn mean(&self) -> PositiveF64 {
+        let scale = self.scale();
+        PositiveF64::new((self.location.get() + scale.powi(2) / 2.).exp()).unwrap()
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        let scale = self.scale();
+        let variance =
+            (scale.powi(2).exp() - 1.) * (2. * self.location.get() + scale.powi(2)).exp();
+        PositiveF64::new(variance).unwrap()
+    }
+
+    fn scale(&self) -> f64 {
+        assert!(self.scale.get() > 0.);
+        self.scale.get()
+    }
+}
+impl ContinuousDistribution for LogNormal {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        non_negative(x).map_or(PositiveF64::ZERO, |x| LogNormal::pdf(self, x))
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        non_negative(x).map_or(NormalizedF64::ZERO, |x| LogNormal::cdf(self, x))
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        FiniteF64::new(LogNormal::quantile(self, p).get()).unwrap()
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(LogNormal::mean(self).get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        LogNormal::variance(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_normal() {
+        let log_normal = LogNormal {
+            location: FiniteF64::new(1.).unwrap(),
+            scale: PositiveF64::new(0.5).unwrap(),
+        };
+        let median = PositiveF64::new(1_f64.exp()).unwrap();
+        assert!((log_normal.cdf(median).get() - 0.5).abs() < 1e-15);
+        let p = NormalizedF64::new(0.975).unwrap();
+        let expected = (1. + 0.5 * 1.959_963_984_540_054_f64).exp();
+        assert!((log_normal.quantile(p).get() - expected).abs() < 1e-12);
+        assert!((log_normal.mean().get() - 1.125_f64.exp()).abs() < 1e-14);
+        let pdf = log_normal.pdf(median).get();
+        assert!(
+            (pdf - 1. / (median.get() * 0.5 * (2. * std::f64::consts::PI).sqrt())).abs() < 1e-15
+        );
+    }
+
+    #[test]
+    fn test_fit() {
+        let data = [1_f64, 2., 4., 8.];
+        let log_normal = LogNormal::fit(&data);
+        assert!((log_normal.location.get() - 1.5 * 2_f64.ln()).abs() < 1e-15);
+        assert!((log_normal.scale.get() - 1.25_f64.sqrt() * 2_f64.ln()).abs() < 1e-15);
+    }
+}
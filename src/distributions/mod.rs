@@ -1,4 +1,191 @@
+pub mod beta;
+pub mod binomial;
 pub mod chi_square;
+pub mod exponential;
 pub mod f;
+pub mod gamma;
+pub mod hypergeometric;
+pub mod log_normal;
+pub mod negative_binomial;
+pub mod noncentral_chi_square;
+pub mod noncentral_f;
+pub mod noncentral_t;
 pub mod normal;
+pub mod poisson;
+pub mod studentized_range;
 pub mod t;
+pub mod uniform;
+pub mod weibull;
+
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+/// Common interface of univariate continuous distributions, with values taken anywhere on the real line.
+///
+/// - Outside the support, the density is zero and the CDF is 0 or 1.
+/// - Moments panic where they are undefined, e.g., the mean of Student's t with `df <= 1`.
+/// - The lookup tables such as [`normal::Z_SCORE_TABLE`] predate this trait and keep their own APIs.
+pub trait ContinuousDistribution {
+    /// Probability density at `x`
+    fn pdf(&self, x: FiniteF64) -> PositiveF64;
+
+    /// Probability of a value less than or equal to `x`
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64;
+
+    /// Probability of a value greater than `x`
+    fn sf(&self, x: FiniteF64) -> NormalizedF64 {
+        NormalizedF64::new(1. - self.cdf(x).get()).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64;
+
+    fn mean(&self) -> FiniteF64;
+
+    fn variance(&self) -> PositiveF64;
+}
+
+/// Common interface of distributions over the counts `0, 1, 2, ...`
+pub trait DiscreteDistribution {
+    /// Probability of exactly `k`
+    fn pmf(&self, k: usize) -> NormalizedF64;
+
+    /// Probability of at most `k`
+    fn cdf(&self, k: usize) -> NormalizedF64;
+
+    /// Probability of more than `k`
+    fn sf(&self, k: usize) -> NormalizedF64 {
+        NormalizedF64::new(1. - self.cdf(k).get()).unwrap()
+    }
+
+    /// The smallest `k` whose CDF is at least `p`
+    fn quantile(&self, p: NormalizedF64) -> usize;
+
+    fn mean(&self) -> PositiveF64;
+
+    fn variance(&self) -> PositiveF64;
+}
+
+/// `x` if it is in the support `[0, ∞)`
+fn non_negative(x: FiniteF64) -> Option<PositiveF64> {
+    (x.get() >= 0.).then(|| PositiveF64::new(x.get()).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        beta::Beta, binomial::Binomial, chi_square::ChiSquare, exponential::Exponential, f::F,
+        gamma::Gamma, hypergeometric::Hypergeometric, log_normal::LogNormal,
+        negative_binomial::NegativeBinomial, noncentral_chi_square::NoncentralChiSquare,
+        noncentral_f::NoncentralF, noncentral_t::NoncentralT, normal::Normal, poisson::Poisson,
+        t::StudentT, uniform::Uniform, weibull::Weibull, *,
+    };
+
+    /// Sample mean and unbiased sample variance
+    #[cfg(feature = "rand")]
+    pub fn moments(samples: &[f64]) -> (f64, f64) {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.);
+        (mean, variance)
+    }
+
+    fn positive(x: f64) -> PositiveF64 {
+        PositiveF64::new(x).unwrap()
+    }
+
+    fn finite(x: f64) -> FiniteF64 {
+        FiniteF64::new(x).unwrap()
+    }
+
+    #[test]
+    fn test_continuous_distributions() {
+        let distributions: Vec<Box<dyn ContinuousDistribution>> = vec![
+            Box::new(Normal::standard()),
+            Box::new(StudentT { df: positive(5.) }),
+            Box::new(ChiSquare { df: positive(4.) }),
+            Box::new(F {
+                df_1: positive(4.),
+                df_2: positive(12.),
+            }),
+            Box::new(Beta {
+                a: positive(2.),
+                b: positive(3.),
+            }),
+            Box::new(Gamma {
+                shape: positive(2.5),
+                rate: positive(1.5),
+            }),
+            Box::new(Exponential { rate: positive(2.) }),
+            Box::new(Uniform {
+                lower: finite(-1.),
+                upper: finite(2.),
+            }),
+            Box::new(LogNormal {
+                location: finite(0.),
+                scale: positive(0.5),
+            }),
+            Box::new(Weibull {
+                shape: positive(1.5),
+                scale: positive(2.),
+            }),
+            Box::new(NoncentralT {
+                df: positive(8.),
+                noncentrality: finite(1.),
+            }),
+            Box::new(NoncentralChiSquare {
+                df: positive(3.),
+                noncentrality: positive(2.),
+            }),
+            Box::new(NoncentralF {
+                df_1: positive(3.),
+                df_2: positive(10.),
+                noncentrality: positive(2.),
+            }),
+        ];
+        for distribution in &distributions {
+            for p in [0.1, 0.5, 0.9] {
+                let x = distribution.quantile(NormalizedF64::new(p).unwrap());
+                assert!((distribution.cdf(x).get() - p).abs() < 1e-8);
+                assert!((distribution.cdf(x).get() + distribution.sf(x).get() - 1.).abs() < 1e-12);
+
+                // The density is the derivative of the CDF
+                let h = 1e-5;
+                let slope = (distribution.cdf(finite(x.get() + h)).get()
+                    - distribution.cdf(finite(x.get() - h)).get())
+                    / (2. * h);
+                assert!((distribution.pdf(x).get() - slope).abs() < 1e-5);
+            }
+            assert!(distribution.variance().get() > 0.);
+        }
+    }
+
+    #[test]
+    fn test_discrete_distributions() {
+        let distributions: Vec<Box<dyn DiscreteDistribution>> = vec![
+            Box::new(Binomial {
+                trials: 12,
+                probability: NormalizedF64::new(0.3).unwrap(),
+            }),
+            Box::new(Poisson { rate: positive(4.) }),
+            Box::new(Hypergeometric {
+                population: 30,
+                successes: 10,
+                draws: 8,
+            }),
+            Box::new(NegativeBinomial {
+                size: positive(2.5),
+                probability: NormalizedF64::new(0.4).unwrap(),
+            }),
+        ];
+        for distribution in &distributions {
+            let mut cdf = 0.;
+            for k in 0..8 {
+                cdf += distribution.pmf(k).get();
+                assert!((distribution.cdf(k).get() - cdf).abs() < 1e-12);
+                assert!((distribution.cdf(k).get() + distribution.sf(k).get() - 1.).abs() < 1e-12);
+                let p = NormalizedF64::new(distribution.cdf(k).get() - 1e-9).unwrap();
+                assert_eq!(distribution.quantile(p), k);
+            }
+        }
+    }
+}
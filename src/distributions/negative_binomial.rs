@@ -0,0 +1,135 @@
+use strict_num::{NormalizedF64, PositiveF64};
+
+use super::DiscreteDistribution;
+use crate::special::{betainc_regularized, ln_gamma};
+
+/// Number of failures before the `size`-th success in trials each succeeding with `probability`
+///
+/// - `size` may be fractional, giving the Poisson–Gamma mixture used for overdispersed counts.
+#[derive(Debug, Clone, Copy)]
+pub struct NegativeBinomial {
+    pub size: PositiveF64,
+    pub probability: NormalizedF64,
+}
+impl NegativeBinomial {
+    /// Parameterize by `mean` and `dispersion` where `variance = mean + dispersion * mean^2`
+    pub fn from_mean_and_dispersion(mean: PositiveF64, dispersion: PositiveF64) -> Self {
+        assert!(dispersion.get() > 0.);
+        let size = 1. / dispersion.get();
+        let probability = size / (size + mean.get());
+        Self {
+            size: PositiveF64::new(size).unwrap(),
+            probability: NormalizedF64::new(probability).unwrap(),
+        }
+    }
+
+    /// Probability of exactly `k` failures
+    pub fn pmf(&self, k: usize) -> NormalizedF64 {
+        let (size, p) = self.parameters();
+        let k = k as f64;
+        let ln_failures = if k == 0. { 0. } else { k * (1. - p).ln() };
+        let ln_pmf =
+            ln_gamma(k + size) - ln_gamma(size) - ln_gamma(k + 1.) + size * p.ln() + ln_failures;
+        NormalizedF64::new(ln_pmf.exp().clamp(0., 1.)).unwrap()
+    }
+
+    /// Probability of at most `k` failures
+    pub fn cdf(&self, k: usize) -> NormalizedF64 {
+        let (size, p) = self.parameters();
+        // `P(X <= k) = I_p(size, k + 1)`
+        let cdf = betainc_regularized(size, k as f64 + 1., p);
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The smallest `k` whose CDF is at least `p`, for `0 <= p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> usize {
+        assert!(p.get() < 1.);
+        let mut upper = self.mean().get().ceil() as usize + 1;
+        while self.cdf(upper).get() < p.get() {
+            upper *= 2;
+        }
+        let mut lower = 0;
+        while lower < upper {
+            let middle = (lower + upper) / 2;
+            if self.cdf(middle).get() < p.get() {
+                lower = middle + 1;
+            } else {
+                upper = middle;
+            }
+        }
+        lower
+    }
+
+    pub fn mean(&self) -> PositiveF64 {
+        let (size, p) = self.parameters();
+        PositiveF64::new(size * (1. - p) / p).unwrap()
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        let (size, p) = self.parameters();
+        PositiveF64::new(size * (1. - p) / p.powi(2)).unwrap()
+    }
+
+    fn parameters(&self) -> (f64, f64) {
+        let (size, p) = (self.size.get(), self.probability.get());
+        assert!(size > 0.);
+        assert!(p > 0.);
+        (size, p)
+    }
+}
+impl DiscreteDistribution for NegativeBinomial {
+    fn pmf(&self, k: usize) -> NormalizedF64 {
+        NegativeBinomial::pmf(self, k)
+    }
+
+    fn cdf(&self, k: usize) -> NormalizedF64 {
+        NegativeBinomial::cdf(self, k)
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> usize {
+        NegativeBinomial::quantile(self, p)
+    }
+
+    fn mean(&self) -> PositiveF64 {
+        NegativeBinomial::mean(self)
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        NegativeBinomial::variance(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pmf_cdf() {
+        let negative_binomial = NegativeBinomial {
+            size: PositiveF64::new(3.).unwrap(),
+            probability: NormalizedF64::new(0.4).unwrap(),
+        };
+        // `C(4, 2) 0.4^3 0.6^2`
+        assert!((negative_binomial.pmf(2).get() - 0.138_24).abs() < 1e-14);
+        assert!((negative_binomial.cdf(2).get() - 0.317_44).abs() < 1e-14);
+        assert!((negative_binomial.mean().get() - 4.5).abs() < 1e-14);
+        assert!((negative_binomial.variance().get() - 11.25).abs() < 1e-14);
+        assert_eq!(
+            negative_binomial.quantile(NormalizedF64::new(0.3).unwrap()),
+            2
+        );
+        assert_eq!(
+            negative_binomial.quantile(NormalizedF64::new(0.32).unwrap()),
+            3
+        );
+    }
+
+    #[test]
+    fn test_from_mean_and_dispersion() {
+        let mean = PositiveF64::new(4.).unwrap();
+        let dispersion = PositiveF64::new(0.5).unwrap();
+        let negative_binomial = NegativeBinomial::from_mean_and_dispersion(mean, dispersion);
+        assert!((negative_binomial.mean().get() - 4.).abs() < 1e-14);
+        assert!((negative_binomial.variance().get() - 12.).abs() < 1e-14);
+    }
+}
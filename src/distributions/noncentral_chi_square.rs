@@ -0,0 +1,150 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use super::gamma::Gamma;
+use super::{non_negative, ContinuousDistribution};
+use crate::special::{bisect, gammainc_regularized, gammainc_upper_regularized, ln_gamma};
+
+/// Poisson weights below this are skipped
+const MIN_WEIGHT: f64 = 1e-17;
+
+#[derive(Debug, Clone, Copy)]
+pub struct NoncentralChiSquare {
+    pub df: PositiveF64,
+    pub noncentrality: PositiveF64,
+}
+impl NoncentralChiSquare {
+    /// Probability density at `x`
+    ///
+    /// - Unbounded at 0 if `df < 2`, which panics.
+    pub fn pdf(&self, x: PositiveF64) -> PositiveF64 {
+        let df = self.df.get();
+        assert!(df > 0.);
+        let pdf = poisson_mixture(self.noncentrality.get() / 2., |j| {
+            let chi_square = Gamma::chi_square(PositiveF64::new(df + 2. * j).unwrap());
+            chi_square.pdf(x).get()
+        });
+        PositiveF64::new(pdf).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: PositiveF64) -> NormalizedF64 {
+        let cdf = noncentral_chi_square_cdf(x.get(), self.df.get(), self.noncentrality.get());
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// Probability of a value greater than `x`, accurate in the far upper tail
+    pub fn sf(&self, x: PositiveF64) -> NormalizedF64 {
+        let df = self.df.get();
+        assert!(df > 0.);
+        let sf = poisson_mixture(self.noncentrality.get() / 2., |j| {
+            gammainc_upper_regularized(df / 2. + j, x.get() / 2.)
+        });
+        NormalizedF64::new(sf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`, for `0 <= p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> PositiveF64 {
+        assert!(p.get() < 1.);
+        let f = |x: f64| {
+            noncentral_chi_square_cdf(x, self.df.get(), self.noncentrality.get()) - p.get()
+        };
+        let mut upper = self.df.get() + self.noncentrality.get() + 1.;
+        while f(upper) < 0. {
+            upper *= 2.;
+        }
+        PositiveF64::new(bisect(f, 0., upper)).unwrap()
+    }
+
+    pub fn mean(&self) -> PositiveF64 {
+        PositiveF64::new(self.df.get() + self.noncentrality.get()).unwrap()
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        PositiveF64::new(2. * (self.df.get() + 2. * self.noncentrality.get())).unwrap()
+    }
+}
+impl ContinuousDistribution for NoncentralChiSquare {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        non_negative(x).map_or(PositiveF64::ZERO, |x| NoncentralChiSquare::pdf(self, x))
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        non_negative(x).map_or(NormalizedF64::ZERO, |x| NoncentralChiSquare::cdf(self, x))
+    }
+
+    fn sf(&self, x: FiniteF64) -> NormalizedF64 {
+        non_negative(x).map_or(NormalizedF64::ONE, |x| NoncentralChiSquare::sf(self, x))
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        FiniteF64::new(NoncentralChiSquare::quantile(self, p).get()).unwrap()
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(NoncentralChiSquare::mean(self).get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        NoncentralChiSquare::variance(self)
+    }
+}
+
+/// Poisson mixture of central chi-square CDFs with weights from `Poisson(noncentrality / 2)`
+fn noncentral_chi_square_cdf(x: f64, df: f64, noncentrality: f64) -> f64 {
+    assert!(df > 0.);
+    poisson_mixture(noncentrality / 2., |j| {
+        gammainc_regularized(df / 2. + j, x / 2.)
+    })
+}
+
+/// `sum_j Poisson(j; half_lambda) term(j)`
+pub(crate) fn poisson_mixture(half_lambda: f64, term: impl Fn(f64) -> f64) -> f64 {
+    if half_lambda == 0. {
+        return term(0.);
+    }
+    let last = (half_lambda + 12. * (half_lambda + 1.).sqrt() + 30.) as usize;
+    (0..=last)
+        .filter_map(|j| {
+            let j = j as f64;
+            let weight = (-half_lambda + j * half_lambda.ln() - ln_gamma(j + 1.)).exp();
+            (weight >= MIN_WEIGHT).then(|| weight * term(j))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cdf() {
+        let chi_square = NoncentralChiSquare {
+            df: PositiveF64::new(3.).unwrap(),
+            noncentrality: PositiveF64::new(2.5).unwrap(),
+        };
+        let x = PositiveF64::new(8.).unwrap();
+        assert!((chi_square.cdf(x).get() - 0.777_954_817_074).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let central = NoncentralChiSquare {
+            df: PositiveF64::new(5.).unwrap(),
+            noncentrality: PositiveF64::new(0.).unwrap(),
+        };
+        let p = NormalizedF64::new(0.95).unwrap();
+        assert!((central.quantile(p).get() - 11.070_497_693_516).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sf() {
+        let chi_square = NoncentralChiSquare {
+            df: PositiveF64::new(3.).unwrap(),
+            noncentrality: PositiveF64::new(2.5).unwrap(),
+        };
+        let x = PositiveF64::new(8.).unwrap();
+        assert!((chi_square.sf(x).get() - (1. - 0.777_954_817_074)).abs() < 1e-10);
+        assert!((chi_square.mean().get() - 5.5).abs() < 1e-15);
+        assert!((chi_square.variance().get() - 16.).abs() < 1e-15);
+    }
+}
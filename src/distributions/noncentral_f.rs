@@ -0,0 +1,164 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use super::f::beta_prime_pdf;
+use super::noncentral_chi_square::poisson_mixture;
+use super::{non_negative, ContinuousDistribution};
+use crate::special::{betainc_regularized, bisect};
+
+#[derive(Debug, Clone, Copy)]
+pub struct NoncentralF {
+    pub df_1: PositiveF64,
+    pub df_2: PositiveF64,
+    pub noncentrality: PositiveF64,
+}
+impl NoncentralF {
+    /// Probability density at `x`
+    ///
+    /// - Unbounded at 0 if `df_1 < 2`, which panics.
+    pub fn pdf(&self, x: PositiveF64) -> PositiveF64 {
+        let (df_1, df_2) = (self.df_1.get(), self.df_2.get());
+        assert!(df_1 > 0.);
+        assert!(df_2 > 0.);
+        let y = df_1 * x.get() / df_2;
+        let pdf = df_1 / df_2
+            * poisson_mixture(self.noncentrality.get() / 2., |j| {
+                beta_prime_pdf(y, df_1 / 2. + j, df_2 / 2.)
+            });
+        PositiveF64::new(pdf).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: PositiveF64) -> NormalizedF64 {
+        let cdf = noncentral_f_cdf(
+            x.get(),
+            self.df_1.get(),
+            self.df_2.get(),
+            self.noncentrality.get(),
+        );
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// Probability of a value greater than `x`, accurate in the far upper tail
+    pub fn sf(&self, x: PositiveF64) -> NormalizedF64 {
+        let (df_1, df_2) = (self.df_1.get(), self.df_2.get());
+        assert!(df_1 > 0.);
+        assert!(df_2 > 0.);
+        let y = df_2 / (df_1 * x.get() + df_2);
+        let sf = poisson_mixture(self.noncentrality.get() / 2., |j| {
+            betainc_regularized(df_2 / 2., df_1 / 2. + j, y)
+        });
+        NormalizedF64::new(sf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`, for `0 <= p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> PositiveF64 {
+        assert!(p.get() < 1.);
+        let f = |x: f64| {
+            noncentral_f_cdf(
+                x,
+                self.df_1.get(),
+                self.df_2.get(),
+                self.noncentrality.get(),
+            ) - p.get()
+        };
+        let mut upper = 1.;
+        while f(upper) < 0. {
+            upper *= 2.;
+        }
+        PositiveF64::new(bisect(f, 0., upper)).unwrap()
+    }
+
+    /// Defined for `df_2 > 2`
+    pub fn mean(&self) -> PositiveF64 {
+        let (df_1, df_2) = (self.df_1.get(), self.df_2.get());
+        assert!(df_2 > 2.);
+        let mean = df_2 * (df_1 + self.noncentrality.get()) / (df_1 * (df_2 - 2.));
+        PositiveF64::new(mean).unwrap()
+    }
+
+    /// Defined for `df_2 > 4`
+    pub fn variance(&self) -> PositiveF64 {
+        let (df_1, df_2) = (self.df_1.get(), self.df_2.get());
+        assert!(df_2 > 4.);
+        let lambda = self.noncentrality.get();
+        let variance = 2.
+            * (df_2 / df_1).powi(2)
+            * ((df_1 + lambda).powi(2) + (df_1 + 2. * lambda) * (df_2 - 2.))
+            / ((df_2 - 2.).powi(2) * (df_2 - 4.));
+        PositiveF64::new(variance).unwrap()
+    }
+}
+impl ContinuousDistribution for NoncentralF {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        non_negative(x).map_or(PositiveF64::ZERO, |x| NoncentralF::pdf(self, x))
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        non_negative(x).map_or(NormalizedF64::ZERO, |x| NoncentralF::cdf(self, x))
+    }
+
+    fn sf(&self, x: FiniteF64) -> NormalizedF64 {
+        non_negative(x).map_or(NormalizedF64::ONE, |x| NoncentralF::sf(self, x))
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        FiniteF64::new(NoncentralF::quantile(self, p).get()).unwrap()
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(NoncentralF::mean(self).get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        NoncentralF::variance(self)
+    }
+}
+
+/// Poisson mixture of central F CDFs with weights from `Poisson(noncentrality / 2)`
+fn noncentral_f_cdf(x: f64, df_1: f64, df_2: f64, noncentrality: f64) -> f64 {
+    assert!(df_1 > 0.);
+    assert!(df_2 > 0.);
+    let y = df_1 * x / (df_1 * x + df_2);
+    let half_lambda = noncentrality / 2.;
+    poisson_mixture(half_lambda, |j| {
+        betainc_regularized(df_1 / 2. + j, df_2 / 2., y)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cdf() {
+        let f = NoncentralF {
+            df_1: PositiveF64::new(3.).unwrap(),
+            df_2: PositiveF64::new(10.).unwrap(),
+            noncentrality: PositiveF64::new(4.).unwrap(),
+        };
+        let x = PositiveF64::new(2.).unwrap();
+        assert!((f.cdf(x).get() - 0.466_364_131).abs() < 1e-7);
+
+        let central = NoncentralF {
+            df_1: PositiveF64::new(5.).unwrap(),
+            df_2: PositiveF64::new(20.).unwrap(),
+            noncentrality: PositiveF64::new(0.).unwrap(),
+        };
+        let x = PositiveF64::new(1.).unwrap();
+        assert!((central.cdf(x).get() - 0.556_974_815).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_sf() {
+        let f = NoncentralF {
+            df_1: PositiveF64::new(3.).unwrap(),
+            df_2: PositiveF64::new(10.).unwrap(),
+            noncentrality: PositiveF64::new(4.).unwrap(),
+        };
+        let x = PositiveF64::new(2.).unwrap();
+        assert!((f.sf(x).get() - (1. - 0.466_364_131)).abs() < 1e-7);
+        assert!((f.mean().get() - 10. * 7. / 24.).abs() < 1e-14);
+        let variance = 2. * (10_f64 / 3.).powi(2) * (49. + 11. * 8.) / (64. * 6.);
+        assert!((f.variance().get() - variance).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,186 @@
+use std::f64::consts::PI;
+
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use super::ContinuousDistribution;
+use crate::special::{betainc_regularized, bisect, ln_gamma, normal_cdf};
+
+const MAX_ITERATIONS: usize = 1000;
+const MAX_ERROR: f64 = 1e-12;
+
+#[derive(Debug, Clone, Copy)]
+pub struct NoncentralT {
+    pub df: PositiveF64,
+    pub noncentrality: FiniteF64,
+}
+impl NoncentralT {
+    /// Probability density at `t`
+    pub fn pdf(&self, t: FiniteF64) -> PositiveF64 {
+        let df = self.df.get();
+        assert!(df > 0.);
+        let delta = self.noncentrality.get();
+        let t = t.get();
+        let pdf = if t == 0. {
+            (ln_gamma((df + 1.) / 2.)
+                - ln_gamma(df / 2.)
+                - 0.5 * (PI * df).ln()
+                - delta.powi(2) / 2.)
+                .exp()
+        } else {
+            // `f(t) = df / t (F_{df + 2}(t sqrt(1 + 2 / df)) - F_df(t))`
+            let stretched = t * (1. + 2. / df).sqrt();
+            df / t * (noncentral_t_cdf(stretched, df + 2., delta) - noncentral_t_cdf(t, df, delta))
+        };
+        PositiveF64::new(pdf.max(0.)).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `t`
+    pub fn cdf(&self, t: FiniteF64) -> NormalizedF64 {
+        assert!(self.df.get() > 0.);
+        let cdf = noncentral_t_cdf(t.get(), self.df.get(), self.noncentrality.get());
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `t` whose CDF is `p`, for `0 < p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        assert!(0. < p.get() && p.get() < 1.);
+        let f = |t: f64| noncentral_t_cdf(t, self.df.get(), self.noncentrality.get()) - p.get();
+        let mut lower = self.noncentrality.get() - 1.;
+        let mut upper = self.noncentrality.get() + 1.;
+        while f(lower) > 0. {
+            lower -= 2. * (upper - lower);
+        }
+        while f(upper) < 0. {
+            upper += 2. * (upper - lower);
+        }
+        FiniteF64::new(bisect(f, lower, upper)).unwrap()
+    }
+
+    /// Defined for `df > 1`
+    pub fn mean(&self) -> FiniteF64 {
+        let df = self.df.get();
+        assert!(df > 1.);
+        let ratio = (ln_gamma((df - 1.) / 2.) - ln_gamma(df / 2.)).exp();
+        FiniteF64::new(self.noncentrality.get() * (df / 2.).sqrt() * ratio).unwrap()
+    }
+
+    /// Defined for `df > 2`
+    pub fn variance(&self) -> PositiveF64 {
+        let df = self.df.get();
+        assert!(df > 2.);
+        let delta = self.noncentrality.get();
+        let variance = df * (1. + delta.powi(2)) / (df - 2.) - self.mean().get().powi(2);
+        PositiveF64::new(variance.max(0.)).unwrap()
+    }
+}
+impl ContinuousDistribution for NoncentralT {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        NoncentralT::pdf(self, x)
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        NoncentralT::cdf(self, x)
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        NoncentralT::quantile(self, p)
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        NoncentralT::mean(self)
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        NoncentralT::variance(self)
+    }
+}
+
+fn noncentral_t_cdf(t: f64, df: f64, delta: f64) -> f64 {
+    // ref: Lenth, R. V. (1989). Algorithm AS 243: Cumulative distribution function of the non-central t distribution.
+    let (t, delta, negated) = if t < 0. {
+        (-t, -delta, true)
+    } else {
+        (t, delta, false)
+    };
+
+    let mut cdf = 0.;
+    let x = t * t / (t * t + df);
+    if x > 0. {
+        let lambda = delta * delta;
+        let mut p = 0.5 * (-0.5 * lambda).exp();
+        let mut q = (2. / PI).sqrt() * p * delta;
+        let mut s = 0.5 - p;
+        let mut a = 0.5;
+        let b = 0.5 * df;
+        let rxb = (1. - x).powf(b);
+        let ln_beta = PI.sqrt().ln() + ln_gamma(b) - ln_gamma(a + b);
+        let mut x_odd = betainc_regularized(a, b, x);
+        let mut g_odd = 2. * rxb * (a * x.ln() - ln_beta).exp();
+        let mut x_even = 1. - rxb;
+        let mut g_even = b * x * rxb;
+        cdf = p * x_odd + q * x_even;
+
+        let mut n = 1.;
+        loop {
+            a += 1.;
+            x_odd -= g_odd;
+            x_even -= g_even;
+            g_odd *= x * (a + b - 1.) / a;
+            g_even *= x * (a + b - 0.5) / (a + 0.5);
+            p *= lambda / (2. * n);
+            q *= lambda / (2. * n + 1.);
+            s -= p;
+            n += 1.;
+            cdf += p * x_odd + q * x_even;
+            let error_bound = 2. * s * (x_odd - g_odd);
+            if error_bound <= MAX_ERROR || n as usize > MAX_ITERATIONS {
+                break;
+            }
+        }
+    }
+    cdf += normal_cdf(-delta);
+    if negated {
+        1. - cdf
+    } else {
+        cdf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noncentral_t(df: f64, noncentrality: f64) -> NoncentralT {
+        NoncentralT {
+            df: PositiveF64::new(df).unwrap(),
+            noncentrality: FiniteF64::new(noncentrality).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_cdf() {
+        let t = |t| FiniteF64::new(t).unwrap();
+        assert!((noncentral_t(10., 1.).cdf(t(1.5)).get() - 0.669_516_848_215).abs() < 1e-9);
+        assert!((noncentral_t(5., 0.5).cdf(t(-1.)).get() - 0.082_444_091_113).abs() < 1e-9);
+        assert!((noncentral_t(20., 2.5).cdf(t(3.)).get() - 0.661_602_873_493).abs() < 1e-9);
+        // Central
+        assert!((noncentral_t(3., 0.).cdf(t(0.)).get() - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let p = NormalizedF64::new(0.975).unwrap();
+        let t = noncentral_t(126., 0.).quantile(p);
+        assert!((t.get() - 1.978_970_602).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_moments() {
+        let t = NoncentralT {
+            df: PositiveF64::new(10.).unwrap(),
+            noncentrality: FiniteF64::new(1.).unwrap(),
+        };
+        assert!((t.mean().get() - 1.083_722_307_939).abs() < 1e-12);
+        assert!((t.variance().get() - 1.325_545_959_275).abs() < 1e-12);
+    }
+}
@@ -1,5 +1,12 @@
+use std::f64::consts::PI;
+
 use once_cell::sync::Lazy;
-use strict_num::{FiniteF64, NormalizedF64};
+#[cfg(feature = "rand")]
+use rand::{distributions::Distribution, Rng};
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use super::ContinuousDistribution;
+use crate::special::{normal_cdf, normal_quantile};
 
 const ENTRIES: usize = 31 * 10;
 const END_Z: f64 = 3.10;
@@ -60,6 +67,13 @@ impl ZScoreTable {
         NormalizedF64::new(self.p_value_one_sided(z).get() * 2.).unwrap()
     }
 
+    /// Area under the standard normal curve to the left of `z`
+    pub fn area_up_to(&self, z: FiniteF64) -> NormalizedF64 {
+        let area = self.area(z).get();
+        let area_up_to_z = if z.get() < 0. { 0.5 - area } else { 0.5 + area };
+        NormalizedF64::new(area_up_to_z).unwrap()
+    }
+
     fn area(&self, z: FiniteF64) -> NormalizedF64 {
         let z = z.get().abs();
         if z >= END_Z {
@@ -97,6 +111,104 @@ impl Default for ZScoreTable {
     }
 }
 
+/// Normal distribution `N(mean, standard_deviation^2)`
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Normal {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub mean: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub standard_deviation: PositiveF64,
+}
+impl Normal {
+    pub fn standard() -> Self {
+        Self {
+            mean: FiniteF64::new(0.).unwrap(),
+            standard_deviation: PositiveF64::new(1.).unwrap(),
+        }
+    }
+
+    /// Maximum likelihood estimate, whose variance divides by `n`
+    pub fn fit(data: &[f64]) -> Self {
+        assert!(data.len() >= 2);
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let variance = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        Self {
+            mean: FiniteF64::new(mean).unwrap(),
+            standard_deviation: PositiveF64::new(variance.sqrt()).unwrap(),
+        }
+    }
+
+    /// Probability density at `x`
+    pub fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        let sd = self.standard_deviation();
+        let z = (x.get() - self.mean.get()) / sd;
+        PositiveF64::new((-z * z / 2.).exp() / (sd * (2. * PI).sqrt())).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        let z = (x.get() - self.mean.get()) / self.standard_deviation();
+        NormalizedF64::new(normal_cdf(z).clamp(0., 1.)).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`, for `0 < p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        let x = self.mean.get() + self.standard_deviation() * normal_quantile(p.get());
+        FiniteF64::new(x).unwrap()
+    }
+
+    pub fn mean(&self) -> FiniteF64 {
+        self.mean
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        PositiveF64::new(self.standard_deviation().powi(2)).unwrap()
+    }
+
+    fn standard_deviation(&self) -> f64 {
+        assert!(self.standard_deviation.get() > 0.);
+        self.standard_deviation.get()
+    }
+}
+impl ContinuousDistribution for Normal {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        Normal::pdf(self, x)
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        Normal::cdf(self, x)
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        Normal::quantile(self, p)
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        Normal::mean(self)
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        Normal::variance(self)
+    }
+}
+#[cfg(feature = "rand")]
+impl Distribution<f64> for Normal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        self.mean.get() + self.standard_deviation() * standard_normal_sample(rng)
+    }
+}
+
+/// Box–Muller transform
+#[cfg(feature = "rand")]
+pub(crate) fn standard_normal_sample<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    // `1 - [0, 1)` keeps the logarithm finite
+    let u_1 = 1. - rng.gen::<f64>();
+    let u_2 = rng.gen::<f64>();
+    (-2. * u_1.ln()).sqrt() * (2. * PI * u_2).cos()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,4 +232,45 @@ mod tests {
     fn z_1_05() {
         assert_eq!(Z_SCORE_TABLE.area(FiniteF64::new(1.05).unwrap()), 0.3531);
     }
+
+    #[test]
+    fn area_up_to_1_96() {
+        let z = FiniteF64::new(1.96).unwrap();
+        assert!((Z_SCORE_TABLE.area_up_to(z).get() - 0.975).abs() < 0.001);
+        let z = FiniteF64::new(-1.96).unwrap();
+        assert!((Z_SCORE_TABLE.area_up_to(z).get() - 0.025).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_normal() {
+        let normal = Normal {
+            mean: FiniteF64::new(1.).unwrap(),
+            standard_deviation: PositiveF64::new(2.).unwrap(),
+        };
+        let x = FiniteF64::new(1. + 2. * 1.959_963_984_540_054).unwrap();
+        assert!((normal.cdf(x).get() - 0.975).abs() < 1e-15);
+        let p = NormalizedF64::new(0.975).unwrap();
+        assert!((normal.quantile(p).get() - x.get()).abs() < 1e-12);
+        let pdf = normal.pdf(FiniteF64::new(1.).unwrap()).get();
+        assert!((pdf - 1. / (2. * (2. * PI).sqrt())).abs() < 1e-15);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let normal = Normal {
+            mean: FiniteF64::new(1.).unwrap(),
+            standard_deviation: PositiveF64::new(2.).unwrap(),
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples = normal
+            .sample_iter(&mut rng)
+            .take(20_000)
+            .collect::<Vec<_>>();
+        let (mean, variance) = crate::distributions::tests::moments(&samples);
+        assert!((mean - 1.).abs() < 0.05);
+        assert!((variance - 4.).abs() < 0.15);
+    }
 }
@@ -0,0 +1,146 @@
+#[cfg(feature = "rand")]
+use rand::{distributions::Distribution, Rng};
+use strict_num::{NormalizedF64, PositiveF64};
+
+use super::DiscreteDistribution;
+use crate::special::{gammainc_regularized, gammainc_upper_regularized, ln_gamma};
+
+/// Number of events in a fixed interval where events occur independently at `rate`
+#[derive(Debug, Clone, Copy)]
+pub struct Poisson {
+    pub rate: PositiveF64,
+}
+impl Poisson {
+    /// Probability of exactly `k` events
+    pub fn pmf(&self, k: usize) -> NormalizedF64 {
+        let rate = self.rate.get();
+        let k = k as f64;
+        let ln_power = if k == 0. { 0. } else { k * rate.ln() };
+        let pmf = (-rate + ln_power - ln_gamma(k + 1.)).exp();
+        NormalizedF64::new(pmf.clamp(0., 1.)).unwrap()
+    }
+
+    /// Probability of at most `k` events
+    pub fn cdf(&self, k: usize) -> NormalizedF64 {
+        if self.rate.get() == 0. {
+            return NormalizedF64::ONE;
+        }
+        // `P(X <= k) = Q(k + 1, rate)`
+        let cdf = gammainc_upper_regularized(k as f64 + 1., self.rate.get());
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// Probability of more than `k` events, accurate in the far upper tail
+    pub fn sf(&self, k: usize) -> NormalizedF64 {
+        if self.rate.get() == 0. {
+            return NormalizedF64::ZERO;
+        }
+        let sf = gammainc_regularized(k as f64 + 1., self.rate.get());
+        NormalizedF64::new(sf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The smallest `k` whose CDF is at least `p`, for `0 <= p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> usize {
+        assert!(p.get() < 1.);
+        let mut upper = self.rate.get().ceil() as usize + 1;
+        while self.cdf(upper).get() < p.get() {
+            upper *= 2;
+        }
+        let mut lower = 0;
+        while lower < upper {
+            let middle = (lower + upper) / 2;
+            if self.cdf(middle).get() < p.get() {
+                lower = middle + 1;
+            } else {
+                upper = middle;
+            }
+        }
+        lower
+    }
+
+    pub fn mean(&self) -> PositiveF64 {
+        self.rate
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        self.rate
+    }
+}
+impl DiscreteDistribution for Poisson {
+    fn pmf(&self, k: usize) -> NormalizedF64 {
+        Poisson::pmf(self, k)
+    }
+
+    fn cdf(&self, k: usize) -> NormalizedF64 {
+        Poisson::cdf(self, k)
+    }
+
+    fn sf(&self, k: usize) -> NormalizedF64 {
+        Poisson::sf(self, k)
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> usize {
+        Poisson::quantile(self, p)
+    }
+
+    fn mean(&self) -> PositiveF64 {
+        Poisson::mean(self)
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        Poisson::variance(self)
+    }
+}
+
+/// Sampling by inversion of the CDF
+#[cfg(feature = "rand")]
+impl Distribution<usize> for Poisson {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        self.quantile(NormalizedF64::new(rng.gen::<f64>()).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pmf_cdf() {
+        let poisson = Poisson {
+            rate: PositiveF64::new(10.).unwrap(),
+        };
+        assert!((poisson.pmf(3).get() - 0.007_566_654_960).abs() < 1e-12);
+        assert!((poisson.cdf(3).get() - 0.010_336_050_676).abs() < 1e-12);
+        assert!((poisson.cdf(3).get() + poisson.sf(3).get() - 1.).abs() < 1e-14);
+        assert_eq!(poisson.pmf(0).get(), (-10_f64).exp());
+    }
+
+    #[test]
+    fn test_quantile() {
+        let poisson = Poisson {
+            rate: PositiveF64::new(10.).unwrap(),
+        };
+        assert_eq!(poisson.quantile(NormalizedF64::new(0.01).unwrap()), 3);
+        assert_eq!(poisson.quantile(NormalizedF64::new(0.0104).unwrap()), 4);
+        assert_eq!(poisson.quantile(NormalizedF64::new(0.999_99).unwrap()), 26);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let poisson = Poisson {
+            rate: PositiveF64::new(10.).unwrap(),
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples = poisson
+            .sample_iter(&mut rng)
+            .take(20_000)
+            .map(|k| k as f64)
+            .collect::<Vec<_>>();
+        let (mean, variance) = crate::distributions::tests::moments(&samples);
+        assert!((mean - 10.).abs() < 0.1);
+        assert!((variance - 10.).abs() < 0.4);
+    }
+}
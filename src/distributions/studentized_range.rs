@@ -0,0 +1,102 @@
+use strict_num::{NormalizedF64, PositiveF64};
+
+use crate::special::{bisect, ln_gamma, normal_cdf, simpson};
+
+/// Subintervals of Simpson's rule in each dimension
+const INTEGRATION_STEPS: usize = 100;
+/// Half width of the integration range over a standard normal
+const NORMAL_RANGE: f64 = 8.;
+
+/// Range of `groups` independent standard normals divided by an independent `sqrt(chi_square(df) / df)`
+#[derive(Debug, Clone, Copy)]
+pub struct StudentizedRange {
+    pub groups: usize,
+    pub df: PositiveF64,
+}
+impl StudentizedRange {
+    /// Probability of a value less than or equal to `q`
+    pub fn cdf(&self, q: PositiveF64) -> NormalizedF64 {
+        let cdf = studentized_range_cdf(q.get(), self.groups, self.df.get());
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `q` whose CDF is `p`, for `0 <= p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> PositiveF64 {
+        assert!(p.get() < 1.);
+        let f = |q: f64| studentized_range_cdf(q, self.groups, self.df.get()) - p.get();
+        let mut upper = 4.;
+        while f(upper) < 0. {
+            upper *= 2.;
+        }
+        PositiveF64::new(bisect(f, 0., upper)).unwrap()
+    }
+}
+
+/// `P(Q <= q) = ∫ f_S(s) P(R <= q s) ds` where `S = sqrt(chi_square(df) / df)`
+fn studentized_range_cdf(q: f64, groups: usize, df: f64) -> f64 {
+    assert!(groups >= 2);
+    assert!(df > 0.);
+    if q == 0. {
+        return 0.;
+    }
+    let spread = 12. / (2. * df).sqrt();
+    let lower = (1. - spread).max(0.);
+    let upper = 1. + spread;
+    let ln_normalizer = df / 2. * df.ln() - ln_gamma(df / 2.) - (df / 2. - 1.) * 2_f64.ln();
+    let density = |s: f64| {
+        if s <= 0. {
+            return 0.;
+        }
+        (ln_normalizer + (df - 1.) * s.ln() - df * s * s / 2.).exp()
+    };
+    simpson(
+        |s| density(s) * normal_range_cdf(q * s, groups),
+        lower,
+        upper,
+        INTEGRATION_STEPS,
+    )
+}
+
+/// CDF of the range of `groups` independent standard normals: `k ∫ φ(z) (Φ(z + w) - Φ(z))^(k - 1) dz`
+fn normal_range_cdf(w: f64, groups: usize) -> f64 {
+    if w <= 0. {
+        return 0.;
+    }
+    let pdf = |z: f64| (-z * z / 2.).exp() / (2. * std::f64::consts::PI).sqrt();
+    let integrand = |z: f64| {
+        let inside = (normal_cdf(z + w) - normal_cdf(z)).max(0.);
+        pdf(z) * inside.powi(groups as i32 - 1)
+    };
+    groups as f64 * simpson(integrand, -NORMAL_RANGE, NORMAL_RANGE, INTEGRATION_STEPS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantile() {
+        // ref: `qtukey` in R
+        let p = NormalizedF64::new(0.95).unwrap();
+        let q = StudentizedRange {
+            groups: 3,
+            df: PositiveF64::new(10.).unwrap(),
+        }
+        .quantile(p);
+        assert!((q.get() - 3.876_777).abs() < 1e-5);
+        let q = StudentizedRange {
+            groups: 4,
+            df: PositiveF64::new(20.).unwrap(),
+        }
+        .quantile(p);
+        assert!((q.get() - 3.958_293).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_two_groups() {
+        // The range of two standard normals is `sqrt(2) |Z|`
+        let q = 1.959_963_984_540_054 * 2_f64.sqrt();
+        let cdf = normal_range_cdf(q, 2);
+        assert!((cdf - 0.95).abs() < 1e-7);
+    }
+}
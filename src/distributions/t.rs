@@ -1,9 +1,15 @@
-use std::num::NonZeroUsize;
+use std::{f64::consts::PI, num::NonZeroUsize};
 
 use once_cell::sync::Lazy;
-use strict_num::{FiniteF64, NormalizedF64};
+#[cfg(feature = "rand")]
+use rand::{distributions::Distribution, Rng};
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
 
 use super::normal::Z_SCORE_TABLE;
+use super::ContinuousDistribution;
+#[cfg(feature = "rand")]
+use super::{gamma::standard_gamma_sample, normal::standard_normal_sample};
+use crate::special::{betainc_regularized, bisect, ln_gamma};
 
 const TAIL_AREA_SEQUENCE_SIZE: usize = 10;
 #[rustfmt::skip]
@@ -67,28 +73,38 @@ impl TScoreTable {
         }
     }
 
-    fn row(&self, df: NonZeroUsize) -> Option<&[f64; TAIL_AREA_SEQUENCE_SIZE]> {
-        if df.get() <= 30 {
-            return Some(&self.t_scores_30[df.get() - 1]);
+    /// Critical t-scores for `df`, interpolated linearly in `ln(df)` between tabulated rows
+    fn row(&self, df: NonZeroUsize) -> Option<[f64; TAIL_AREA_SEQUENCE_SIZE]> {
+        let df = df.get();
+        if df <= MAX_DEGREES_OF_FREEDOM {
+            return Some(self.t_scores_30[df - 1]);
         }
-        if df.get() <= 40 {
-            return Some(&self.t_scores_40);
-        }
-        if df.get() <= 60 {
-            return Some(&self.t_scores_60);
-        }
-        if df.get() <= 80 {
-            return Some(&self.t_scores_80);
-        }
-        if df.get() <= 100 {
-            return Some(&self.t_scores_100);
-        }
-        if df.get() <= 1000 {
-            return Some(&self.t_scores_1000);
-        }
-        None
+        let rows = [
+            (
+                MAX_DEGREES_OF_FREEDOM,
+                &self.t_scores_30[MAX_DEGREES_OF_FREEDOM - 1],
+            ),
+            (40, &self.t_scores_40),
+            (60, &self.t_scores_60),
+            (80, &self.t_scores_80),
+            (100, &self.t_scores_100),
+            (1000, &self.t_scores_1000),
+        ];
+        let (lower, upper) = rows
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .find(|(_, (upper_df, _))| df <= *upper_df)?;
+        let ((lower_df, lower_row), (upper_df, upper_row)) = (lower, upper);
+        let weight = (df as f64 / lower_df as f64).ln() / (upper_df as f64 / lower_df as f64).ln();
+        Some(std::array::from_fn(|i| {
+            lower_row[i] + weight * (upper_row[i] - lower_row[i])
+        }))
     }
 
+    /// Tail area beyond `|t|`, interpolated linearly between the tabulated tail areas
+    ///
+    /// - Below the first column, the interpolation is toward the tail area of one half at `t = 0`.
+    /// - Beyond the last column, the tail area is zero.
     pub fn p_value_one_sided(&self, df: NonZeroUsize, t: FiniteF64) -> NormalizedF64 {
         let row = match self.row(df) {
             Some(row) => row,
@@ -97,17 +113,17 @@ impl TScoreTable {
 
         let t = t.get().abs();
 
-        let mut i = 0;
-        for col in row {
-            if *col > t {
-                break;
+        let mut previous = (0., 0.5);
+        for (&score, &area) in row.iter().zip(TAIL_AREA_SEQUENCE.iter()) {
+            if t < score {
+                let (previous_score, previous_area) = previous;
+                let weight = (t - previous_score) / (score - previous_score);
+                let p = previous_area + weight * (area - previous_area);
+                return NormalizedF64::new(p).unwrap();
             }
-            i += 1;
-        }
-        if i == row.len() {
-            return NormalizedF64::new(0.).unwrap();
+            previous = (score, area);
         }
-        NormalizedF64::new(TAIL_AREA_SEQUENCE[i]).unwrap()
+        NormalizedF64::new(0.).unwrap()
     }
 
     pub fn p_value_two_sided(&self, df: NonZeroUsize, t: FiniteF64) -> NormalizedF64 {
@@ -120,6 +136,98 @@ impl Default for TScoreTable {
     }
 }
 
+/// Student's t distribution with `df` degrees of freedom
+#[derive(Debug, Clone, Copy)]
+pub struct StudentT {
+    pub df: PositiveF64,
+}
+impl StudentT {
+    /// Probability density at `t`
+    pub fn pdf(&self, t: FiniteF64) -> PositiveF64 {
+        let df = self.df();
+        let ln_pdf = ln_gamma((df + 1.) / 2.)
+            - ln_gamma(df / 2.)
+            - 0.5 * (df * PI).ln()
+            - (df + 1.) / 2. * (t.get().powi(2) / df).ln_1p();
+        PositiveF64::new(ln_pdf.exp()).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `t`
+    pub fn cdf(&self, t: FiniteF64) -> NormalizedF64 {
+        let cdf = student_t_cdf(t.get(), self.df());
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `t` whose CDF is `p`, for `0 < p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        assert!(0. < p.get() && p.get() < 1.);
+        let df = self.df();
+        let f = |t: f64| student_t_cdf(t, df) - p.get();
+        let mut bound = 1.;
+        while f(-bound) > 0. || f(bound) < 0. {
+            bound *= 2.;
+        }
+        FiniteF64::new(bisect(f, -bound, bound)).unwrap()
+    }
+
+    /// Defined for `df > 1`
+    pub fn mean(&self) -> FiniteF64 {
+        assert!(self.df() > 1.);
+        FiniteF64::new(0.).unwrap()
+    }
+
+    /// Defined for `df > 2`
+    pub fn variance(&self) -> PositiveF64 {
+        let df = self.df();
+        assert!(df > 2.);
+        PositiveF64::new(df / (df - 2.)).unwrap()
+    }
+
+    fn df(&self) -> f64 {
+        assert!(self.df.get() > 0.);
+        self.df.get()
+    }
+}
+impl ContinuousDistribution for StudentT {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        StudentT::pdf(self, x)
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        StudentT::cdf(self, x)
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        StudentT::quantile(self, p)
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        StudentT::mean(self)
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        StudentT::variance(self)
+    }
+}
+#[cfg(feature = "rand")]
+impl Distribution<f64> for StudentT {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let df = self.df();
+        let chi_square = 2. * standard_gamma_sample(df / 2., rng);
+        standard_normal_sample(rng) / (chi_square / df).sqrt()
+    }
+}
+
+fn student_t_cdf(t: f64, df: f64) -> f64 {
+    // `P(|T| > |t|) = I_{df / (df + t^2)}(df / 2, 1 / 2)`
+    let tail = betainc_regularized(df / 2., 0.5, df / (df + t * t)) / 2.;
+    if t > 0. {
+        1. - tail
+    } else {
+        tail
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,6 +278,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn interpolation() {
+        // ref: `2 * pt(-2, df)` in R
+        let p = T_SCORE_TABLE
+            .p_value_two_sided(NonZeroUsize::new(35).unwrap(), FiniteF64::new(2.).unwrap())
+            .get();
+        assert!((p - 0.053_3).abs() < 0.002);
+        let p = T_SCORE_TABLE
+            .p_value_two_sided(NonZeroUsize::new(10).unwrap(), FiniteF64::new(2.).unwrap())
+            .get();
+        assert!((p - 0.073_4).abs() < 0.005);
+        let p = T_SCORE_TABLE
+            .p_value_two_sided(
+                NonZeroUsize::new(500).unwrap(),
+                FiniteF64::new(1.5).unwrap(),
+            )
+            .get();
+        assert!((p - 0.134_2).abs() < 0.01);
+    }
+
     #[test]
     fn df_1001_t_2() {
         assert!(
@@ -191,4 +319,31 @@ mod tests {
                 < 0.05
         );
     }
+
+    #[test]
+    fn test_student_t() {
+        let t = StudentT {
+            df: PositiveF64::new(10.).unwrap(),
+        };
+        let critical = FiniteF64::new(2.228_138_851_986_27).unwrap();
+        assert!((t.cdf(critical).get() - 0.975).abs() < 1e-12);
+        let p = NormalizedF64::new(0.025).unwrap();
+        assert!((t.quantile(p).get() + critical.get()).abs() < 1e-10);
+        assert!((t.variance().get() - 1.25).abs() < 1e-15);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_sample() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let t = StudentT {
+            df: PositiveF64::new(10.).unwrap(),
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples = t.sample_iter(&mut rng).take(20_000).collect::<Vec<_>>();
+        let (mean, variance) = crate::distributions::tests::moments(&samples);
+        assert!(mean.abs() < 0.05);
+        assert!((variance - 1.25).abs() < 0.1);
+    }
 }
@@ -0,0 +1,92 @@
+use super::ContinuousDistribution;
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+/// Continuous uniform distribution on `[lower, upper]`
+#[derive(Debug, Clone, Copy)]
+pub struct Uniform {
+    pub lower: FiniteF64,
+    pub upper: FiniteF64,
+}
+impl Uniform {
+    /// Probability density at `x`
+    pub fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        let (lower, upper) = self.bounds();
+        let pdf = if (lower..=upper).contains(&x.get()) {
+            1. / (upper - lower)
+        } else {
+            0.
+        };
+        PositiveF64::new(pdf).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        let (lower, upper) = self.bounds();
+        let cdf = (x.get() - lower) / (upper - lower);
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`
+    pub fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        let (lower, upper) = self.bounds();
+        FiniteF64::new(lower + p.get() * (upper - lower)).unwrap()
+    }
+
+    pub fn mean(&self) -> FiniteF64 {
+        let (lower, upper) = self.bounds();
+        FiniteF64::new((lower + upper) / 2.).unwrap()
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        let (lower, upper) = self.bounds();
+        PositiveF64::new((upper - lower).powi(2) / 12.).unwrap()
+    }
+
+    fn bounds(&self) -> (f64, f64) {
+        let (lower, upper) = (self.lower.get(), self.upper.get());
+        assert!(lower < upper);
+        (lower, upper)
+    }
+}
+impl ContinuousDistribution for Uniform {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        Uniform::pdf(self, x)
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        Uniform::cdf(self, x)
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        Uniform::quantile(self, p)
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        Uniform::mean(self)
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        Uniform::variance(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uniform() {
+        let uniform = Uniform {
+            lower: FiniteF64::new(-1.).unwrap(),
+            upper: FiniteF64::new(3.).unwrap(),
+        };
+        assert_eq!(uniform.pdf(FiniteF64::new(0.).unwrap()).get(), 0.25);
+        assert_eq!(uniform.pdf(FiniteF64::new(4.).unwrap()).get(), 0.);
+        assert_eq!(uniform.cdf(FiniteF64::new(0.).unwrap()).get(), 0.25);
+        assert_eq!(uniform.cdf(FiniteF64::new(-2.).unwrap()).get(), 0.);
+        let p = NormalizedF64::new(0.75).unwrap();
+        assert_eq!(uniform.quantile(p).get(), 2.);
+        assert_eq!(uniform.mean().get(), 1.);
+        assert!((uniform.variance().get() - 16. / 12.).abs() < 1e-15);
+    }
+}
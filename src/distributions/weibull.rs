@@ -0,0 +1,163 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use super::{non_negative, ContinuousDistribution};
+use crate::special::{bisect, ln_gamma};
+
+/// Weibull distribution with CDF `1 - e^(-(x / scale)^shape)`
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Weibull {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub shape: PositiveF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub scale: PositiveF64,
+}
+impl Weibull {
+    /// Maximum likelihood estimate from positive observations
+    ///
+    /// - The shape solves `1 / k + mean(ln x) - sum(x^k ln x) / sum(x^k) = 0`, whose left side decreases in `k`.
+    /// - Panics if all observations are equal, whose likelihood grows without bound in the shape.
+    pub fn fit(data: &[f64]) -> Self {
+        assert!(data.len() >= 2);
+        assert!(data.iter().all(|&x| x > 0.));
+        assert!(data.iter().any(|&x| x != data[0]));
+        let n = data.len() as f64;
+        let mean_ln = data.iter().map(|x| x.ln()).sum::<f64>() / n;
+        // Scale by the largest observation so that `x^k` cannot overflow
+        let max = data.iter().copied().fold(f64::MIN, f64::max);
+        let scaled = data.iter().map(|x| x / max).collect::<Vec<_>>();
+        let sums = |k: f64| {
+            scaled.iter().fold((0., 0.), |(sum, weighted), x| {
+                let power = x.powf(k);
+                (sum + power, weighted + power * x.ln())
+            })
+        };
+        let score = |k: f64| {
+            let (sum, weighted) = sums(k);
+            weighted / sum + max.ln() - 1. / k - mean_ln
+        };
+        let mut upper = 1.;
+        while score(upper) < 0. {
+            upper *= 2.;
+        }
+        let mut lower = 1.;
+        while score(lower) > 0. {
+            lower /= 2.;
+        }
+        let shape = bisect(score, lower, upper);
+        let scale = max * (sums(shape).0 / n).powf(1. / shape);
+        Self {
+            shape: PositiveF64::new(shape).unwrap(),
+            scale: PositiveF64::new(scale).unwrap(),
+        }
+    }
+
+    /// Probability density at `x`
+    ///
+    /// - Unbounded at 0 if `shape < 1`, which panics.
+    pub fn pdf(&self, x: PositiveF64) -> PositiveF64 {
+        let (shape, scale) = self.parameters();
+        let y = x.get() / scale;
+        let pdf = if y == 0. && shape == 1. {
+            1. / scale
+        } else {
+            shape / scale * y.powf(shape - 1.) * (-y.powf(shape)).exp()
+        };
+        PositiveF64::new(pdf).unwrap()
+    }
+
+    /// Probability of a value less than or equal to `x`
+    pub fn cdf(&self, x: PositiveF64) -> NormalizedF64 {
+        let (shape, scale) = self.parameters();
+        let cdf = -(-(x.get() / scale).powf(shape)).exp_m1();
+        NormalizedF64::new(cdf.clamp(0., 1.)).unwrap()
+    }
+
+    /// The `x` whose CDF is `p`, for `0 <= p < 1`
+    pub fn quantile(&self, p: NormalizedF64) -> PositiveF64 {
+        assert!(p.get() < 1.);
+        let (shape, scale) = self.parameters();
+        let x = scale * (-(-p.get()).ln_1p()).powf(1. / shape);
+        PositiveF64::new(x).unwrap()
+    }
+
+    pub fn mean(&self) -> PositiveF64 {
+        let (shape, scale) = self.parameters();
+        PositiveF64::new(scale * ln_gamma(1. + 1. / shape).exp()).unwrap()
+    }
+
+    pub fn variance(&self) -> PositiveF64 {
+        let (shape, scale) = self.parameters();
+        let first = ln_gamma(1. + 1. / shape).exp();
+        let second = ln_gamma(1. + 2. / shape).exp();
+        PositiveF64::new(scale.powi(2) * (second - first.powi(2))).unwrap()
+    }
+
+    fn parameters(&self) -> (f64, f64) {
+        let (shape, scale) = (self.shape.get(), self.scale.get());
+        assert!(shape > 0.);
+        assert!(scale > 0.);
+        (shape, scale)
+    }
+}
+impl ContinuousDistribution for Weibull {
+    fn pdf(&self, x: FiniteF64) -> PositiveF64 {
+        non_negative(x).map_or(PositiveF64::ZERO, |x| Weibull::pdf(self, x))
+    }
+
+    fn cdf(&self, x: FiniteF64) -> NormalizedF64 {
+        non_negative(x).map_or(NormalizedF64::ZERO, |x| Weibull::cdf(self, x))
+    }
+
+    fn quantile(&self, p: NormalizedF64) -> FiniteF64 {
+        FiniteF64::new(Weibull::quantile(self, p).get()).unwrap()
+    }
+
+    fn mean(&self) -> FiniteF64 {
+        FiniteF64::new(Weibull::mean(self).get()).unwrap()
+    }
+
+    fn variance(&self) -> PositiveF64 {
+        Weibull::variance(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weibull() {
+        let weibull = Weibull {
+            shape: PositiveF64::new(2.).unwrap(),
+            scale: PositiveF64::new(3.).unwrap(),
+        };
+        let x = PositiveF64::new(3.).unwrap();
+        assert!((weibull.cdf(x).get() - (1. - (-1_f64).exp())).abs() < 1e-15);
+        assert!((weibull.pdf(x).get() - 2. / 3. * (-1_f64).exp()).abs() < 1e-15);
+        let p = NormalizedF64::new(1. - (-1_f64).exp()).unwrap();
+        assert!((weibull.quantile(p).get() - 3.).abs() < 1e-14);
+        // `scale * sqrt(pi) / 2`
+        let mean = 3. * std::f64::consts::PI.sqrt() / 2.;
+        assert!((weibull.mean().get() - mean).abs() < 1e-12);
+        assert!((weibull.variance().get() - (9. - mean.powi(2))).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fit() {
+        let truth = Weibull {
+            shape: PositiveF64::new(1.5).unwrap(),
+            scale: PositiveF64::new(2.).unwrap(),
+        };
+        let n = 2000;
+        let data = (0..n)
+            .map(|i| {
+                let p = (i as f64 + 0.5) / n as f64;
+                truth.quantile(NormalizedF64::new(p).unwrap()).get()
+            })
+            .collect::<Vec<_>>();
+        let fitted = Weibull::fit(&data);
+        assert!((fitted.shape.get() - 1.5).abs() < 0.01);
+        assert!((fitted.scale.get() - 2.).abs() < 0.01);
+    }
+}
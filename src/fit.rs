@@ -0,0 +1,221 @@
+use std::num::NonZeroUsize;
+
+use strict_num::{FiniteF64, NormalizedF64};
+
+use crate::{
+    descriptive::Ecdf,
+    distributions::{
+        exponential::Exponential, gamma::Gamma, log_normal::LogNormal, normal::Normal,
+        weibull::Weibull, ContinuousDistribution,
+    },
+    model::{aic, bic, FittedModel},
+    Alternative, TestResult,
+};
+
+/// Terms kept of the alternating series of the Kolmogorov distribution
+const KOLMOGOROV_TERMS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Family {
+    Normal,
+    Exponential,
+    /// Positive data only
+    Gamma,
+    /// Positive data only
+    Weibull,
+    /// Positive data only
+    LogNormal,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FittedDistribution {
+    Normal(Normal),
+    Exponential(Exponential),
+    Gamma(Gamma),
+    Weibull(Weibull),
+    LogNormal(LogNormal),
+}
+impl FittedDistribution {
+    pub fn as_continuous(&self) -> &dyn ContinuousDistribution {
+        match self {
+            FittedDistribution::Normal(d) => d,
+            FittedDistribution::Exponential(d) => d,
+            FittedDistribution::Gamma(d) => d,
+            FittedDistribution::Weibull(d) => d,
+            FittedDistribution::LogNormal(d) => d,
+        }
+    }
+
+    /// Count of estimated parameters
+    pub fn parameters(&self) -> usize {
+        match self {
+            FittedDistribution::Exponential(_) => 1,
+            _ => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fit {
+    pub distribution: FittedDistribution,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub log_likelihood: FiniteF64,
+    /// `2 k - 2 ln L`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub aic: FiniteF64,
+    /// `k ln n - 2 ln L`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub bic: FiniteF64,
+    /// Count `n` of the data
+    pub count: NonZeroUsize,
+    /// [`kolmogorov_smirnov`] against the fitted distribution
+    ///
+    /// - Conservative since the parameters come from the same data.
+    pub goodness_of_fit: TestResult,
+}
+
+/// Maximum likelihood fit of `family` to `data` with its information criteria and goodness of fit
+pub fn fit_distribution(data: &[f64], family: Family) -> Fit {
+    let distribution = match family {
+        Family::Normal => FittedDistribution::Normal(Normal::fit(data)),
+        Family::Exponential => FittedDistribution::Exponential(Exponential::fit(data)),
+        Family::Gamma => FittedDistribution::Gamma(Gamma::fit(data)),
+        Family::Weibull => FittedDistribution::Weibull(Weibull::fit(data)),
+        Family::LogNormal => FittedDistribution::LogNormal(LogNormal::fit(data)),
+    };
+    let continuous = distribution.as_continuous();
+    let log_likelihood = data
+        .iter()
+        .map(|&x| continuous.pdf(FiniteF64::new(x).unwrap()).get().ln())
+        .sum::<f64>();
+    let log_likelihood = FiniteF64::new(log_likelihood).unwrap();
+    let k = distribution.parameters();
+    let count = NonZeroUsize::new(data.len()).unwrap();
+    Fit {
+        distribution,
+        log_likelihood,
+        aic: aic(log_likelihood, k),
+        bic: bic(log_likelihood, k, count),
+        count,
+        goodness_of_fit: kolmogorov_smirnov(data, continuous),
+    }
+}
+impl FittedModel for Fit {
+    fn log_likelihood(&self) -> FiniteF64 {
+        self.log_likelihood
+    }
+
+    fn parameter_count(&self) -> usize {
+        self.distribution.parameters()
+    }
+
+    fn observation_count(&self) -> NonZeroUsize {
+        self.count
+    }
+}
+
+/// One-sample Kolmogorov–Smirnov test.
+///
+/// Null hypothesis: `data` come from `distribution`.
+///
+/// - The statistic is the largest distance `D` between the empirical and the hypothesized CDFs.
+/// - The p-value is from the asymptotic Kolmogorov distribution with Stephens' correction for small samples.
+pub fn kolmogorov_smirnov(data: &[f64], distribution: &dyn ContinuousDistribution) -> TestResult {
+    let ecdf = Ecdf::new(data);
+    let n = data.len() as f64;
+    // The ECDF jumps from `i / n` to `(i + 1) / n` at the `i`-th value
+    let d = ecdf
+        .values()
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let cdf = distribution.cdf(FiniteF64::new(x).unwrap()).get();
+            ((i + 1) as f64 / n - cdf).max(cdf - i as f64 / n)
+        })
+        .fold(0., f64::max);
+
+    let lambda = (n.sqrt() + 0.12 + 0.11 / n.sqrt()) * d;
+    let p_value = (1..=KOLMOGOROV_TERMS)
+        .map(|k| {
+            let sign = if k % 2 == 1 { 1. } else { -1. };
+            sign * (-2. * (k * k) as f64 * lambda.powi(2)).exp()
+        })
+        .sum::<f64>()
+        * 2.;
+    TestResult {
+        statistic: FiniteF64::new(d).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(p_value.clamp(0., 1.)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: [f64; 20] = [
+        1.2, 2.3, 0.8, 3.1, 1.7, 2.9, 0.5, 1.1, 4.2, 2.0, 1.5, 0.9, 2.6, 3.8, 1.3, 0.7, 1.9, 2.4,
+        5.1, 1.6,
+    ];
+
+    #[test]
+    fn test_fit_distribution() {
+        // `(family, log-likelihood, AIC, BIC, D, p-value)`
+        let expected = [
+            (
+                Family::Normal,
+                -32.174_087_925,
+                68.348_175_850,
+                70.339_640_392,
+                0.126_379_644,
+                0.885_395_239,
+            ),
+            (
+                Family::Exponential,
+                -34.647_357_874,
+                71.294_715_749,
+                72.290_448_022,
+                0.235_761_588,
+                0.186_913_278,
+            ),
+            (
+                Family::Gamma,
+                -29.563_687_718,
+                63.127_375_437,
+                65.118_839_984,
+                0.061_856_669,
+                0.999_997_636,
+            ),
+            (
+                Family::Weibull,
+                -29.955_688_120,
+                63.911_376_240,
+                65.902_840_788,
+                0.076_499_013,
+                0.999_640_576,
+            ),
+            (
+                Family::LogNormal,
+                -29.588_056_111,
+                63.176_112_222,
+                65.167_576_770,
+                0.074_368_377,
+                0.999_791_925,
+            ),
+        ];
+        for (family, log_likelihood, aic, bic, d, p_value) in expected {
+            let fit = fit_distribution(&DATA, family);
+            assert!((fit.log_likelihood.get() - log_likelihood).abs() < 1e-7);
+            assert!((fit.aic.get() - aic).abs() < 1e-7);
+            assert!((fit.bic.get() - bic).abs() < 1e-7);
+            assert!((fit.goodness_of_fit.statistic.get() - d).abs() < 1e-7);
+            assert!((fit.goodness_of_fit.p_value.get() - p_value).abs() < 1e-7);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! Reading samples and tables from CSV with a header row.
+
+use std::fmt;
+
+use crate::{categorical::ContingencyTable, numerical::NumericalSample};
+
+#[derive(Debug)]
+pub enum Error {
+    Csv(csv::Error),
+    /// No column of this name in the header
+    MissingColumn(String),
+    /// A cell that is not a finite number, by its 1-based line
+    NotANumber {
+        line: u64,
+        value: String,
+    },
+    /// Fewer values than the statistic needs
+    TooFewValues {
+        required: usize,
+        found: usize,
+    },
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Csv(e) => write!(f, "{e}"),
+            Error::MissingColumn(column) => write!(f, "no column `{column}`"),
+            Error::NotANumber { line, value } => {
+                write!(f, "line {line}: `{value}` is not a finite number")
+            }
+            Error::TooFewValues { required, found } => {
+                write!(f, "expected at least {required} values, found {found}")
+            }
+        }
+    }
+}
+impl std::error::Error for Error {}
+impl From<csv::Error> for Error {
+    fn from(e: csv::Error) -> Self {
+        Error::Csv(e)
+    }
+}
+
+/// Finite numbers in `column`, skipping empty cells
+pub fn read_column(reader: impl std::io::Read, column: &str) -> Result<Vec<f64>, Error> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let index = column_index(&mut reader, column)?;
+    let mut values = vec![];
+    for record in reader.records() {
+        let record = record?;
+        let cell = record.get(index).unwrap_or_default().trim();
+        if cell.is_empty() {
+            continue;
+        }
+        let value = cell
+            .parse::<f64>()
+            .ok()
+            .filter(|value| value.is_finite())
+            .ok_or_else(|| Error::NotANumber {
+                line: record.position().map_or(0, |position| position.line()),
+                value: cell.to_string(),
+            })?;
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// [`NumericalSample::from_values`] of the numbers in `column`, of which there must be at least 2
+pub fn read_sample(reader: impl std::io::Read, column: &str) -> Result<NumericalSample, Error> {
+    let values = read_column(reader, column)?;
+    if values.len() < 2 {
+        return Err(Error::TooFewValues {
+            required: 2,
+            found: values.len(),
+        });
+    }
+    Ok(NumericalSample::from_values(&values))
+}
+
+/// Cross tabulation of `row_column` against `col_column`
+///
+/// - Labels are in order of first appearance.
+/// - Rows with an empty cell in either column are skipped.
+pub fn read_crosstab(
+    reader: impl std::io::Read,
+    row_column: &str,
+    col_column: &str,
+) -> Result<ContingencyTable, Error> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let row_index = column_index(&mut reader, row_column)?;
+    let col_index = column_index(&mut reader, col_column)?;
+    let mut pairs = vec![];
+    for record in reader.records() {
+        let record = record?;
+        let row = record.get(row_index).unwrap_or_default().trim();
+        let col = record.get(col_index).unwrap_or_default().trim();
+        if row.is_empty() || col.is_empty() {
+            continue;
+        }
+        pairs.push((row.to_string(), col.to_string()));
+    }
+    Ok(ContingencyTable::from_pairs(pairs))
+}
+
+fn column_index<R: std::io::Read>(
+    reader: &mut csv::Reader<R>,
+    column: &str,
+) -> Result<usize, Error> {
+    reader
+        .headers()?
+        .iter()
+        .position(|header| header.trim() == column)
+        .ok_or_else(|| Error::MissingColumn(column.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "\
+group,outcome,score
+a,yes,7.1
+a,no,6.8
+b,yes,
+b,no,6.2
+a,yes,7.4
+";
+
+    #[test]
+    fn test_read_column() {
+        let values = read_column(CSV.as_bytes(), "score").unwrap();
+        assert_eq!(values, [7.1, 6.8, 6.2, 7.4]);
+        let sample = read_sample(CSV.as_bytes(), "score").unwrap();
+        assert_eq!(sample.count.get(), 4);
+        assert!(matches!(
+            read_sample("score\n7.1\n".as_bytes(), "score"),
+            Err(Error::TooFewValues {
+                required: 2,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            read_column(CSV.as_bytes(), "missing"),
+            Err(Error::MissingColumn(_))
+        ));
+        assert!(matches!(
+            read_column(CSV.as_bytes(), "group"),
+            Err(Error::NotANumber { line: 2, .. })
+        ));
+        assert!(matches!(
+            read_sample("x\n1\nNaN\n3\n".as_bytes(), "x"),
+            Err(Error::NotANumber { line: 3, .. })
+        ));
+        assert!(read_column("x\n-inf\n".as_bytes(), "x").is_err());
+    }
+
+    #[test]
+    fn test_read_crosstab() {
+        let table = read_crosstab(CSV.as_bytes(), "group", "outcome").unwrap();
+        assert_eq!(table.row_labels, ["a", "b"]);
+        assert_eq!(table.col_labels, ["yes", "no"]);
+        assert_eq!(table.counts, [[2, 1], [1, 1]]);
+    }
+}
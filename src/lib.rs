@@ -1,3 +1,121 @@
+pub mod agreement;
+pub mod auto;
+pub mod bayes;
 pub mod categorical;
+pub mod classification;
+pub mod confidence;
+pub mod correlation;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+pub mod descriptive;
 pub mod distributions;
+pub mod fit;
+#[cfg(feature = "csv")]
+pub mod io;
+mod linear_algebra;
+pub mod meta;
+pub mod model;
+pub mod multiple_testing;
+pub mod multivariate;
+pub mod nonparametric;
 pub mod numerical;
+pub mod outliers;
+pub mod power;
+pub mod regression;
+#[cfg(feature = "rand")]
+pub mod resampling;
+pub mod robust;
+#[cfg(feature = "serde")]
+mod serde_strict;
+pub mod special;
+pub mod survival;
+pub mod timeseries;
+
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+/// Direction of the alternative hypothesis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alternative {
+    /// The parameter differs from its null value
+    TwoSided,
+    /// The parameter is less than its null value
+    Less,
+    /// The parameter is greater than its null value
+    Greater,
+}
+
+/// Outcome of a hypothesis test
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TestResult {
+    /// The test statistic, e.g., `z`, `t`, `F`, or chi-square
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub statistic: FiniteF64,
+    /// Degrees of freedom of the null distribution of the statistic, if it has any
+    pub df: Option<DegreesOfFreedom>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub p_value: NormalizedF64,
+    pub alternative: Alternative,
+    /// Standardized effect size, e.g., Cohen's d, Cohen's h, or Cramér's V
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub effect_size: Option<FiniteF64>,
+    /// Confidence interval of the tested parameter
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub confidence_interval: Option<(FiniteF64, FiniteF64)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DegreesOfFreedom {
+    /// e.g., of a t or chi-square statistic
+    One(#[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))] PositiveF64),
+    /// e.g., of an F statistic
+    Two(
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))] PositiveF64,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))] PositiveF64,
+    ),
+}
+impl DegreesOfFreedom {
+    pub(crate) fn one(df: usize) -> Self {
+        Self::One(PositiveF64::new(df as f64).unwrap())
+    }
+}
+
+/// Whether one large-sample condition of a test holds
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Diagnostic {
+    /// e.g., "expected successes n p"
+    pub condition: String,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub observed: FiniteF64,
+    /// The least `observed` value for the condition to hold
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub required: FiniteF64,
+}
+impl Diagnostic {
+    pub(crate) fn at_least(condition: impl Into<String>, observed: f64, required: f64) -> Self {
+        Self {
+            condition: condition.into(),
+            observed: FiniteF64::new(observed).unwrap(),
+            required: FiniteF64::new(required).unwrap(),
+        }
+    }
+
+    pub fn is_satisfied(&self) -> bool {
+        self.observed >= self.required
+    }
+}
+
+/// Inputs of a test that can report on its large-sample conditions before the test asserts them
+pub trait CheckAssumptions {
+    fn check_assumptions(&self) -> Vec<Diagnostic>;
+
+    /// Whether every condition holds
+    fn satisfies_assumptions(&self) -> bool {
+        self.check_assumptions()
+            .iter()
+            .all(|diagnostic| diagnostic.is_satisfied())
+    }
+}
@@ -0,0 +1,83 @@
+pub(crate) type Matrix = Vec<Vec<f64>>;
+
+/// Lower triangular `L` such that `a = L L^T`.
+///
+/// Returns `None` if `a` is not positive definite.
+pub(crate) fn cholesky(a: &[Vec<f64>]) -> Option<Matrix> {
+    let n = a.len();
+    let mut l = vec![vec![0.; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum = (0..j).map(|k| l[i][k] * l[j][k]).sum::<f64>();
+            if i == j {
+                let diagonal = a[i][i] - sum;
+                if diagonal <= 0. || !diagonal.is_finite() {
+                    return None;
+                }
+                l[i][j] = diagonal.sqrt();
+            } else {
+                l[i][j] = (a[i][j] - sum) / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Solve `a x = b` for a symmetric positive definite `a`.
+pub(crate) fn solve_symmetric(a: &[Vec<f64>], b: &[f64]) -> Option<Vec<f64>> {
+    let l = cholesky(a)?;
+    Some(cholesky_solve(&l, b))
+}
+
+/// Inverse of a symmetric positive definite matrix.
+pub(crate) fn inverse_symmetric(a: &[Vec<f64>]) -> Option<Matrix> {
+    let l = cholesky(a)?;
+    let n = a.len();
+    let mut inverse = vec![vec![0.; n]; n];
+    for j in 0..n {
+        let mut unit = vec![0.; n];
+        unit[j] = 1.;
+        let column = cholesky_solve(&l, &unit);
+        (0..n).for_each(|i| inverse[i][j] = column[i]);
+    }
+    Some(inverse)
+}
+
+/// Natural logarithm of the determinant of a symmetric positive definite matrix.
+pub(crate) fn ln_determinant(a: &[Vec<f64>]) -> Option<f64> {
+    let l = cholesky(a)?;
+    Some(2. * (0..a.len()).map(|i| l[i][i].ln()).sum::<f64>())
+}
+
+fn cholesky_solve(l: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let n = l.len();
+    // L y = b
+    let mut y = vec![0.; n];
+    for i in 0..n {
+        let sum = (0..i).map(|k| l[i][k] * y[k]).sum::<f64>();
+        y[i] = (b[i] - sum) / l[i][i];
+    }
+    // L^T x = y
+    let mut x = vec![0.; n];
+    for i in (0..n).rev() {
+        let sum = (i + 1..n).map(|k| l[k][i] * x[k]).sum::<f64>();
+        x[i] = (y[i] - sum) / l[i][i];
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inverse_symmetric() {
+        let a = vec![vec![4., 2.], vec![2., 3.]];
+        let inverse = inverse_symmetric(&a).unwrap();
+        assert!((inverse[0][0] - 3. / 8.).abs() < 1e-12);
+        assert!((inverse[0][1] - -2. / 8.).abs() < 1e-12);
+        assert!((inverse[1][1] - 4. / 8.).abs() < 1e-12);
+        assert!(inverse_symmetric(&[vec![1., 2.], vec![2., 1.]]).is_none());
+        assert!((ln_determinant(&a).unwrap() - 8_f64.ln()).abs() < 1e-12);
+    }
+}
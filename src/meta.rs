@@ -0,0 +1,155 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    special::{gammainc_upper_regularized, normal_cdf, normal_quantile},
+    Alternative, DegreesOfFreedom, TestResult,
+};
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PooledEffect {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub estimate: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub standard_error: PositiveF64,
+    /// `z = estimate / standard_error` with the confidence interval of the estimate
+    ///
+    /// Null hypothesis: the pooled effect is zero.
+    pub test: TestResult,
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MetaAnalysis {
+    /// Inverse-variance fixed-effect model
+    pub fixed: PooledEffect,
+    /// DerSimonian–Laird random-effects model
+    pub random: PooledEffect,
+    /// Cochran's Q with `k - 1` degrees of freedom
+    ///
+    /// Null hypothesis: all studies share one true effect.
+    pub heterogeneity: TestResult,
+    /// Share of the variation across studies due to heterogeneity rather than chance
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub i_squared: NormalizedF64,
+    /// Between-study variance
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub tau_squared: PositiveF64,
+}
+
+/// Pool per-study effect estimates.
+///
+/// - `variances[i]`: sampling variance of `estimates[i]`
+///
+/// ref: DerSimonian, R., & Laird, N. (1986). Meta-analysis in clinical trials.
+pub fn meta_analysis(
+    estimates: &[f64],
+    variances: &[PositiveF64],
+    confidence: NormalizedF64,
+) -> MetaAnalysis {
+    assert_eq!(estimates.len(), variances.len());
+    assert!(estimates.len() >= 2);
+    assert!(variances.iter().all(|v| v.get() > 0.));
+    let k = estimates.len();
+
+    let weights = variances.iter().map(|v| 1. / v.get()).collect::<Vec<_>>();
+    let fixed = pool(estimates, &weights, confidence);
+    let q = estimates
+        .iter()
+        .zip(&weights)
+        .map(|(y, w)| w * (y - fixed.estimate.get()).powi(2))
+        .sum::<f64>();
+    let df = (k - 1) as f64;
+
+    let sum_weights = weights.iter().sum::<f64>();
+    let c = sum_weights - weights.iter().map(|w| w.powi(2)).sum::<f64>() / sum_weights;
+    let tau_squared = ((q - df) / c).max(0.);
+    let i_squared = if q > 0. { ((q - df) / q).max(0.) } else { 0. };
+    let random_weights = variances
+        .iter()
+        .map(|v| 1. / (v.get() + tau_squared))
+        .collect::<Vec<_>>();
+    let random = pool(estimates, &random_weights, confidence);
+
+    let heterogeneity = TestResult {
+        statistic: FiniteF64::new(q).unwrap(),
+        df: Some(DegreesOfFreedom::one(k - 1)),
+        p_value: NormalizedF64::new(gammainc_upper_regularized(df / 2., q / 2.)).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: None,
+        confidence_interval: None,
+    };
+    MetaAnalysis {
+        fixed,
+        random,
+        heterogeneity,
+        i_squared: NormalizedF64::new(i_squared).unwrap(),
+        tau_squared: PositiveF64::new(tau_squared).unwrap(),
+    }
+}
+
+fn pool(estimates: &[f64], weights: &[f64], confidence: NormalizedF64) -> PooledEffect {
+    let sum_weights = weights.iter().sum::<f64>();
+    let estimate = estimates
+        .iter()
+        .zip(weights)
+        .map(|(y, w)| w * y)
+        .sum::<f64>()
+        / sum_weights;
+    let standard_error = sum_weights.sqrt().recip();
+    let z = estimate / standard_error;
+    let margin = normal_quantile(0.5 + confidence.get() / 2.) * standard_error;
+    PooledEffect {
+        estimate: FiniteF64::new(estimate).unwrap(),
+        standard_error: PositiveF64::new(standard_error).unwrap(),
+        test: TestResult {
+            statistic: FiniteF64::new(z).unwrap(),
+            df: None,
+            p_value: NormalizedF64::new(2. * normal_cdf(-z.abs())).unwrap(),
+            alternative: Alternative::TwoSided,
+            effect_size: None,
+            confidence_interval: Some((
+                FiniteF64::new(estimate - margin).unwrap(),
+                FiniteF64::new(estimate + margin).unwrap(),
+            )),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meta_analysis() {
+        let estimates = [0.10, 0.30, 0.35, 0.65, 0.45, 0.15];
+        let variances = [0.03, 0.03, 0.05, 0.01, 0.05, 0.02].map(|v| PositiveF64::new(v).unwrap());
+        let meta = meta_analysis(&estimates, &variances, NormalizedF64::new(0.95).unwrap());
+
+        assert!((meta.fixed.estimate.get() - 0.396_753_247).abs() < 1e-9);
+        assert!((meta.fixed.standard_error.get() - 0.062_418_778).abs() < 1e-9);
+        let (lower, upper) = meta.fixed.test.confidence_interval.unwrap();
+        assert!((lower.get() - 0.274_414_689).abs() < 1e-8);
+        assert!((upper.get() - 0.519_091_804).abs() < 1e-8);
+        assert!((meta.fixed.test.p_value.get() - 2.066_547e-10).abs() < 1e-15);
+
+        assert!((meta.heterogeneity.statistic.get() - 12.805_627_706).abs() < 1e-8);
+        assert!((meta.heterogeneity.p_value.get() - 0.025_269_953).abs() < 1e-8);
+        assert!((meta.tau_squared.get() - 0.039_785_966).abs() < 1e-9);
+        assert!((meta.i_squared.get() - 0.609_546_668).abs() < 1e-9);
+
+        assert!((meta.random.estimate.get() - 0.344_249_678).abs() < 1e-9);
+        assert!((meta.random.standard_error.get() - 0.106_754_190).abs() < 1e-9);
+        assert!((meta.random.test.p_value.get() - 0.001_261_070).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_homogeneous_studies() {
+        let estimates = [0.2, 0.2, 0.2];
+        let variances = [0.01, 0.02, 0.04].map(|v| PositiveF64::new(v).unwrap());
+        let meta = meta_analysis(&estimates, &variances, NormalizedF64::new(0.95).unwrap());
+        assert_eq!(meta.tau_squared.get(), 0.);
+        assert_eq!(meta.i_squared.get(), 0.);
+        assert_eq!(meta.fixed.estimate, meta.random.estimate);
+    }
+}
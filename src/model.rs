@@ -0,0 +1,119 @@
+use std::num::NonZeroUsize;
+
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{special::gammainc_upper_regularized, Alternative, DegreesOfFreedom, TestResult};
+
+/// A model fitted by maximum likelihood
+pub trait FittedModel {
+    /// Maximized log-likelihood `ln L`
+    fn log_likelihood(&self) -> FiniteF64;
+
+    /// Count `k` of estimated parameters
+    fn parameter_count(&self) -> usize;
+
+    /// Count `n` of observations the model is fitted to
+    fn observation_count(&self) -> NonZeroUsize;
+
+    /// [`aic`] of this model
+    fn aic(&self) -> FiniteF64 {
+        aic(self.log_likelihood(), self.parameter_count())
+    }
+
+    /// [`bic`] of this model
+    fn bic(&self) -> FiniteF64 {
+        bic(
+            self.log_likelihood(),
+            self.parameter_count(),
+            self.observation_count(),
+        )
+    }
+}
+
+/// Akaike information criterion `2 k - 2 ln L`; lower is better
+pub fn aic(log_likelihood: FiniteF64, parameters: usize) -> FiniteF64 {
+    FiniteF64::new(2. * parameters as f64 - 2. * log_likelihood.get()).unwrap()
+}
+
+/// Bayesian information criterion `k ln n - 2 ln L`; lower is better
+pub fn bic(log_likelihood: FiniteF64, parameters: usize, observations: NonZeroUsize) -> FiniteF64 {
+    let penalty = parameters as f64 * (observations.get() as f64).ln();
+    FiniteF64::new(penalty - 2. * log_likelihood.get()).unwrap()
+}
+
+/// Likelihood-ratio test of nested models.
+///
+/// Null hypothesis: the simpler model fits as well as the one with `df_diff` more free parameters.
+///
+/// - The statistic `2 (ln L_alt - ln L_null)` is asymptotically chi-square with `df_diff` degrees of freedom.
+pub fn lrt(loglik_null: FiniteF64, loglik_alt: FiniteF64, df_diff: NonZeroUsize) -> TestResult {
+    let statistic = 2. * (loglik_alt.get() - loglik_null.get());
+    // The larger model can fall short only by rounding errors
+    assert!(statistic > -LOG_LIKELIHOOD_TOLERANCE);
+    let statistic = statistic.max(0.);
+    let df = df_diff.get() as f64;
+    let p_value = gammainc_upper_regularized(df / 2., statistic / 2.);
+    TestResult {
+        statistic: FiniteF64::new(statistic).unwrap(),
+        df: Some(DegreesOfFreedom::One(PositiveF64::new(df).unwrap())),
+        p_value: NormalizedF64::new(p_value.clamp(0., 1.)).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// [`lrt`] of a `null` model nested in `alternative`, both fitted to the same observations
+pub fn compare_nested(null: &dyn FittedModel, alternative: &dyn FittedModel) -> TestResult {
+    assert_eq!(null.observation_count(), alternative.observation_count());
+    let df_diff = alternative
+        .parameter_count()
+        .checked_sub(null.parameter_count())
+        .unwrap();
+    lrt(
+        null.log_likelihood(),
+        alternative.log_likelihood(),
+        NonZeroUsize::new(df_diff).unwrap(),
+    )
+}
+
+/// Slack for a larger model with a smaller maximized log-likelihood despite rounding errors
+const LOG_LIKELIHOOD_TOLERANCE: f64 = 1e-6;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fit::{fit_distribution, Family};
+
+    #[test]
+    fn test_lrt() {
+        let null = FiniteF64::new(-120.5).unwrap();
+        let alt = FiniteF64::new(-115.2).unwrap();
+        let result = lrt(null, alt, NonZeroUsize::new(2).unwrap());
+        assert!((result.statistic.get() - 10.6).abs() < 1e-9);
+        // The chi-square survival function with 2 degrees of freedom is `exp(-x / 2)`
+        assert!((result.p_value.get() - (-5.3_f64).exp()).abs() < 1e-12);
+
+        assert_eq!(aic(null, 3).get(), 247.);
+        let n = NonZeroUsize::new(100).unwrap();
+        assert!((bic(null, 3, n).get() - (3. * 100_f64.ln() + 241.)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_compare_nested() {
+        let data = [
+            1.2, 2.3, 0.8, 3.1, 1.7, 2.9, 0.5, 1.1, 4.2, 2.0, 1.5, 0.9, 2.6, 3.8, 1.3, 0.7, 1.9,
+            2.4, 5.1, 1.6,
+        ];
+        // The exponential distribution is the gamma distribution with shape 1
+        let exponential = fit_distribution(&data, Family::Exponential);
+        let gamma = fit_distribution(&data, Family::Gamma);
+        assert_eq!(FittedModel::aic(&gamma), gamma.aic);
+        assert_eq!(FittedModel::bic(&gamma), gamma.bic);
+
+        let result = compare_nested(&exponential, &gamma);
+        let statistic = 2. * (gamma.log_likelihood.get() - exponential.log_likelihood.get());
+        assert!((result.statistic.get() - statistic).abs() < 1e-12);
+        assert!(result.p_value.get() < 0.05);
+    }
+}
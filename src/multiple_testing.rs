@@ -0,0 +1,287 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    linear_algebra::solve_symmetric,
+    special::{gammainc_upper_regularized, normal_cdf, normal_quantile},
+    Alternative, DegreesOfFreedom, TestResult,
+};
+
+/// Adjustment of p-values for testing several hypotheses at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Correction {
+    /// Multiply every p-value by the count of tests, controlling the family-wise error rate
+    Bonferroni,
+    /// Step-down Bonferroni, controlling the family-wise error rate and never less powerful than [`Correction::Bonferroni`]
+    Holm,
+    /// Benjamini–Hochberg step-up procedure, controlling the false discovery rate of independent or positively dependent tests
+    BenjaminiHochberg,
+}
+
+/// Adjusted p-values in the same order as `p_values`, to be compared against the significance level directly
+pub fn adjust(p_values: &[NormalizedF64], correction: Correction) -> Vec<NormalizedF64> {
+    let m = p_values.len() as f64;
+    let mut order = (0..p_values.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| p_values[a].cmp(&p_values[b]));
+
+    let mut adjusted = vec![0.; p_values.len()];
+    match correction {
+        Correction::Bonferroni => p_values
+            .iter()
+            .zip(&mut adjusted)
+            .for_each(|(p, adjusted)| *adjusted = p.get() * m),
+        Correction::Holm => {
+            let mut running_max = 0_f64;
+            for (rank, &i) in order.iter().enumerate() {
+                running_max = running_max.max((m - rank as f64) * p_values[i].get());
+                adjusted[i] = running_max;
+            }
+        }
+        Correction::BenjaminiHochberg => {
+            let mut running_min = 1_f64;
+            for (rank, &i) in order.iter().enumerate().rev() {
+                running_min = running_min.min(m * p_values[i].get() / (rank + 1) as f64);
+                adjusted[i] = running_min;
+            }
+        }
+    }
+    adjusted
+        .into_iter()
+        .map(|p| NormalizedF64::new(p.min(1.)).unwrap())
+        .collect()
+}
+
+/// Way to combine the p-values of independent tests of the same null hypothesis
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Combination {
+    /// `-2 sum ln p`, chi-square with `2 k` degrees of freedom; sensitive to a single small p-value
+    Fisher,
+    /// `sum w z / sqrt(sum w^2)` of `z = Φ^-1(1 - p)`, standard normal; weights are often the square roots of the sample sizes
+    Stouffer,
+    /// `sum w / sum (w / p)`, robust to dependence between the tests
+    ///
+    /// - The harmonic mean itself is reported as the p-value, which is accurate for small values and slightly anti-conservative otherwise.
+    HarmonicMean,
+}
+
+/// Combine the one-sided p-values of `k` independent tests into one.
+///
+/// - p-values of `0` and `1` are moved just inside the unit interval, where Fisher's and Stouffer's statistics stay finite.
+/// - `weights`: one per p-value, for [`Combination::Stouffer`] and [`Combination::HarmonicMean`]; equal weights if absent
+pub fn combine(
+    p_values: &[NormalizedF64],
+    method: Combination,
+    weights: Option<&[PositiveF64]>,
+) -> TestResult {
+    let k = p_values.len();
+    assert!(k >= 1);
+    let weights = match weights {
+        Some(weights) => {
+            assert_eq!(weights.len(), k);
+            assert_ne!(method, Combination::Fisher, "Fisher's method is unweighted");
+            weights.iter().map(|w| w.get()).collect()
+        }
+        None => vec![1.; k],
+    };
+    let p_values = p_values
+        .iter()
+        .map(|p| p.get().clamp(f64::MIN_POSITIVE, 1. - f64::EPSILON / 2.))
+        .collect::<Vec<_>>();
+    let (statistic, df, p_value) = match method {
+        Combination::Fisher => {
+            let chi_square = -2. * p_values.iter().map(|p| p.ln()).sum::<f64>();
+            let p_value = gammainc_upper_regularized(k as f64, chi_square / 2.);
+            (chi_square, Some(DegreesOfFreedom::one(2 * k)), p_value)
+        }
+        Combination::Stouffer => {
+            // `Φ^-1(1 - p) = -Φ^-1(p)` keeps the precision of small p-values
+            let weighted_sum = p_values
+                .iter()
+                .zip(&weights)
+                .map(|(p, w)| -w * normal_quantile(*p))
+                .sum::<f64>();
+            let z = weighted_sum / weights.iter().map(|w| w.powi(2)).sum::<f64>().sqrt();
+            (z, None, normal_cdf(-z))
+        }
+        Combination::HarmonicMean => {
+            let harmonic_mean = weights.iter().sum::<f64>()
+                / p_values
+                    .iter()
+                    .zip(&weights)
+                    .map(|(p, w)| w / p)
+                    .sum::<f64>();
+            (harmonic_mean, None, harmonic_mean)
+        }
+    };
+    TestResult {
+        statistic: FiniteF64::new(statistic).unwrap(),
+        df,
+        p_value: NormalizedF64::new(p_value.clamp(0., 1.)).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// Estimator of `π₀`, the proportion of true null hypotheses, from the p-values above each tuning parameter `λ` in `0.05, 0.10, ..., 0.95`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pi0Method {
+    /// Fit a quadratic, with the same three degrees of freedom as Storey and Tibshirani's smoothing spline, to `π₀(λ)` and evaluate it at the largest `λ`
+    Smoother,
+    /// Pick the `π₀(λ)` of least bootstrap mean squared error, in the closed form of Storey, Taylor and Siegmund
+    Bootstrap,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QValues {
+    /// Estimated proportion of true null hypotheses
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub pi0: NormalizedF64,
+    /// Minimum false discovery rate at which each hypothesis is rejected, in the same order as the p-values
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub q_values: Vec<NormalizedF64>,
+}
+
+/// Storey's q-values: [`Correction::BenjaminiHochberg`] scaled by the estimated proportion of true null hypotheses, gaining power when many of them are false.
+///
+/// - The estimate of `π₀` needs many p-values, as in genome-wide screening, to be stable.
+/// - `π₀` is at least `1 / m`, the smallest positive proportion of `m` hypotheses, so that extrapolating the smoother below zero cannot zero out every q-value.
+pub fn qvalues(p_values: &[NormalizedF64], pi0: Pi0Method) -> QValues {
+    let m = p_values.len() as f64;
+    assert!(m >= 1.);
+    let lambdas = (1..20).map(|i| 0.05 * i as f64).collect::<Vec<_>>();
+    let above = |lambda: f64| p_values.iter().filter(|p| p.get() >= lambda).count() as f64;
+    let pi0_at = lambdas
+        .iter()
+        .map(|&lambda| above(lambda) / (m * (1. - lambda)))
+        .collect::<Vec<_>>();
+    let pi0 = match pi0 {
+        Pi0Method::Smoother => {
+            // Centering `λ` keeps the normal equations well conditioned
+            let powers = |lambda: f64| [1., lambda - 0.5, (lambda - 0.5).powi(2)];
+            let mut gram = vec![vec![0.; 3]; 3];
+            let mut moments = vec![0.; 3];
+            for (&lambda, &pi0) in lambdas.iter().zip(&pi0_at) {
+                let x = powers(lambda);
+                for (i, row) in gram.iter_mut().enumerate() {
+                    for (j, entry) in row.iter_mut().enumerate() {
+                        *entry += x[i] * x[j];
+                    }
+                    moments[i] += x[i] * pi0;
+                }
+            }
+            let coefficients = solve_symmetric(&gram, &moments).unwrap();
+            let x = powers(*lambdas.last().unwrap());
+            x.iter().zip(&coefficients).map(|(x, c)| x * c).sum::<f64>()
+        }
+        Pi0Method::Bootstrap => {
+            let mut sorted = pi0_at.clone();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            // Linearly interpolated 10th percentile
+            let position = (sorted.len() - 1) as f64 * 0.1;
+            let lower = position as usize;
+            let min_pi0 =
+                sorted[lower] + (position - lower as f64) * (sorted[lower + 1] - sorted[lower]);
+            let mse = |(&lambda, &pi0): (&f64, &f64)| {
+                let w = above(lambda);
+                w / (m * (1. - lambda)).powi(2) * (1. - w / m) + (pi0 - min_pi0).powi(2)
+            };
+            let mut best = (f64::INFINITY, f64::INFINITY);
+            for (mse, pi0) in lambdas.iter().zip(&pi0_at).map(mse).zip(&pi0_at) {
+                if (mse, *pi0) < best {
+                    best = (mse, *pi0);
+                }
+            }
+            best.1
+        }
+    };
+    let pi0 = NormalizedF64::new(pi0.clamp(1. / m, 1.)).unwrap();
+    let q_values = adjust(p_values, Correction::BenjaminiHochberg)
+        .into_iter()
+        .map(|p| NormalizedF64::new(pi0.get() * p.get()).unwrap())
+        .collect();
+    QValues { pi0, q_values }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjust() {
+        let p_values = [0.01, 0.04, 0.03, 0.005, 0.2].map(|p| NormalizedF64::new(p).unwrap());
+        let adjusted = |correction| {
+            adjust(&p_values, correction)
+                .into_iter()
+                .map(|p| p.get())
+                .collect::<Vec<_>>()
+        };
+        let close = |a: Vec<f64>, b: [f64; 5]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-12);
+        assert!(close(
+            adjusted(Correction::Bonferroni),
+            [0.05, 0.2, 0.15, 0.025, 1.]
+        ));
+        assert!(close(
+            adjusted(Correction::Holm),
+            [0.04, 0.09, 0.09, 0.025, 0.2]
+        ));
+        assert!(close(
+            adjusted(Correction::BenjaminiHochberg),
+            [0.025, 0.05, 0.05, 0.025, 0.2]
+        ));
+    }
+
+    #[test]
+    fn test_combine() {
+        let p_values = [0.01, 0.2, 0.3].map(|p| NormalizedF64::new(p).unwrap());
+        let result = combine(&p_values, Combination::Fisher, None);
+        assert!((result.statistic.get() - 14.837_161_805).abs() < 1e-9);
+        assert!((result.p_value.get() - 0.021_561_751).abs() < 1e-9);
+        let result = combine(&p_values, Combination::Stouffer, None);
+        assert!((result.statistic.get() - 2.131_790_594).abs() < 1e-8);
+        assert!((result.p_value.get() - 0.016_512_033).abs() < 1e-9);
+        let result = combine(&p_values, Combination::HarmonicMean, None);
+        assert!((result.p_value.get() - 0.027_692_308).abs() < 1e-9);
+
+        let weights = [3., 1., 1.].map(|w| PositiveF64::new(w).unwrap());
+        let result = combine(&p_values, Combination::Stouffer, Some(&weights));
+        assert!((result.p_value.get() - 0.005_932_539).abs() < 1e-9);
+        let result = combine(&p_values, Combination::HarmonicMean, Some(&weights));
+        assert!((result.p_value.get() - 0.016_216_216).abs() < 1e-9);
+
+        let p_values = [0., 0.5, 1.].map(|p| NormalizedF64::new(p).unwrap());
+        for method in [
+            Combination::Fisher,
+            Combination::Stouffer,
+            Combination::HarmonicMean,
+        ] {
+            assert!(combine(&p_values, method, None).p_value.get() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_qvalues() {
+        let p_values = [
+            0.001, 0.004, 0.008, 0.012, 0.02, 0.03, 0.06, 0.11, 0.18, 0.25, //
+            0.33, 0.41, 0.47, 0.52, 0.58, 0.64, 0.71, 0.77, 0.85, 0.93,
+        ]
+        .map(|p| NormalizedF64::new(p).unwrap());
+        let q = qvalues(&p_values, Pi0Method::Bootstrap);
+        assert!((q.pi0.get() - 0.6875).abs() < 1e-12);
+        assert!((q.q_values[0].get() - 0.013_75).abs() < 1e-12);
+        assert!((q.q_values[4].get() - 0.055).abs() < 1e-12);
+        let q = qvalues(&p_values, Pi0Method::Smoother);
+        assert!((q.pi0.get() - 0.232_050_430_830).abs() < 1e-9);
+        assert!((q.q_values[3].get() - 0.013_923_025_850).abs() < 1e-9);
+
+        // The quadratic through these dips below zero at the largest `λ`
+        let p_values = [0.17, 0.37, 0.39, 0.44, 0.51, 0.52, 0.55, 0.67, 0.7, 0.78]
+            .map(|p| NormalizedF64::new(p).unwrap());
+        let q = qvalues(&p_values, Pi0Method::Smoother);
+        assert_eq!(q.pi0.get(), 0.1);
+        assert!(q.q_values.iter().all(|q| q.get() > 0.));
+    }
+}
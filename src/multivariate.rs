@@ -0,0 +1,439 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    distributions::chi_square::ChiSquare,
+    linear_algebra::{inverse_symmetric, ln_determinant, solve_symmetric, Matrix},
+    special::{betainc_regularized, gammainc_upper_regularized},
+    Alternative, DegreesOfFreedom, TestResult,
+};
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HotellingT2 {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub t_squared: PositiveF64,
+    /// F statistic scaled from `t_squared`
+    pub test: TestResult,
+}
+
+/// One-sample Hotelling's T² test of a mean vector.
+///
+/// Null hypothesis: the population mean is `mean_0`.
+///
+/// - `sample[i]`: observation `i`, a vector of `p` variables
+/// - `F = (n - p) / (p (n - 1)) T²` with `p` and `n - p` degrees of freedom
+pub fn hotelling_t2(sample: &[Vec<f64>], mean_0: &[f64]) -> HotellingT2 {
+    let n = sample.len();
+    let p = mean_0.len();
+    assert!(n > p);
+    let (mean, covariance) = mean_and_covariance(sample);
+    assert_eq!(mean.len(), p);
+    let difference = mean
+        .iter()
+        .zip(mean_0)
+        .map(|(a, b)| a - b)
+        .collect::<Vec<_>>();
+    let t_squared = n as f64 * quadratic_form_inverse(&covariance, &difference);
+    let f = (n - p) as f64 / (p * (n - 1)) as f64 * t_squared;
+    hotelling_result(t_squared, f, p, n - p)
+}
+
+/// Two-sample Hotelling's T² test of mean vectors, assuming a common covariance matrix.
+///
+/// Null hypothesis: both populations have the same mean vector.
+///
+/// - `sample_1[i]`: observation `i`, a vector of `p` variables
+/// - `F = (n_1 + n_2 - p - 1) / (p (n_1 + n_2 - 2)) T²` with `p` and `n_1 + n_2 - p - 1` degrees of freedom
+pub fn hotelling_t2_two_sample(sample_1: &[Vec<f64>], sample_2: &[Vec<f64>]) -> HotellingT2 {
+    let (n_1, n_2) = (sample_1.len(), sample_2.len());
+    let (mean_1, scatter_1) = mean_and_scatter(sample_1);
+    let (mean_2, scatter_2) = mean_and_scatter(sample_2);
+    let p = mean_1.len();
+    assert_eq!(mean_2.len(), p);
+    assert!(n_1 + n_2 > p + 1);
+    let pooled = scale(&add(&scatter_1, &scatter_2), 1. / (n_1 + n_2 - 2) as f64);
+    let difference = mean_1
+        .iter()
+        .zip(&mean_2)
+        .map(|(a, b)| a - b)
+        .collect::<Vec<_>>();
+    let (n_1, n_2) = (n_1 as f64, n_2 as f64);
+    let t_squared = n_1 * n_2 / (n_1 + n_2) * quadratic_form_inverse(&pooled, &difference);
+    let df_2 = sample_1.len() + sample_2.len() - p - 1;
+    let f = df_2 as f64 / (p as f64 * (n_1 + n_2 - 2.)) * t_squared;
+    hotelling_result(t_squared, f, p, df_2)
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Manova {
+    /// `det(E) / det(E + H)` for the within-group scatter `E` and the between-group scatter `H`; small values favor group differences
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub wilks_lambda: NormalizedF64,
+    /// Rao's F approximation of `wilks_lambda`
+    pub wilks: TestResult,
+    /// `tr(H (E + H)^-1)`, the most robust of the statistics to unequal covariance matrices
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub pillai_trace: PositiveF64,
+    /// F approximation of `pillai_trace`
+    pub pillai: TestResult,
+}
+
+/// One-way MANOVA, the [`crate::numerical::anova`] of vector-valued outcomes.
+///
+/// Null hypothesis: all groups have the same mean vector.
+///
+/// - `groups[g][i]`: observation `i` of group `g`, a vector of `p` variables
+/// - Assumes multivariate normal outcomes with a common covariance matrix; see [`box_m`].
+pub fn manova(groups: &[&[Vec<f64>]]) -> Manova {
+    let k = groups.len();
+    assert!(k >= 2);
+    let summaries = groups
+        .iter()
+        .map(|group| mean_and_scatter(group))
+        .collect::<Vec<_>>();
+    let p = summaries[0].0.len();
+    assert!(summaries.iter().all(|(mean, _)| mean.len() == p));
+    let n = groups.iter().map(|group| group.len()).sum::<usize>();
+    assert!(n >= k + p);
+
+    let grand_mean = (0..p)
+        .map(|j| {
+            groups
+                .iter()
+                .zip(&summaries)
+                .map(|(group, (mean, _))| group.len() as f64 * mean[j])
+                .sum::<f64>()
+                / n as f64
+        })
+        .collect::<Vec<_>>();
+    let mut within = vec![vec![0.; p]; p];
+    let mut between = vec![vec![0.; p]; p];
+    for (group, (mean, scatter)) in groups.iter().zip(&summaries) {
+        within = add(&within, scatter);
+        let size = group.len() as f64;
+        for a in 0..p {
+            for b in 0..p {
+                between[a][b] += size * (mean[a] - grand_mean[a]) * (mean[b] - grand_mean[b]);
+            }
+        }
+    }
+    let total = add(&within, &between);
+    let singular = "the within-group scatter matrix should be positive definite";
+
+    let ln_lambda =
+        ln_determinant(&within).expect(singular) - ln_determinant(&total).expect(singular);
+    let lambda = ln_lambda.exp();
+    let (p_f64, q, df_error) = (p as f64, (k - 1) as f64, (n - k) as f64);
+    let t = if p_f64.powi(2) + q.powi(2) - 5. > 0. {
+        ((p_f64.powi(2) * q.powi(2) - 4.) / (p_f64.powi(2) + q.powi(2) - 5.)).sqrt()
+    } else {
+        1.
+    };
+    let w = df_error + q - (p_f64 + q + 1.) / 2.;
+    let (df_1, df_2) = (p_f64 * q, w * t - (p_f64 * q - 2.) / 2.);
+    let root = (ln_lambda / t).exp();
+    let wilks = f_test((1. - root) / root * df_2 / df_1, df_1, df_2);
+
+    let total_inverse = inverse_symmetric(&total).expect(singular);
+    let trace = (0..p)
+        .map(|i| {
+            (0..p)
+                .map(|j| between[i][j] * total_inverse[j][i])
+                .sum::<f64>()
+        })
+        .sum::<f64>();
+    let s = p_f64.min(q);
+    let m = ((p_f64 - q).abs() - 1.) / 2.;
+    let n_term = (df_error - p_f64 - 1.) / 2.;
+    let (df_1, df_2) = (s * (2. * m + s + 1.), s * (2. * n_term + s + 1.));
+    let pillai = f_test(
+        (2. * n_term + s + 1.) / (2. * m + s + 1.) * trace / (s - trace),
+        df_1,
+        df_2,
+    );
+
+    Manova {
+        wilks_lambda: NormalizedF64::new(lambda.clamp(0., 1.)).unwrap(),
+        wilks,
+        pillai_trace: PositiveF64::new(trace).unwrap(),
+        pillai,
+    }
+}
+
+/// Box's M test of equal covariance matrices, the assumption of [`manova`] and [`hotelling_t2_two_sample`].
+///
+/// Null hypothesis: all groups share the same covariance matrix.
+///
+/// - `groups[g][i]`: observation `i` of group `g`, a vector of `p` variables
+/// - The statistic is `M (1 - c)`, chi-square with `p (p + 1) (k - 1) / 2` degrees of freedom for `k` groups.
+/// - Sensitive to departures from normality, so it is usually judged at a small significance level such as 0.001.
+pub fn box_m(groups: &[&[Vec<f64>]]) -> TestResult {
+    let k = groups.len();
+    assert!(k >= 2);
+    let scatters = groups
+        .iter()
+        .map(|group| mean_and_scatter(group).1)
+        .collect::<Vec<_>>();
+    let p = scatters[0].len();
+    assert!(scatters.iter().all(|scatter| scatter.len() == p));
+    assert!(groups.iter().all(|group| group.len() > p));
+    let n = groups.iter().map(|group| group.len()).sum::<usize>();
+
+    let singular = "every covariance matrix should be positive definite";
+    let mut pooled = vec![vec![0.; p]; p];
+    let mut sum_of_ln_determinants = 0.;
+    let mut sum_of_reciprocals = 0.;
+    for (group, scatter) in groups.iter().zip(&scatters) {
+        let df = (group.len() - 1) as f64;
+        pooled = add(&pooled, scatter);
+        sum_of_ln_determinants += df * ln_determinant(&scale(scatter, 1. / df)).expect(singular);
+        sum_of_reciprocals += 1. / df;
+    }
+    let df_pooled = (n - k) as f64;
+    let pooled = scale(&pooled, 1. / df_pooled);
+    let m = df_pooled * ln_determinant(&pooled).expect(singular) - sum_of_ln_determinants;
+
+    let (p_f64, k_f64) = (p as f64, k as f64);
+    let c = (sum_of_reciprocals - 1. / df_pooled) * (2. * p_f64.powi(2) + 3. * p_f64 - 1.)
+        / (6. * (p_f64 + 1.) * (k_f64 - 1.));
+    let chi_square = (m * (1. - c)).max(0.);
+    let df = p * (p + 1) * (k - 1) / 2;
+    let p_value = gammainc_upper_regularized(df as f64 / 2., chi_square / 2.);
+    TestResult {
+        statistic: FiniteF64::new(chi_square).unwrap(),
+        df: Some(DegreesOfFreedom::one(df)),
+        p_value: NormalizedF64::new(p_value).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// Mahalanobis distance of `point` from the mean of `data`, scaled by the sample covariance matrix of `data`
+///
+/// - `data[i]`: observation `i`, a vector of `p` variables
+pub fn mahalanobis(data: &[Vec<f64>], point: &[f64]) -> PositiveF64 {
+    let (mean, covariance) = mean_and_covariance(data);
+    assert_eq!(point.len(), mean.len());
+    let difference = point
+        .iter()
+        .zip(&mean)
+        .map(|(a, b)| a - b)
+        .collect::<Vec<_>>();
+    PositiveF64::new(quadratic_form_inverse(&covariance, &difference).sqrt()).unwrap()
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MahalanobisOutliers {
+    /// Squared [`mahalanobis`] distance of each observation from the mean of all of them
+    pub squared_distances: Vec<f64>,
+    /// The chi-square quantile beyond which an observation is an outlier
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub critical_value: PositiveF64,
+}
+impl MahalanobisOutliers {
+    pub fn outlier_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.squared_distances
+            .iter()
+            .enumerate()
+            .filter(|(_, &d)| d > self.critical_value.get())
+            .map(|(i, _)| i)
+    }
+}
+
+/// Flag observations whose squared Mahalanobis distance exceeds the `1 - alpha` quantile of chi-square with `p` degrees of freedom, its distribution for multivariate normal data.
+///
+/// - `data[i]`: observation `i`, a vector of `p` variables
+/// - `alpha`: greater than 0, since no finite distance reaches the quantile at 1
+/// - The outliers themselves inflate the sample covariance matrix, which can mask them in small samples.
+pub fn mahalanobis_outliers(data: &[Vec<f64>], alpha: NormalizedF64) -> MahalanobisOutliers {
+    assert!(alpha.get() > 0., "alpha should be greater than 0");
+    let (mean, covariance) = mean_and_covariance(data);
+    let inverse =
+        inverse_symmetric(&covariance).expect("the covariance matrix should be positive definite");
+    let squared_distances = data
+        .iter()
+        .map(|observation| {
+            let difference = observation
+                .iter()
+                .zip(&mean)
+                .map(|(a, b)| a - b)
+                .collect::<Vec<_>>();
+            (0..mean.len())
+                .flat_map(|a| (0..mean.len()).map(move |b| (a, b)))
+                .map(|(a, b)| difference[a] * inverse[a][b] * difference[b])
+                .sum()
+        })
+        .collect();
+    let chi_square = ChiSquare {
+        df: PositiveF64::new(mean.len() as f64).unwrap(),
+    };
+    let critical_value = chi_square.quantile(NormalizedF64::new(1. - alpha.get()).unwrap());
+    MahalanobisOutliers {
+        squared_distances,
+        critical_value,
+    }
+}
+
+/// Mean vector and the sample covariance matrix, dividing by `n - 1`
+fn mean_and_covariance(data: &[Vec<f64>]) -> (Vec<f64>, Matrix) {
+    assert!(data.len() >= 2);
+    let (mean, scatter) = mean_and_scatter(data);
+    (mean, scale(&scatter, 1. / (data.len() - 1) as f64))
+}
+
+fn hotelling_result(t_squared: f64, f: f64, df_1: usize, df_2: usize) -> HotellingT2 {
+    HotellingT2 {
+        t_squared: PositiveF64::new(t_squared).unwrap(),
+        test: f_test(f, df_1 as f64, df_2 as f64),
+    }
+}
+
+/// `F` with `df_1` and `df_2` degrees of freedom, which need not be whole numbers
+fn f_test(f: f64, df_1: f64, df_2: f64) -> TestResult {
+    // `1 - I_y(df_1 / 2, df_2 / 2) = I_{1 - y}(df_2 / 2, df_1 / 2)` keeps small p-values accurate
+    let p_value = betainc_regularized(df_2 / 2., df_1 / 2., df_2 / (df_1 * f + df_2));
+    TestResult {
+        statistic: FiniteF64::new(f).unwrap(),
+        df: Some(DegreesOfFreedom::Two(
+            PositiveF64::new(df_1).unwrap(),
+            PositiveF64::new(df_2).unwrap(),
+        )),
+        p_value: NormalizedF64::new(p_value.clamp(0., 1.)).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// Mean vector and the sum of squares and cross products about it
+fn mean_and_scatter(sample: &[Vec<f64>]) -> (Vec<f64>, Matrix) {
+    assert!(!sample.is_empty());
+    let p = sample[0].len();
+    assert!(p >= 1);
+    assert!(sample.iter().all(|observation| observation.len() == p));
+    let n = sample.len() as f64;
+    let mean = (0..p)
+        .map(|j| sample.iter().map(|observation| observation[j]).sum::<f64>() / n)
+        .collect::<Vec<_>>();
+    let mut scatter = vec![vec![0.; p]; p];
+    for observation in sample {
+        for a in 0..p {
+            for b in 0..p {
+                scatter[a][b] += (observation[a] - mean[a]) * (observation[b] - mean[b]);
+            }
+        }
+    }
+    (mean, scatter)
+}
+
+/// `v^T a^-1 v` for a symmetric positive definite `a`
+fn quadratic_form_inverse(a: &[Vec<f64>], v: &[f64]) -> f64 {
+    let solution =
+        solve_symmetric(a, v).expect("the covariance matrix should be positive definite");
+    v.iter().zip(&solution).map(|(v, x)| v * x).sum()
+}
+
+fn add(a: &[Vec<f64>], b: &[Vec<f64>]) -> Matrix {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| a.iter().zip(b).map(|(a, b)| a + b).collect())
+        .collect()
+}
+
+fn scale(a: &[Vec<f64>], factor: f64) -> Matrix {
+    a.iter()
+        .map(|row| row.iter().map(|x| x * factor).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(points: &[[f64; 2]]) -> Vec<Vec<f64>> {
+        points.iter().map(|point| point.to_vec()).collect()
+    }
+
+    #[test]
+    fn test_hotelling_t2() {
+        let x = sample(&[
+            [2.0, 3.1],
+            [2.5, 2.9],
+            [1.8, 3.5],
+            [2.9, 3.8],
+            [2.2, 2.7],
+            [3.1, 3.3],
+            [2.4, 3.0],
+            [2.7, 3.6],
+        ]);
+        let y = sample(&[
+            [2.9, 3.0],
+            [3.4, 3.1],
+            [3.1, 2.6],
+            [3.8, 3.3],
+            [3.0, 2.8],
+            [3.6, 3.5],
+            [3.3, 2.9],
+        ]);
+        let result = hotelling_t2(&x, &[2., 3.]);
+        assert!((result.t_squared.get() - 8.924_891_909).abs() < 1e-8);
+        assert!((result.test.statistic.get() - 3.824_953_675).abs() < 1e-8);
+        assert!((result.test.p_value.get() - 0.084_930_688).abs() < 1e-8);
+
+        let result = hotelling_t2_two_sample(&x, &y);
+        assert!((result.t_squared.get() - 30.537_146_844).abs() < 1e-8);
+        assert!((result.test.statistic.get() - 14.094_067_774).abs() < 1e-8);
+        assert!((result.test.p_value.get() - 0.000_708_762).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_manova() {
+        let a = sample(&[[2.0, 3.1], [2.5, 2.9], [1.8, 3.5], [2.9, 3.8], [2.2, 2.7]]);
+        let b = sample(&[
+            [2.9, 3.0],
+            [3.4, 3.1],
+            [3.1, 2.6],
+            [3.8, 3.3],
+            [3.0, 2.8],
+            [3.6, 3.5],
+        ]);
+        let c = sample(&[[2.4, 3.9], [2.8, 4.1], [2.2, 3.6], [2.6, 4.4], [3.0, 3.8]]);
+        let result = manova(&[&a, &b, &c]);
+        assert!((result.wilks_lambda.get() - 0.119_625_914).abs() < 1e-9);
+        assert!((result.wilks.statistic.get() - 11.347_568_676).abs() < 1e-8);
+        assert!((result.wilks.p_value.get() - 2.593_423_362e-5).abs() < 1e-12);
+        assert!((result.pillai_trace.get() - 1.195_445_557).abs() < 1e-9);
+        assert!((result.pillai.statistic.get() - 9.658_011_563).abs() < 1e-8);
+        assert!((result.pillai.p_value.get() - 6.336_507_533e-5).abs() < 1e-12);
+
+        let test = box_m(&[&a, &b, &c]);
+        assert!((test.statistic.get() - 2.572_262_751).abs() < 1e-9);
+        assert!((test.p_value.get() - 0.860_294_269).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mahalanobis() {
+        let data = sample(&[
+            [2.0, 3.1],
+            [2.5, 2.9],
+            [1.8, 3.5],
+            [2.9, 3.8],
+            [2.2, 2.7],
+            [3.1, 3.3],
+            [2.4, 3.0],
+            [2.7, 3.6],
+            [4.5, 2.0],
+        ]);
+        assert!((mahalanobis(&data, &[3., 3.]).get() - 0.404_553_405).abs() < 1e-9);
+
+        let outliers = mahalanobis_outliers(&data, NormalizedF64::new(0.05).unwrap());
+        assert!((outliers.critical_value.get() - 5.991_464_547).abs() < 1e-6);
+        assert!((outliers.squared_distances[8] - 6.112_908_113).abs() < 1e-9);
+        assert_eq!(outliers.outlier_indices().collect::<Vec<_>>(), [8]);
+        let last = mahalanobis(&data, &data[8]).get();
+        assert!((last.powi(2) - outliers.squared_distances[8]).abs() < 1e-9);
+    }
+}
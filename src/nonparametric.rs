@@ -0,0 +1,836 @@
+use std::num::NonZeroUsize;
+
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    correlation::{for_each_permutation, ranks, tie_group_sizes},
+    distributions::{
+        f::{FParams, F_CDF},
+        t::StudentT,
+    },
+    multiple_testing::{adjust, Correction},
+    special::{gammainc_upper_regularized, normal_cdf},
+    Alternative, DegreesOfFreedom, TestResult,
+};
+
+/// Wald–Wolfowitz runs test.
+///
+/// Null hypothesis: the order of the two kinds of values is random.
+///
+/// - The statistic is `z` of the count of runs, from the normal approximation.
+/// - Too few runs suggest clustering; too many suggest alternation.
+pub fn runs_test(sequence: &[bool]) -> TestResult {
+    let n_1 = sequence.iter().filter(|&&x| x).count() as f64;
+    let n_2 = sequence.len() as f64 - n_1;
+    assert!(n_1 > 0.);
+    assert!(n_2 > 0.);
+    let n = n_1 + n_2;
+    let runs = 1 + sequence.windows(2).filter(|w| w[0] != w[1]).count();
+
+    let mean = 2. * n_1 * n_2 / n + 1.;
+    let variance = 2. * n_1 * n_2 * (2. * n_1 * n_2 - n) / (n.powi(2) * (n - 1.));
+    let z = (runs as f64 - mean) / variance.sqrt();
+    TestResult {
+        statistic: FiniteF64::new(z).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(2. * normal_cdf(-z.abs())).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// [`runs_test`] of whether each value is above the median, dropping values equal to the median
+pub fn runs_test_median(data: &[f64]) -> TestResult {
+    assert!(!data.is_empty());
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let middle = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] + sorted[middle]) / 2.
+    } else {
+        sorted[middle]
+    };
+    let sequence = data
+        .iter()
+        .filter(|&&x| x != median)
+        .map(|&x| x > median)
+        .collect::<Vec<_>>();
+    runs_test(&sequence)
+}
+
+/// Kendall's coefficient of concordance `W` among judges ranking the same items.
+///
+/// Null hypothesis: the judges rank the items independently of each other.
+///
+/// - `rankings[i][j]`: score of item `j` by judge `i`; scores are ranked within each judge, with the correction for ties
+/// - The statistic is `m (n - 1) W`, chi-square with `n - 1` degrees of freedom for `m` judges and `n` items.
+/// - The effect size is `W`, from 0 for no agreement to 1 for identical rankings.
+pub fn kendalls_w(rankings: &[Vec<f64>]) -> TestResult {
+    let m = rankings.len();
+    assert!(m >= 2);
+    let n = rankings[0].len();
+    assert!(n >= 2);
+    assert!(rankings.iter().all(|scores| scores.len() == n));
+
+    let mut rank_sums = vec![0.; n];
+    let mut ties = 0.;
+    for scores in rankings {
+        ranks(scores)
+            .iter()
+            .zip(&mut rank_sums)
+            .for_each(|(rank, sum)| *sum += rank);
+        let mut sorted = scores.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        ties += tie_group_sizes(sorted.iter())
+            .into_iter()
+            .map(|t| (t.pow(3) - t) as f64)
+            .sum::<f64>();
+    }
+    let (m, n) = (m as f64, n as f64);
+    let mean_rank_sum = m * (n + 1.) / 2.;
+    let s = rank_sums
+        .iter()
+        .map(|sum| (sum - mean_rank_sum).powi(2))
+        .sum::<f64>();
+    let denominator = m.powi(2) * (n.powi(3) - n) - m * ties;
+    assert!(denominator > 0.);
+    let w = 12. * s / denominator;
+
+    let chi_square = m * (n - 1.) * w;
+    let df = n as usize - 1;
+    let p_value = gammainc_upper_regularized(df as f64 / 2., chi_square / 2.);
+    TestResult {
+        statistic: FiniteF64::new(chi_square).unwrap(),
+        df: Some(DegreesOfFreedom::one(df)),
+        p_value: NormalizedF64::new(p_value).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: Some(FiniteF64::new(w).unwrap()),
+        confidence_interval: None,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DunnComparison {
+    /// Indices of the two compared groups
+    pub groups: (usize, usize),
+    /// `z` of the difference of the mean ranks, with the unadjusted two-sided p-value
+    pub test: TestResult,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub adjusted_p_value: NormalizedF64,
+}
+
+/// Dunn's test comparing every pair of groups after [`crate::numerical::kruskal_wallis`].
+///
+/// Null hypothesis of each comparison: the two groups have the same mean rank among all the groups.
+///
+/// - Ranks are shared across all groups, with the correction for ties.
+/// - `correction` adjusts the p-values over all `k (k - 1) / 2` comparisons.
+/// - Two groups with the same mean rank give `z = 0` and a p-value of `1`, even if every value is tied.
+pub fn dunn(groups: &[&[f64]], correction: Correction) -> Vec<DunnComparison> {
+    assert!(groups.len() >= 2);
+    assert!(groups.iter().all(|group| !group.is_empty()));
+    let values = groups.concat();
+    let ranks = ranks(&values);
+    let n = values.len() as f64;
+
+    let mut offset = 0;
+    let mut mean_ranks = vec![];
+    for group in groups {
+        let rank_sum = ranks[offset..offset + group.len()].iter().sum::<f64>();
+        mean_ranks.push(rank_sum / group.len() as f64);
+        offset += group.len();
+    }
+    let mut sorted = values;
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let ties = tie_group_sizes(sorted.iter())
+        .into_iter()
+        .map(|t| (t.pow(3) - t) as f64)
+        .sum::<f64>();
+    let rank_variance = n * (n + 1.) / 12. - ties / (12. * (n - 1.));
+
+    let mut comparisons = vec![];
+    for i in 0..groups.len() {
+        for j in i + 1..groups.len() {
+            let standard_error = (rank_variance
+                * (1. / groups[i].len() as f64 + 1. / groups[j].len() as f64))
+                .sqrt();
+            let difference = mean_ranks[i] - mean_ranks[j];
+            // all tied values leave no rank variance
+            let z = if difference == 0. {
+                0.
+            } else {
+                difference / standard_error
+            };
+            let test = TestResult {
+                statistic: FiniteF64::new(z).unwrap(),
+                df: None,
+                p_value: NormalizedF64::new(2. * normal_cdf(-z.abs())).unwrap(),
+                alternative: Alternative::TwoSided,
+                effect_size: None,
+                confidence_interval: None,
+            };
+            comparisons.push(((i, j), test));
+        }
+    }
+    let p_values = comparisons
+        .iter()
+        .map(|(_, test)| test.p_value)
+        .collect::<Vec<_>>();
+    comparisons
+        .into_iter()
+        .zip(adjust(&p_values, correction))
+        .map(|((groups, test), adjusted_p_value)| DunnComparison {
+            groups,
+            test,
+            adjusted_p_value,
+        })
+        .collect()
+}
+
+/// Brunner–Munzel test of stochastic equality, without assuming equal variances or shapes.
+///
+/// Null hypothesis: `P(X < Y) + P(X = Y) / 2 = 1 / 2`.
+///
+/// - The p-value is from the t distribution with Welch–Satterthwaite-like degrees of freedom.
+/// - The effect size is the estimate of `P(X < Y) + P(X = Y) / 2`.
+/// - If the placements do not vary, e.g. when the samples do not overlap, there are no degrees of freedom and the p-value is `0`, or `1` if the effect size is `1 / 2`.
+pub fn brunner_munzel(x: &[f64], y: &[f64]) -> TestResult {
+    let statistic = BrunnerMunzel::new(x, y);
+    let (df, p_value) = match PositiveF64::new(statistic.df) {
+        Some(df) if statistic.w.is_finite() => {
+            let tail = StudentT { df }
+                .cdf(FiniteF64::new(-statistic.w.abs()).unwrap())
+                .get();
+            (Some(DegreesOfFreedom::One(df)), (2. * tail).min(1.))
+        }
+        _ => (None, if statistic.w == 0. { 1. } else { 0. }),
+    };
+    TestResult {
+        statistic: FiniteF64::new(statistic.w.clamp(f64::MIN, f64::MAX)).unwrap(),
+        df,
+        p_value: NormalizedF64::new(p_value).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: Some(FiniteF64::new(statistic.effect).unwrap()),
+        confidence_interval: None,
+    }
+}
+
+/// [`brunner_munzel`] with the p-value from `n_perms` permutations of the studentized statistic, for small samples
+#[cfg(feature = "rand")]
+pub fn brunner_munzel_permutation(x: &[f64], y: &[f64], n_perms: usize, seed: u64) -> TestResult {
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    let mut result = brunner_munzel(x, y);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut pooled = [x, y].concat();
+    let p_value = permutation_p_value(result.statistic.get(), n_perms, || {
+        pooled.shuffle(&mut rng);
+        let (x, y) = pooled.split_at(x.len());
+        BrunnerMunzel::new(x, y).w
+    });
+    result.p_value = NormalizedF64::new(p_value).unwrap();
+    result
+}
+
+struct BrunnerMunzel {
+    /// Infinite if the placements do not vary but the effect is not `1 / 2`, and zero if it is
+    w: f64,
+    /// Not a number if the placements do not vary
+    df: f64,
+    effect: f64,
+}
+impl BrunnerMunzel {
+    fn new(x: &[f64], y: &[f64]) -> Self {
+        assert!(x.len() >= 2);
+        assert!(y.len() >= 2);
+        let (n_x, n_y) = (x.len() as f64, y.len() as f64);
+        let pooled_ranks = ranks(&[x, y].concat());
+        let (pooled_x, pooled_y) = pooled_ranks.split_at(x.len());
+        // Variance of the placements, the ranks among the other sample
+        let placement_variance = |pooled: &[f64], within: &[f64]| {
+            let n = pooled.len() as f64;
+            let mean = pooled.iter().sum::<f64>() / n;
+            let sum_of_squares = pooled
+                .iter()
+                .zip(within)
+                .map(|(r, w)| (r - w - mean + (n + 1.) / 2.).powi(2))
+                .sum::<f64>();
+            (mean, sum_of_squares / (n - 1.))
+        };
+        let (mean_x, variance_x) = placement_variance(pooled_x, &ranks(x));
+        let (mean_y, variance_y) = placement_variance(pooled_y, &ranks(y));
+        let (scaled_x, scaled_y) = (n_x * variance_x, n_y * variance_y);
+
+        let difference = mean_y - mean_x;
+        let w = if scaled_x + scaled_y == 0. && difference == 0. {
+            0.
+        } else {
+            n_x * n_y * difference / ((n_x + n_y) * (scaled_x + scaled_y).sqrt())
+        };
+        let df = (scaled_x + scaled_y).powi(2)
+            / (scaled_x.powi(2) / (n_x - 1.) + scaled_y.powi(2) / (n_y - 1.));
+        let effect = (mean_y - (n_y + 1.) / 2.) / n_x;
+        Self { w, df, effect }
+    }
+}
+
+/// Quade test of treatments measured in each block, weighting each block by the rank of its range.
+///
+/// Null hypothesis: every treatment has the same effect.
+///
+/// - `blocks[i][j]`: measurement of treatment `j` in block `i`; measurements are ranked within each block
+/// - The statistic is F with `k - 1` and `(b - 1)(k - 1)` degrees of freedom for `b` blocks and `k` treatments.
+/// - More powerful than Friedman's test for few treatments.
+/// - If every block ranks the treatments alike, the statistic is `f64::MAX` with a p-value of zero.
+pub fn quade(blocks: &[Vec<f64>]) -> TestResult {
+    let b = blocks.len();
+    assert!(b >= 2);
+    let k = blocks[0].len();
+    assert!(k >= 2);
+    assert!(blocks.iter().all(|block| block.len() == k));
+
+    let range = |block: &[f64]| {
+        let max = block.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min = block.iter().copied().fold(f64::INFINITY, f64::min);
+        max - min
+    };
+    let ranges = blocks.iter().map(|block| range(block)).collect::<Vec<_>>();
+    let block_weights = ranks(&ranges);
+    let mean_rank = (k as f64 + 1.) / 2.;
+    let mut a = 0.;
+    let mut treatment_sums = vec![0.; k];
+    for (block, weight) in blocks.iter().zip(&block_weights) {
+        for (rank, sum) in ranks(block).iter().zip(&mut treatment_sums) {
+            let score = weight * (rank - mean_rank);
+            a += score.powi(2);
+            *sum += score;
+        }
+    }
+    let sum_of_squares = treatment_sums.iter().map(|s| s.powi(2)).sum::<f64>();
+    let between = sum_of_squares / b as f64;
+    let df_1 = NonZeroUsize::new(k - 1).unwrap();
+    let df_2 = NonZeroUsize::new((b - 1) * (k - 1)).unwrap();
+    // The scores are multiples of 1/4, so these sums are exact
+    let (f, p_value) = if a == 0. {
+        (0., NormalizedF64::new(1.).unwrap())
+    } else if b as f64 * a <= sum_of_squares {
+        (f64::MAX, NormalizedF64::new(0.).unwrap())
+    } else {
+        let f = (b - 1) as f64 * between / (a - between);
+        let f_params = FParams {
+            x: PositiveF64::new(f).unwrap(),
+            df_1,
+            df_2,
+        };
+        (f, F_CDF.p_value(f_params))
+    };
+    TestResult {
+        statistic: FiniteF64::new(f).unwrap(),
+        df: Some(DegreesOfFreedom::Two(
+            PositiveF64::new(df_1.get() as f64).unwrap(),
+            PositiveF64::new(df_2.get() as f64).unwrap(),
+        )),
+        p_value,
+        alternative: Alternative::Greater,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// How to compute the two-sided p-value of a rank statistic against an ordered alternative
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrendPValue {
+    /// Normal approximation, with the correction for ties
+    Normal,
+    /// Exact null distribution, which assumes no ties
+    ///
+    /// - Only for small samples.
+    Exact,
+    /// Share of `n_perms` relabelings under the null hypothesis with a statistic at least as far from its mean as the observed one
+    ///
+    /// - Valid with ties.
+    /// - Panics without the `rand` feature.
+    Permutation { n_perms: usize, seed: u64 },
+}
+
+/// Jonckheere–Terpstra test of a monotone trend across ordered groups.
+///
+/// Null hypothesis: all groups come from the same distribution, against the alternative that they are stochastically ordered.
+///
+/// - The statistic is `J`, the count over pairs of groups `i < j` of pairs of values with the one from group `i` smaller, ties counting half.
+/// - The p-value is two-sided.
+pub fn jonckheere_terpstra(ordered_groups: &[&[f64]], p_value_method: TrendPValue) -> TestResult {
+    assert!(ordered_groups.len() >= 2);
+    assert!(ordered_groups.iter().all(|group| !group.is_empty()));
+    let j = jonckheere_statistic(ordered_groups);
+    let sizes = ordered_groups
+        .iter()
+        .map(|group| group.len())
+        .collect::<Vec<_>>();
+    let mut pooled = ordered_groups.concat();
+    let n = pooled.len() as f64;
+    let mean = (n.powi(2) - sizes.iter().map(|&n| (n as f64).powi(2)).sum::<f64>()) / 4.;
+
+    let p_value = match p_value_method {
+        TrendPValue::Normal => {
+            pooled.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let ties = tie_group_sizes(pooled.iter())
+                .into_iter()
+                .map(|t| t as f64)
+                .collect::<Vec<_>>();
+            let sizes = sizes.iter().map(|&n| n as f64).collect::<Vec<_>>();
+            let sum = |values: &[f64], f: fn(f64) -> f64| values.iter().map(|&x| f(x)).sum::<f64>();
+            let variance = (n * (n - 1.) * (2. * n + 5.)
+                - sum(&sizes, |n| n * (n - 1.) * (2. * n + 5.))
+                - sum(&ties, |t| t * (t - 1.) * (2. * t + 5.)))
+                / 72.
+                + sum(&sizes, |n| n * (n - 1.) * (n - 2.))
+                    * sum(&ties, |t| t * (t - 1.) * (t - 2.))
+                    / (36. * n * (n - 1.) * (n - 2.))
+                + sum(&sizes, |n| n * (n - 1.)) * sum(&ties, |t| t * (t - 1.))
+                    / (8. * n * (n - 1.));
+            let z = (j - mean) / variance.sqrt();
+            2. * normal_cdf(-z.abs())
+        }
+        TrendPValue::Exact => {
+            assert_no_ties(&pooled);
+            // `J` sums independent Mann–Whitney counts of each group against all the groups before it
+            let mut distribution = vec![1.];
+            let mut before = 0;
+            for &size in &sizes {
+                distribution = convolve(&distribution, &mann_whitney_distribution(before, size));
+                before += size;
+            }
+            exact_two_sided_p_value(&distribution, j)
+        }
+        #[cfg(not(feature = "rand"))]
+        TrendPValue::Permutation { .. } => {
+            panic!("permutation p-values require the `rand` feature")
+        }
+        #[cfg(feature = "rand")]
+        TrendPValue::Permutation { n_perms, seed } => {
+            use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+            let mut rng = StdRng::seed_from_u64(seed);
+            permutation_p_value(j - mean, n_perms, || {
+                pooled.shuffle(&mut rng);
+                let mut groups = vec![];
+                let mut rest = pooled.as_slice();
+                for &size in &sizes {
+                    let (group, remaining) = rest.split_at(size);
+                    groups.push(group);
+                    rest = remaining;
+                }
+                jonckheere_statistic(&groups) - mean
+            })
+        }
+    };
+    TestResult {
+        statistic: FiniteF64::new(j).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(p_value.min(1.)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// Page's test of a predicted order of treatments measured in each block, the ordered alternative to Friedman's test.
+///
+/// Null hypothesis: within each block, every ordering of the treatments is equally likely.
+///
+/// - `blocks[i][j]`: measurement of treatment `j` in block `i`; measurements are ranked within each block
+/// - `predicted_order`: the treatments from the one predicted smallest to the one predicted largest
+/// - The statistic is `L`, the sum over treatments of the predicted rank times the rank sum.
+/// - The p-value is two-sided.
+pub fn page_trend(
+    blocks: &[Vec<f64>],
+    predicted_order: &[usize],
+    p_value_method: TrendPValue,
+) -> TestResult {
+    let k = predicted_order.len();
+    assert!(k >= 2);
+    assert!(!blocks.is_empty());
+    assert!(blocks.iter().all(|block| block.len() == k));
+    let mut predicted_ranks = vec![0.; k];
+    for (position, &treatment) in predicted_order.iter().enumerate() {
+        assert_eq!(
+            predicted_ranks[treatment], 0.,
+            "`predicted_order` should be a permutation"
+        );
+        predicted_ranks[treatment] = (position + 1) as f64;
+    }
+    let block_ranks = blocks.iter().map(|block| ranks(block)).collect::<Vec<_>>();
+    let statistic = |block_ranks: &[Vec<f64>]| {
+        block_ranks
+            .iter()
+            .flat_map(|ranks| ranks.iter().zip(&predicted_ranks).map(|(r, c)| r * c))
+            .sum::<f64>()
+    };
+    let l = statistic(&block_ranks);
+    let (m, k_f64) = (blocks.len() as f64, k as f64);
+    let mean = m * k_f64 * (k_f64 + 1.).powi(2) / 4.;
+
+    let p_value = match p_value_method {
+        TrendPValue::Normal => {
+            let mean_rank = (k_f64 + 1.) / 2.;
+            let sum_of_squares =
+                |values: &[f64]| values.iter().map(|x| (x - mean_rank).powi(2)).sum::<f64>();
+            let variance = block_ranks
+                .iter()
+                .map(|ranks| {
+                    sum_of_squares(&predicted_ranks) * sum_of_squares(ranks) / (k_f64 - 1.)
+                })
+                .sum::<f64>();
+            let z = (l - mean) / variance.sqrt();
+            2. * normal_cdf(-z.abs())
+        }
+        TrendPValue::Exact => {
+            blocks.iter().for_each(|block| assert_no_ties(block));
+            let max = predicted_ranks.iter().map(|c| c * c).sum::<f64>() as usize;
+            let mut block_distribution = vec![0.; max + 1];
+            let mut ranks = (1..=k).map(|r| r as f64).collect::<Vec<_>>();
+            for_each_permutation(&mut ranks, &mut |ranks| {
+                let l = ranks
+                    .iter()
+                    .zip(&predicted_ranks)
+                    .map(|(r, c)| r * c)
+                    .sum::<f64>();
+                block_distribution[l as usize] += 1.;
+            });
+            let total = block_distribution.iter().sum::<f64>();
+            block_distribution.iter_mut().for_each(|p| *p /= total);
+            let distribution = blocks.iter().fold(vec![1.], |distribution, _| {
+                convolve(&distribution, &block_distribution)
+            });
+            exact_two_sided_p_value(&distribution, l)
+        }
+        #[cfg(not(feature = "rand"))]
+        TrendPValue::Permutation { .. } => {
+            panic!("permutation p-values require the `rand` feature")
+        }
+        #[cfg(feature = "rand")]
+        TrendPValue::Permutation { n_perms, seed } => {
+            use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut block_ranks = block_ranks.clone();
+            permutation_p_value(l - mean, n_perms, || {
+                block_ranks
+                    .iter_mut()
+                    .for_each(|ranks| ranks.shuffle(&mut rng));
+                statistic(&block_ranks) - mean
+            })
+        }
+    };
+    TestResult {
+        statistic: FiniteF64::new(l).unwrap(),
+        df: None,
+        p_value: NormalizedF64::new(p_value.min(1.)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+fn jonckheere_statistic(ordered_groups: &[&[f64]]) -> f64 {
+    let mut j = 0.;
+    for (i, lower) in ordered_groups.iter().enumerate() {
+        for upper in &ordered_groups[i + 1..] {
+            for x in lower.iter() {
+                for y in upper.iter() {
+                    j += if x < y {
+                        1.
+                    } else if x == y {
+                        0.5
+                    } else {
+                        0.
+                    };
+                }
+            }
+        }
+    }
+    j
+}
+
+fn assert_no_ties(values: &[f64]) {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert!(
+        sorted.windows(2).all(|pair| pair[0] < pair[1]),
+        "the exact distribution assumes no ties"
+    );
+}
+
+/// Probability of each count `u` of pairs with the value from the first sample smaller, for samples of sizes `n` and `m` without ties
+///
+/// - Built up one value of the second sample at a time: the largest value adds no pairs if it is from the first sample, and all the values of the first sample otherwise.
+/// - Mixing probabilities rather than counting orderings keeps every step in `[0, 1]`.
+fn mann_whitney_distribution(n: usize, m: usize) -> Vec<f64> {
+    // `distributions[i]` is for samples of sizes `i` and `j`, starting from `j = 0`
+    let mut distributions = vec![vec![1.]; n + 1];
+    for j in 1..=m {
+        let mut next = vec![vec![1.]];
+        for i in 1..=n {
+            let total = (i + j) as f64;
+            let mut distribution = vec![0.; i * j + 1];
+            for (u, p) in next[i - 1].iter().enumerate() {
+                distribution[u] += i as f64 / total * p;
+            }
+            for (u, p) in distributions[i].iter().enumerate() {
+                distribution[u + i] += j as f64 / total * p;
+            }
+            next.push(distribution);
+        }
+        distributions = next;
+    }
+    distributions.pop().unwrap()
+}
+
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.; a.len() + b.len() - 1];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            result[i + j] += a * b;
+        }
+    }
+    result
+}
+
+/// Probability of the integer statistics at least as far from the mean as `observed`, where `distribution[k]` is the probability of `k`
+fn exact_two_sided_p_value(distribution: &[f64], observed: f64) -> f64 {
+    let mean = distribution
+        .iter()
+        .enumerate()
+        .map(|(k, p)| k as f64 * p)
+        .sum::<f64>();
+    let distance = (observed - mean).abs();
+    distribution
+        .iter()
+        .enumerate()
+        .filter(|(k, _)| (*k as f64 - mean).abs() >= distance - EXACT_TOLERANCE)
+        .map(|(_, p)| p)
+        .sum()
+}
+
+const EXACT_TOLERANCE: f64 = 1e-9;
+
+/// `(1 + hits) / (1 + n_perms)`, counting the permuted deviations from the mean at least as large in magnitude as the observed one
+#[cfg(feature = "rand")]
+fn permutation_p_value(observed: f64, n_perms: usize, mut permute: impl FnMut() -> f64) -> f64 {
+    assert!(n_perms > 0);
+    let hits = (0..n_perms)
+        .filter(|_| permute().abs() >= observed.abs() - EXACT_TOLERANCE)
+        .count();
+    (1 + hits) as f64 / (1 + n_perms) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs_test() {
+        let sequence = "TTTTTFFFFFTTTTTFFFFF"
+            .chars()
+            .map(|c| c == 'T')
+            .collect::<Vec<_>>();
+        let test = runs_test(&sequence);
+        assert!((test.statistic.get() - -3.216_278_042).abs() < 1e-9);
+        assert!((test.p_value.get() - 0.001_298_649).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_runs_test_median() {
+        let data = [
+            3.1, 4.2, 2.5, 5.6, 6.1, 1.2, 7.3, 3.3, 8.4, 4.0, 9.9, 5.0, 2.2, 6.6, 7.7,
+        ];
+        let test = runs_test_median(&data);
+        assert!((test.statistic.get() - 1.112_697_281).abs() < 1e-9);
+        assert!((test.p_value.get() - 0.265_838_464).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_kendalls_w() {
+        let rankings = [
+            vec![1., 2., 3., 4., 5.],
+            vec![2., 1., 3., 5., 4.],
+            vec![1., 3., 2., 4., 5.],
+        ];
+        let test = kendalls_w(&rankings);
+        assert!((test.effect_size.unwrap().get() - 0.844_444_444).abs() < 1e-9);
+        assert!((test.statistic.get() - 10.133_333_333).abs() < 1e-9);
+        assert!((test.p_value.get() - 0.038_240_605).abs() < 1e-9);
+
+        let scores = [
+            vec![3.1, 2.2, 5.0, 5.0],
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![2.5, 1.5, 4.5, 3.5],
+        ];
+        let test = kendalls_w(&scores);
+        assert!((test.effect_size.unwrap().get() - 0.839_080_460).abs() < 1e-9);
+        assert!((test.p_value.get() - 0.056_244_226).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_jonckheere_terpstra() {
+        let groups: [&[f64]; 3] = [&[1.2, 2.3, 0.8], &[2.1, 3.4, 2.9, 1.9], &[3.8, 4.1, 2.7]];
+        let test = jonckheere_terpstra(&groups, TrendPValue::Normal);
+        assert_eq!(test.statistic.get(), 29.);
+        assert!((test.p_value.get() - 0.016_640_061).abs() < 1e-9);
+        let test = jonckheere_terpstra(&groups, TrendPValue::Exact);
+        assert!((test.p_value.get() - 72. / 4200.).abs() < 1e-12);
+        #[cfg(feature = "rand")]
+        {
+            let method = TrendPValue::Permutation {
+                n_perms: 10_000,
+                seed: 0,
+            };
+            let test = jonckheere_terpstra(&groups, method);
+            assert!((test.p_value.get() - 72. / 4200.).abs() < 0.005);
+        }
+
+        let groups: [&[f64]; 3] = [&[1., 2., 2., 3.], &[2., 3., 3., 4.], &[3., 4., 5., 5.]];
+        let test = jonckheere_terpstra(&groups, TrendPValue::Normal);
+        assert_eq!(test.statistic.get(), 42.);
+        assert!((test.p_value.get() - 0.006_580_746).abs() < 1e-9);
+
+        // Counts of orderings of groups this large overflow integers
+        let x = (0..70).map(|i| (2 * i) as f64).collect::<Vec<_>>();
+        let y = (0..70).map(|i| (2 * i + 1) as f64).collect::<Vec<_>>();
+        let groups: [&[f64]; 2] = [&x, &y];
+        let exact = jonckheere_terpstra(&groups, TrendPValue::Exact)
+            .p_value
+            .get();
+        let normal = jonckheere_terpstra(&groups, TrendPValue::Normal)
+            .p_value
+            .get();
+        assert!((exact - normal).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_mann_whitney_distribution() {
+        // Orderings of 2 and 3 values: 10, with counts 1, 1, 2, 2, 2, 1, 1
+        let distribution = mann_whitney_distribution(2, 3);
+        let expected = [1., 1., 2., 2., 2., 1., 1.].map(|ways| ways / 10.);
+        assert!(distribution
+            .iter()
+            .zip(expected)
+            .all(|(p, expected)| (p - expected).abs() < 1e-15));
+    }
+
+    #[test]
+    fn test_page_trend() {
+        let blocks = [
+            vec![1.1, 2.3, 2.0, 3.5],
+            vec![0.9, 1.4, 2.2, 2.1],
+            vec![1.8, 1.7, 2.9, 3.3],
+            vec![1.0, 2.5, 2.4, 2.8],
+        ];
+        let order = [0, 2, 1, 3];
+        let test = page_trend(&blocks, &order, TrendPValue::Normal);
+        assert_eq!(test.statistic.get(), 114.);
+        assert!((test.p_value.get() - 0.015_313_822).abs() < 1e-9);
+        let test = page_trend(&blocks, &order, TrendPValue::Exact);
+        assert!((test.p_value.get() - 0.014_925_733).abs() < 1e-9);
+        #[cfg(feature = "rand")]
+        {
+            let method = TrendPValue::Permutation {
+                n_perms: 10_000,
+                seed: 0,
+            };
+            let test = page_trend(&blocks, &order, method);
+            assert!((test.p_value.get() - 0.014_925_733).abs() < 0.005);
+        }
+
+        let blocks = [
+            vec![1., 2., 2., 3.],
+            vec![1., 1., 2., 3.],
+            vec![2., 1., 3., 3.],
+        ];
+        let test = page_trend(&blocks, &[0, 1, 2, 3], TrendPValue::Normal);
+        assert_eq!(test.statistic.get(), 87.5);
+        assert!((test.p_value.get() - 0.008_407_995).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dunn() {
+        let groups: [&[f64]; 3] = [
+            &[2.9, 3.0, 2.5, 2.6, 3.2],
+            &[3.8, 2.7, 4.0, 2.4, 4.5, 4.1],
+            &[1.8, 2.4, 1.7, 2.2, 2.0],
+        ];
+        let comparisons = dunn(&groups, Correction::Holm);
+        let groups = comparisons.iter().map(|c| c.groups).collect::<Vec<_>>();
+        assert_eq!(groups, [(0, 1), (0, 2), (1, 2)]);
+        let z = comparisons.iter().map(|c| c.test.statistic.get());
+        let expected = [-0.862_035_285, 2.160_275_306, 3.118_369_254];
+        assert!(z.zip(expected).all(|(z, e)| (z - e).abs() < 1e-9));
+        let adjusted = comparisons.iter().map(|c| c.adjusted_p_value.get());
+        let expected = [0.388_668_101, 0.061_502_727, 0.005_455_644];
+        assert!(adjusted.zip(expected).all(|(p, e)| (p - e).abs() < 1e-9));
+
+        let comparisons = dunn(
+            &[&[1., 2., 3.], &[4., 5., 6.]],
+            Correction::BenjaminiHochberg,
+        );
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].adjusted_p_value, comparisons[0].test.p_value);
+
+        let comparisons = dunn(&[&[1., 1.], &[1., 1., 1.]], Correction::Holm);
+        assert_eq!(comparisons[0].test.statistic.get(), 0.);
+        assert_eq!(comparisons[0].adjusted_p_value.get(), 1.);
+    }
+
+    #[test]
+    fn test_brunner_munzel() {
+        let x = [1., 2., 1., 1., 1., 1., 1., 1., 1., 1., 2., 4., 1., 1.];
+        let y = [3., 3., 4., 3., 1., 2., 3., 1., 1., 5., 4.];
+        let test = brunner_munzel(&x, &y);
+        assert!((test.statistic.get() - 3.137_467_482).abs() < 1e-9);
+        let DegreesOfFreedom::One(df) = test.df.unwrap() else {
+            panic!()
+        };
+        assert!((df.get() - 17.682_841_979).abs() < 1e-9);
+        assert!((test.p_value.get() - 0.005_786_209).abs() < 1e-6);
+        assert!((test.effect_size.unwrap().get() - 0.788_961_039).abs() < 1e-9);
+
+        // Samples that do not overlap
+        let test = brunner_munzel(&[1., 2., 3., 4.], &[5., 6., 7., 8.]);
+        assert_eq!(test.statistic.get(), f64::MAX);
+        assert_eq!(test.p_value.get(), 0.);
+        assert_eq!(test.effect_size.unwrap().get(), 1.);
+        let test = brunner_munzel(&[1., 1.], &[1., 1., 1.]);
+        assert_eq!(test.p_value.get(), 1.);
+
+        #[cfg(feature = "rand")]
+        {
+            let test = brunner_munzel_permutation(&x, &y, 10_000, 0);
+            assert!(test.p_value.get() < 0.02);
+        }
+    }
+
+    #[test]
+    fn test_quade() {
+        // ref: `quade.test` in R
+        let blocks = [
+            vec![5., 4., 7., 10., 12.],
+            vec![1., 3., 1., 0., 2.],
+            vec![16., 12., 22., 22., 35.],
+            vec![5., 4., 3., 5., 4.],
+            vec![10., 9., 7., 13., 10.],
+            vec![19., 18., 28., 37., 58.],
+            vec![10., 7., 6., 8., 7.],
+        ];
+        let test = quade(&blocks);
+        assert!((test.statistic.get() - 3.829_251_584).abs() < 1e-9);
+        assert!((test.p_value.get() - 0.015_189_020).abs() < 1e-9);
+
+        let test = quade(&[vec![1., 2., 3.], vec![2., 3., 4.], vec![5., 6., 7.]]);
+        assert_eq!(test.statistic.get(), f64::MAX);
+        assert_eq!(test.p_value.get(), 0.);
+    }
+}
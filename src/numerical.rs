@@ -2,30 +2,76 @@ use std::num::NonZeroUsize;
 
 use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
 
-use crate::distributions::{
-    f::{FParams, F_CDF},
-    normal::Z_SCORE_TABLE,
-    t::T_SCORE_TABLE,
+use crate::{
+    categorical::finite_population_correction,
+    correlation::{ranks, tie_group_sizes},
+    distributions::{
+        f::{FParams, F_CDF},
+        noncentral_t::NoncentralT,
+        normal::Z_SCORE_TABLE,
+        t::{StudentT, T_SCORE_TABLE},
+    },
+    special::{bisect, gammainc_upper_regularized},
+    Alternative, DegreesOfFreedom, TestResult,
 };
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NumericalSample {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
     pub mean: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
     pub variance: PositiveF64,
     pub count: NonZeroUsize,
+    /// Size of the finite population sampled without replacement, if known
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub population: Option<NonZeroUsize>,
 }
 impl NumericalSample {
+    /// With the finite population correction if [`Self::population`] is known
     pub fn standard_error_squared(&self) -> f64 {
         self.variance.get() / (self.count.get() as f64)
+            * finite_population_correction(self.count.get(), self.population)
+    }
+
+    /// Mean and unbiased variance of at least two observations
+    pub fn from_values(values: &[f64]) -> Self {
+        let count = NonZeroUsize::new(values.len()).unwrap();
+        assert!(count.get() >= 2);
+        let mean = values.iter().sum::<f64>() / count.get() as f64;
+        let variance =
+            values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count.get() - 1) as f64;
+        Self {
+            mean: FiniteF64::new(mean).unwrap(),
+            variance: PositiveF64::new(variance).unwrap(),
+            count,
+            population: None,
+        }
     }
 }
 
 pub fn one_sample_mean(sample: NumericalSample, mean_0: FiniteF64) -> NormalizedF64 {
+    one_sample_mean_result(sample, mean_0).p_value
+}
+
+/// [`one_sample_mean`] with the `t` statistic and Cohen's d against `mean_0`
+pub fn one_sample_mean_result(sample: NumericalSample, mean_0: FiniteF64) -> TestResult {
     let standard_error = standard_error(&[sample]);
     let t = (sample.mean.get() - mean_0.get()) / standard_error;
     let t = FiniteF64::new(t).unwrap();
     let df = NonZeroUsize::new(sample.count.get() - 1).unwrap();
-    T_SCORE_TABLE.p_value_two_sided(df, t)
+    let d = (sample.mean.get() - mean_0.get()) / sample.variance.get().sqrt();
+    TestResult {
+        statistic: t,
+        df: Some(DegreesOfFreedom::one(df.get())),
+        p_value: T_SCORE_TABLE.p_value_two_sided(df, t),
+        alternative: Alternative::TwoSided,
+        effect_size: Some(FiniteF64::new(d).unwrap()),
+        confidence_interval: None,
+    }
 }
 
 pub fn difference_of_two_means(
@@ -33,12 +79,182 @@ pub fn difference_of_two_means(
     sample_2: NumericalSample,
     mean_0: FiniteF64,
 ) -> NormalizedF64 {
+    difference_of_two_means_result(sample_1, sample_2, mean_0).p_value
+}
+
+/// [`difference_of_two_means`] with the `t` statistic and [`cohens_d`]
+pub fn difference_of_two_means_result(
+    sample_1: NumericalSample,
+    sample_2: NumericalSample,
+    mean_0: FiniteF64,
+) -> TestResult {
     let standard_error = standard_error(&[sample_1, sample_2]);
     let t = (sample_1.mean.get() - sample_2.mean.get() - mean_0.get()) / standard_error;
     let t = FiniteF64::new(t).unwrap();
     let df = sample_1.count.min(sample_2.count).get() - 1;
     let df = NonZeroUsize::new(df).unwrap();
-    T_SCORE_TABLE.p_value_two_sided(df, t)
+    TestResult {
+        statistic: t,
+        df: Some(DegreesOfFreedom::one(df.get())),
+        p_value: T_SCORE_TABLE.p_value_two_sided(df, t),
+        alternative: Alternative::TwoSided,
+        effect_size: Some(cohens_d(sample_1, sample_2)),
+        confidence_interval: None,
+    }
+}
+
+/// Observations with survey or reliability weights
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeightedSample {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub mean: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub variance: PositiveF64,
+    /// Kish's effective sample size `(sum w)^2 / sum w^2`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub effective_count: PositiveF64,
+}
+impl WeightedSample {
+    /// Weighted mean and the unbiased weighted variance `sum w (x - mean)^2 / (sum w - sum w^2 / sum w)`
+    pub fn new(values: &[f64], weights: &[PositiveF64]) -> Self {
+        assert_eq!(values.len(), weights.len());
+        let sum = weights.iter().map(|w| w.get()).sum::<f64>();
+        let sum_of_squares = weights.iter().map(|w| w.get().powi(2)).sum::<f64>();
+        assert!(sum > 0.);
+        let mean = values
+            .iter()
+            .zip(weights)
+            .map(|(x, w)| w.get() * x)
+            .sum::<f64>()
+            / sum;
+        let effective_count = sum.powi(2) / sum_of_squares;
+        assert!(effective_count > 1.);
+        let variance = values
+            .iter()
+            .zip(weights)
+            .map(|(x, w)| w.get() * (x - mean).powi(2))
+            .sum::<f64>()
+            / (sum - sum_of_squares / sum);
+        Self {
+            mean: FiniteF64::new(mean).unwrap(),
+            variance: PositiveF64::new(variance).unwrap(),
+            effective_count: PositiveF64::new(effective_count).unwrap(),
+        }
+    }
+
+    pub fn standard_error_squared(&self) -> f64 {
+        self.variance.get() / self.effective_count.get()
+    }
+}
+
+/// [`one_sample_mean_result`] on a [`WeightedSample`]
+///
+/// - The degrees of freedom are `effective_count - 1`, and the p-value is from the exact t distribution.
+pub fn one_sample_weighted_mean_result(sample: WeightedSample, mean_0: FiniteF64) -> TestResult {
+    let difference = sample.mean.get() - mean_0.get();
+    let t = difference / sample.standard_error_squared().sqrt();
+    let df = sample.effective_count.get() - 1.;
+    let d = difference / sample.variance.get().sqrt();
+    weighted_t_test(t, df, d)
+}
+
+/// [`difference_of_two_means_result`] on [`WeightedSample`]s
+///
+/// - The degrees of freedom are from the Welch–Satterthwaite equation with the effective counts, and the p-value is from the exact t distribution.
+/// - The effect size is Cohen's d with the variances pooled by their effective degrees of freedom.
+pub fn difference_of_two_weighted_means_result(
+    sample_1: WeightedSample,
+    sample_2: WeightedSample,
+    mean_0: FiniteF64,
+) -> TestResult {
+    let (se2_1, se2_2) = (
+        sample_1.standard_error_squared(),
+        sample_2.standard_error_squared(),
+    );
+    let (df_1, df_2) = (
+        sample_1.effective_count.get() - 1.,
+        sample_2.effective_count.get() - 1.,
+    );
+    let difference = sample_1.mean.get() - sample_2.mean.get();
+    let t = (difference - mean_0.get()) / (se2_1 + se2_2).sqrt();
+    let df = (se2_1 + se2_2).powi(2) / (se2_1.powi(2) / df_1 + se2_2.powi(2) / df_2);
+    let pooled_variance =
+        (df_1 * sample_1.variance.get() + df_2 * sample_2.variance.get()) / (df_1 + df_2);
+    weighted_t_test(t, df, difference / pooled_variance.sqrt())
+}
+
+fn weighted_t_test(t: f64, df: f64, d: f64) -> TestResult {
+    let df = PositiveF64::new(df).unwrap();
+    let t = FiniteF64::new(t).unwrap();
+    let tail = StudentT { df }
+        .cdf(FiniteF64::new(-t.get().abs()).unwrap())
+        .get();
+    TestResult {
+        statistic: t,
+        df: Some(DegreesOfFreedom::One(df)),
+        p_value: NormalizedF64::new((2. * tail).min(1.)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: Some(FiniteF64::new(d).unwrap()),
+        confidence_interval: None,
+    }
+}
+
+/// Cohen's d: the difference of two means in units of their pooled standard deviation.
+pub fn cohens_d(sample_1: NumericalSample, sample_2: NumericalSample) -> FiniteF64 {
+    let d =
+        (sample_1.mean.get() - sample_2.mean.get()) / pooled_variance(sample_1, sample_2).sqrt();
+    FiniteF64::new(d).unwrap()
+}
+
+/// Confidence interval of Cohen's d by inverting the noncentral t distribution of the pooled t statistic.
+///
+/// - `confidence`: e.g. 0.95
+pub fn cohens_d_confidence_interval(
+    sample_1: NumericalSample,
+    sample_2: NumericalSample,
+    confidence: NormalizedF64,
+) -> (FiniteF64, FiniteF64) {
+    let n_1 = sample_1.count.get() as f64;
+    let n_2 = sample_2.count.get() as f64;
+    let scale = (1. / n_1 + 1. / n_2).sqrt();
+    let t = cohens_d(sample_1, sample_2).get() / scale;
+    let t = FiniteF64::new(t).unwrap();
+    let df = PositiveF64::new(n_1 + n_2 - 2.).unwrap();
+
+    // The CDF at `t` decreases in the noncentrality
+    let noncentrality_at = |cdf: f64| {
+        let f = |delta: f64| {
+            let distribution = NoncentralT {
+                df,
+                noncentrality: FiniteF64::new(delta).unwrap(),
+            };
+            cdf - distribution.cdf(t).get()
+        };
+        let mut lower = t.get() - 1.;
+        let mut upper = t.get() + 1.;
+        while f(lower) > 0. {
+            lower -= 2. * (upper - lower);
+        }
+        while f(upper) < 0. {
+            upper += 2. * (upper - lower);
+        }
+        bisect(f, lower, upper)
+    };
+    let tail = (1. - confidence.get()) / 2.;
+    let lower = noncentrality_at(1. - tail) * scale;
+    let upper = noncentrality_at(tail) * scale;
+    (
+        FiniteF64::new(lower).unwrap(),
+        FiniteF64::new(upper).unwrap(),
+    )
+}
+
+fn pooled_variance(sample_1: NumericalSample, sample_2: NumericalSample) -> f64 {
+    let df_1 = (sample_1.count.get() - 1) as f64;
+    let df_2 = (sample_2.count.get() - 1) as f64;
+    assert!(df_1 + df_2 > 0.);
+    (df_1 * sample_1.variance.get() + df_2 * sample_2.variance.get()) / (df_1 + df_2)
 }
 
 fn standard_error(samples: &[NumericalSample]) -> f64 {
@@ -73,6 +289,33 @@ pub fn min_count_of_each_of_two_samples(
     count.ceil() as usize
 }
 
+/// Determine a proper sample size for testing a single mean against `mean_0`.
+///
+/// `power`: probability that the alternative hypothesis is not confused as a null hypothesis
+///
+/// - usually in
+///   ```math
+///   [0.8, 0.9]
+///   ```
+///
+/// `deviation`: spread of the observations in squared units, as in [`min_count_of_each_of_two_samples`]
+pub fn min_count_one_sample(
+    mean_alt: FiniteF64,
+    mean_0: FiniteF64,
+    deviation: PositiveF64,
+    power: NormalizedF64,
+    max_p_value: NormalizedF64,
+) -> usize {
+    let one_sided_p_value = max_p_value.get() / 2.;
+    let one_sided_p_value = NormalizedF64::new(one_sided_p_value).unwrap();
+    let power_region_extension = Z_SCORE_TABLE.z(power);
+    let reject_region_extension = Z_SCORE_TABLE.z(one_sided_p_value);
+    let region = reject_region_extension.get() - power_region_extension.get();
+    let diff = mean_alt.get() - mean_0.get();
+    let count = deviation.get() / (diff / region).powi(2);
+    count.ceil() as usize
+}
+
 /// Null hypothesis: all means are equal.
 pub fn anova(groups: &[NumericalSample]) -> (FParams, NormalizedF64) {
     let total_n = groups.iter().map(|group| group.count.get()).sum::<usize>();
@@ -93,6 +336,98 @@ pub fn anova(groups: &[NumericalSample]) -> (FParams, NormalizedF64) {
     (f_params, F_CDF.p_value(f_params))
 }
 
+/// [`anova`] with eta squared, the share of the total sum of squares between groups
+pub fn anova_result(groups: &[NumericalSample]) -> TestResult {
+    let (f_params, p_value) = anova(groups);
+    let total_n = groups.iter().map(|group| group.count.get()).sum::<usize>();
+    let ssg = sum_of_squares_between_groups(groups, total_n);
+    let sse = sum_of_squared_errors(groups);
+    let eta_squared = ssg / (ssg + sse);
+    TestResult {
+        statistic: FiniteF64::new(f_params.x.get()).unwrap(),
+        df: Some(DegreesOfFreedom::Two(
+            PositiveF64::new(f_params.df_1.get() as f64).unwrap(),
+            PositiveF64::new(f_params.df_2.get() as f64).unwrap(),
+        )),
+        p_value,
+        alternative: Alternative::Greater,
+        effect_size: Some(FiniteF64::new(eta_squared).unwrap()),
+        confidence_interval: None,
+    }
+}
+
+/// [`anova_result`] on raw observations, one group per column
+#[cfg(feature = "ndarray")]
+pub fn anova_columns(data: ndarray::ArrayView2<f64>) -> TestResult {
+    let groups = data
+        .columns()
+        .into_iter()
+        .map(|column| NumericalSample::from_values(&column.to_vec()))
+        .collect::<Vec<_>>();
+    anova_result(&groups)
+}
+
+/// Kruskal–Wallis H test, with the correction for ties.
+///
+/// Null hypothesis: all groups come from the same distribution.
+///
+/// - The p-value is from the chi-square approximation with `k - 1` degrees of freedom.
+/// - The effect size is epsilon squared, `H / (n - 1)`.
+pub fn kruskal_wallis(groups: &[&[f64]]) -> TestResult {
+    assert!(groups.len() >= 2);
+    assert!(groups.iter().all(|group| !group.is_empty()));
+    let values = groups
+        .iter()
+        .flat_map(|group| group.iter().copied())
+        .collect::<Vec<_>>();
+    let ranks = ranks(&values);
+    let n = values.len() as f64;
+
+    let mut offset = 0;
+    let mut h = 0.;
+    for group in groups {
+        let rank_sum = ranks[offset..offset + group.len()].iter().sum::<f64>();
+        h += rank_sum.powi(2) / group.len() as f64;
+        offset += group.len();
+    }
+    h = 12. / (n * (n + 1.)) * h - 3. * (n + 1.);
+
+    let mut sorted = values;
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let ties = tie_group_sizes(sorted.iter())
+        .into_iter()
+        .map(|t| (t.pow(3) - t) as f64)
+        .sum::<f64>();
+    h /= 1. - ties / (n.powi(3) - n);
+    let h = h.max(0.);
+
+    let df = groups.len() - 1;
+    let p_value = gammainc_upper_regularized(df as f64 / 2., h / 2.);
+    TestResult {
+        statistic: FiniteF64::new(h).unwrap(),
+        df: Some(DegreesOfFreedom::one(df)),
+        p_value: NormalizedF64::new(p_value).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: Some(FiniteF64::new(h / (n - 1.)).unwrap()),
+        confidence_interval: None,
+    }
+}
+
+/// [`kruskal_wallis`] on raw observations, one group per column
+#[cfg(feature = "ndarray")]
+pub fn kruskal_wallis_columns(data: ndarray::ArrayView2<f64>) -> TestResult {
+    let groups = data
+        .columns()
+        .into_iter()
+        .map(|column| column.to_vec())
+        .collect::<Vec<_>>();
+    let groups = groups
+        .iter()
+        .map(|group| group.as_slice())
+        .collect::<Vec<_>>();
+    kruskal_wallis(&groups)
+}
+
 fn mean_square_between_groups(
     groups: &[NumericalSample],
     total_n: usize,
@@ -102,7 +437,7 @@ fn mean_square_between_groups(
     ssg / df_g.get() as f64
 }
 
-fn sum_of_squares_between_groups(groups: &[NumericalSample], total_n: usize) -> f64 {
+pub(crate) fn sum_of_squares_between_groups(groups: &[NumericalSample], total_n: usize) -> f64 {
     let total_sum = groups
         .iter()
         .map(|group| group.mean.get() * group.count.get() as f64)
@@ -122,7 +457,7 @@ fn mean_square_error(groups: &[NumericalSample], df_e: NonZeroUsize) -> f64 {
     sse / df_e.get() as f64
 }
 
-fn sum_of_squared_errors(groups: &[NumericalSample]) -> f64 {
+pub(crate) fn sum_of_squared_errors(groups: &[NumericalSample]) -> f64 {
     groups
         .iter()
         .map(|group| (group.count.get() - 1) as f64 * group.variance.get())
@@ -133,6 +468,42 @@ fn sum_of_squared_errors(groups: &[NumericalSample]) -> f64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_weighted_sample() {
+        let weights = |w: &[f64]| {
+            w.iter()
+                .map(|&w| PositiveF64::new(w).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let sample_1 = WeightedSample::new(
+            &[5.1, 4.8, 6.2, 5.9, 5.5, 6.8, 4.9, 5.3],
+            &weights(&[1., 2., 1., 3., 1., 2., 1., 1.]),
+        );
+        assert!((sample_1.mean.get() - 5.658_333_333).abs() < 1e-9);
+        assert!((sample_1.variance.get() - 0.549_754_098).abs() < 1e-9);
+        assert!((sample_1.effective_count.get() - 6.545_454_545).abs() < 1e-9);
+        let sample_2 = WeightedSample::new(
+            &[4.2, 4.9, 5.0, 4.4, 5.2, 4.7],
+            &weights(&[2., 1., 1., 2., 1., 1.]),
+        );
+
+        let result = one_sample_weighted_mean_result(sample_1, FiniteF64::new(5.).unwrap());
+        assert!((result.statistic.get() - 2.271_598_185).abs() < 1e-9);
+        assert!((result.p_value.get() - 0.067_108_280).abs() < 1e-8);
+
+        let result = difference_of_two_weighted_means_result(
+            sample_1,
+            sample_2,
+            FiniteF64::new(0.).unwrap(),
+        );
+        assert!((result.statistic.get() - 3.070_328_185).abs() < 1e-9);
+        let Some(DegreesOfFreedom::One(df)) = result.df else {
+            panic!()
+        };
+        assert!((df.get() - 8.728_260_252).abs() < 1e-9);
+        assert!((result.p_value.get() - 0.013_832_994).abs() < 1e-8);
+    }
+
     #[test]
     fn test_one_sample_mean() {
         assert!(
@@ -141,6 +512,7 @@ mod tests {
                     mean: FiniteF64::new(97.32).unwrap(),
                     variance: PositiveF64::new(16.98_f64.powi(2)).unwrap(),
                     count: NonZeroUsize::new(100).unwrap(),
+                    population: None,
                 },
                 FiniteF64::new(93.29).unwrap()
             )
@@ -157,11 +529,13 @@ mod tests {
                     mean: FiniteF64::new(7.18).unwrap(),
                     variance: PositiveF64::new(1.60_f64.powi(2)).unwrap(),
                     count: NonZeroUsize::new(100).unwrap(),
+                    population: None,
                 },
                 NumericalSample {
                     mean: FiniteF64::new(6.78).unwrap(),
                     variance: PositiveF64::new(1.43_f64.powi(2)).unwrap(),
                     count: NonZeroUsize::new(50).unwrap(),
+                    population: None,
                 },
                 FiniteF64::new(0.).unwrap()
             )
@@ -181,6 +555,38 @@ mod tests {
         assert_eq!(count, 251);
     }
 
+    #[test]
+    fn test_min_count_one_sample() {
+        let mean_alt = FiniteF64::new(103.).unwrap();
+        let mean_0 = FiniteF64::new(100.).unwrap();
+        let variance = PositiveF64::new((12.0_f64).powi(2)).unwrap();
+        let power = NormalizedF64::new(0.8).unwrap();
+        let max_p_value = NormalizedF64::new(0.05).unwrap();
+        let count = min_count_one_sample(mean_alt, mean_0, variance, power, max_p_value);
+        assert_eq!(count, 126);
+    }
+
+    #[test]
+    fn test_cohens_d_confidence_interval() {
+        let sample_1 = NumericalSample {
+            mean: FiniteF64::new(5.6125).unwrap(),
+            variance: PositiveF64::new(0.235_535_714).unwrap(),
+            count: NonZeroUsize::new(8).unwrap(),
+            population: None,
+        };
+        let sample_2 = NumericalSample {
+            mean: FiniteF64::new(4.675).unwrap(),
+            variance: PositiveF64::new(0.147_857_143).unwrap(),
+            count: NonZeroUsize::new(8).unwrap(),
+            population: None,
+        };
+        assert!((cohens_d(sample_1, sample_2).get() - 2.141_234_66).abs() < 1e-6);
+        let confidence = NormalizedF64::new(0.95).unwrap();
+        let (lower, upper) = cohens_d_confidence_interval(sample_1, sample_2, confidence);
+        assert!((lower.get() - 0.862_195).abs() < 1e-3);
+        assert!((upper.get() - 3.373_990).abs() < 1e-3);
+    }
+
     #[test]
     fn test_anova() {
         let groups = [
@@ -188,16 +594,19 @@ mod tests {
                 mean: FiniteF64::new(85.75).unwrap(),
                 variance: PositiveF64::new(28.25).unwrap(),
                 count: NonZeroUsize::new(4).unwrap(),
+                population: None,
             },
             NumericalSample {
                 mean: FiniteF64::new(84.).unwrap(),
                 variance: PositiveF64::new(13.00).unwrap(),
                 count: NonZeroUsize::new(3).unwrap(),
+                population: None,
             },
             NumericalSample {
                 mean: FiniteF64::new(90.2).unwrap(),
                 variance: PositiveF64::new(15.70).unwrap(),
                 count: NonZeroUsize::new(5).unwrap(),
+                population: None,
             },
         ];
         let (f, p) = anova(&groups);
@@ -205,5 +614,43 @@ mod tests {
         assert_eq!(f.df_2.get(), 9);
         assert!((f.x.get() - 2.1811).abs() < 0.05);
         assert!((p.get() - 0.1689).abs() < 0.05);
+
+        let result = anova_result(&groups);
+        assert_eq!(result.p_value, p);
+        assert!(matches!(result.df, Some(DegreesOfFreedom::Two(_, _))));
+        // SSG = 84.117, SSE = 173.55
+        assert!((result.effect_size.unwrap().get() - 0.326_455).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_kruskal_wallis() {
+        let result = kruskal_wallis(&[
+            &[2.9, 3.0, 2.5, 2.6, 3.2],
+            &[3.8, 2.7, 4.0, 2.4],
+            &[2.8, 3.4, 3.7, 2.2, 2.0],
+        ]);
+        assert!((result.statistic.get() - 0.771_428_571).abs() < 1e-8);
+        assert!((result.p_value.get() - 0.679_964_774).abs() < 1e-8);
+
+        // With ties
+        let result = kruskal_wallis(&[&[1., 2., 2., 3.], &[2., 3., 4., 4.], &[5., 5., 6., 3.]]);
+        assert!((result.statistic.get() - 6.735_507_246).abs() < 1e-8);
+        assert!((result.p_value.get() - 0.034_466_976).abs() < 1e-8);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_columns() {
+        let data = ndarray::arr2(&[
+            [1., 2., 5.], //
+            [2., 3., 5.], //
+            [2., 4., 6.], //
+            [3., 4., 3.], //
+        ]);
+        let result = anova_columns(data.view());
+        assert!((result.statistic.get() - 7.184_210_526).abs() < 1e-8);
+        assert!((result.effect_size.unwrap().get() - 0.614_864_865).abs() < 1e-8);
+        let result = kruskal_wallis_columns(data.view());
+        assert!((result.statistic.get() - 6.735_507_246).abs() < 1e-8);
     }
 }
@@ -0,0 +1,195 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{distributions::t::StudentT, Alternative, DegreesOfFreedom, TestResult};
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grubbs {
+    /// Index of the observation farthest from the mean
+    pub index: usize,
+    /// `G = max |x - mean| / s`
+    ///
+    /// Null hypothesis: there are no outliers.
+    ///
+    /// - The p-value is the Bonferroni bound `2 n P(T > t)`, capped at 1.
+    pub test: TestResult,
+    /// `G` beyond which the observation is an outlier at the given significance level
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub critical_value: PositiveF64,
+}
+impl Grubbs {
+    pub fn is_outlier(&self) -> bool {
+        self.test.statistic.get() > self.critical_value.get()
+    }
+}
+
+/// Two-sided Grubbs test for a single outlier in normal data.
+///
+/// ref: <https://www.itl.nist.gov/div898/handbook/eda/section3/eda35h1.htm>
+pub fn grubbs(data: &[f64], alpha: NormalizedF64) -> Grubbs {
+    let n = data.len();
+    assert!(n >= 3);
+    let (index, g) = most_extreme(data, &(0..n).collect::<Vec<_>>());
+
+    let (n_f, df) = (n as f64, (n - 2) as f64);
+    let t_squared = n_f * df * g.powi(2) / ((n_f - 1.).powi(2) - n_f * g.powi(2));
+    let tail = if t_squared.is_finite() && t_squared >= 0. {
+        student_t(df)
+            .cdf(FiniteF64::new(-t_squared.sqrt()).unwrap())
+            .get()
+    } else {
+        // `G` is at its upper bound `(n - 1) / sqrt(n)`
+        0.
+    };
+    let p_value = (2. * n_f * tail).min(1.);
+    Grubbs {
+        index,
+        test: TestResult {
+            statistic: FiniteF64::new(g).unwrap(),
+            df: Some(DegreesOfFreedom::one(n - 2)),
+            p_value: NormalizedF64::new(p_value).unwrap(),
+            alternative: Alternative::TwoSided,
+            effect_size: None,
+            confidence_interval: None,
+        },
+        critical_value: PositiveF64::new(critical_value(n, alpha)).unwrap(),
+    }
+}
+
+/// One round of the generalized ESD procedure
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EsdStep {
+    /// Index into the original data of the observation removed in this round
+    pub index: usize,
+    /// `R_i = max |x - mean| / s` among the observations left
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub statistic: PositiveF64,
+    /// `lambda_i`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub critical_value: PositiveF64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneralizedEsd {
+    pub steps: Vec<EsdStep>,
+    /// The largest `i` with `R_i > lambda_i`
+    pub outliers: usize,
+}
+impl GeneralizedEsd {
+    pub fn outlier_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.steps[..self.outliers].iter().map(|step| step.index)
+    }
+}
+
+/// Rosner's generalized extreme Studentized deviate test for up to `max_outliers` outliers in normal data.
+///
+/// ref: <https://www.itl.nist.gov/div898/handbook/eda/section3/eda35h3.htm>
+pub fn generalized_esd(data: &[f64], max_outliers: usize, alpha: NormalizedF64) -> GeneralizedEsd {
+    let n = data.len();
+    assert!(max_outliers >= 1);
+    assert!(max_outliers + 2 < n);
+    let mut remaining = (0..n).collect::<Vec<_>>();
+    let steps = (1..=max_outliers)
+        .map(|i| {
+            let (index, statistic) = most_extreme(data, &remaining);
+            remaining.retain(|&j| j != index);
+            EsdStep {
+                index,
+                statistic: PositiveF64::new(statistic).unwrap(),
+                critical_value: PositiveF64::new(critical_value(n - i + 1, alpha)).unwrap(),
+            }
+        })
+        .collect::<Vec<_>>();
+    let outliers = steps
+        .iter()
+        .rposition(|step| step.statistic.get() > step.critical_value.get())
+        .map_or(0, |i| i + 1);
+    GeneralizedEsd { steps, outliers }
+}
+
+/// Index and Studentized deviation of the observation in `indices` farthest from their mean
+fn most_extreme(data: &[f64], indices: &[usize]) -> (usize, f64) {
+    let n = indices.len() as f64;
+    let mean = indices.iter().map(|&i| data[i]).sum::<f64>() / n;
+    let variance = indices
+        .iter()
+        .map(|&i| (data[i] - mean).powi(2))
+        .sum::<f64>()
+        / (n - 1.);
+    assert!(variance > 0.);
+    let (index, deviation) = indices.iter().map(|&i| (i, (data[i] - mean).abs())).fold(
+        (indices[0], f64::NEG_INFINITY),
+        |max, x| {
+            if x.1 > max.1 {
+                x
+            } else {
+                max
+            }
+        },
+    );
+    (index, deviation / variance.sqrt())
+}
+
+/// Two-sided critical value of the Studentized deviation among `remaining` observations
+fn critical_value(remaining: usize, alpha: NormalizedF64) -> f64 {
+    let m = remaining as f64;
+    let p = alpha.get() / (2. * m);
+    let t = student_t(m - 2.)
+        .quantile(NormalizedF64::new(1. - p).unwrap())
+        .get();
+    (m - 1.) * t / ((m - 2. + t.powi(2)) * m).sqrt()
+}
+
+fn student_t(df: f64) -> StudentT {
+    StudentT {
+        df: PositiveF64::new(df).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// NIST example of Rosner's test
+    const DATA: [f64; 54] = [
+        -0.25, 0.68, 0.94, 1.15, 1.20, 1.26, 1.26, 1.34, 1.38, 1.43, 1.49, 1.49, 1.55, 1.56, 1.58,
+        1.65, 1.69, 1.70, 1.76, 1.77, 1.81, 1.91, 1.94, 1.96, 1.99, 2.06, 2.09, 2.10, 2.14, 2.15,
+        2.23, 2.24, 2.26, 2.35, 2.37, 2.40, 2.47, 2.54, 2.62, 2.64, 2.90, 2.92, 2.92, 2.93, 3.21,
+        3.26, 3.30, 3.59, 3.68, 4.30, 4.64, 5.34, 5.42, 6.01,
+    ];
+
+    #[test]
+    fn test_grubbs() {
+        let grubbs = grubbs(&DATA, NormalizedF64::new(0.05).unwrap());
+        assert_eq!(grubbs.index, 53);
+        assert!((grubbs.test.statistic.get() - 3.118_906_049).abs() < 1e-9);
+        assert!((grubbs.critical_value.get() - 3.158_793_941).abs() < 1e-8);
+        assert!((grubbs.test.p_value.get() - 0.058_984_727).abs() < 1e-8);
+        assert!(!grubbs.is_outlier());
+    }
+
+    #[test]
+    fn test_generalized_esd() {
+        let esd = generalized_esd(&DATA, 10, NormalizedF64::new(0.05).unwrap());
+        let expected = [
+            (3.118_906_049, 3.158_793_941),
+            (2.942_973_114, 3.151_430_023),
+            (3.179_423_937, 3.143_889_685),
+            (2.810_181_144, 3.136_164_956),
+            (2.815_579_563, 3.128_247_334),
+            (2.848_171_628, 3.120_127_738),
+            (2.279_327_055, 3.111_796_454),
+            (2.310_366_059, 3.103_243_078),
+            (2.101_580_651, 3.094_456_447),
+            (2.067_178_078, 3.085_424_571),
+        ];
+        for (step, (statistic, critical_value)) in esd.steps.iter().zip(expected) {
+            assert!((step.statistic.get() - statistic).abs() < 1e-8);
+            assert!((step.critical_value.get() - critical_value).abs() < 1e-8);
+        }
+        assert_eq!(esd.outliers, 3);
+        assert_eq!(esd.outlier_indices().collect::<Vec<_>>(), [53, 52, 51]);
+    }
+}
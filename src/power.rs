@@ -0,0 +1,565 @@
+use std::num::NonZeroUsize;
+
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    distributions::{
+        noncentral_chi_square::NoncentralChiSquare, noncentral_f::NoncentralF,
+        noncentral_t::NoncentralT,
+    },
+    special::{bisect, normal_cdf, normal_quantile},
+    Alternative,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TTestDesign {
+    OneSample,
+    /// `n` is the count of each of the two equally sized samples
+    TwoSample,
+    /// `n` is the count of pairs; the effect size is on the within-pair differences
+    Paired,
+}
+
+/// Power of a t-test from the noncentral t distribution.
+///
+/// `effect_size`: Cohen's d, the mean difference in units of the standard deviation
+pub fn t_test_power(
+    design: TTestDesign,
+    effect_size: FiniteF64,
+    n: NonZeroUsize,
+    alpha: NormalizedF64,
+    alternative: Alternative,
+) -> NormalizedF64 {
+    let n = n.get() as f64;
+    let (df, noncentrality) = match design {
+        TTestDesign::OneSample | TTestDesign::Paired => (n - 1., effect_size.get() * n.sqrt()),
+        TTestDesign::TwoSample => (2. * n - 2., effect_size.get() * (n / 2.).sqrt()),
+    };
+    assert!(df > 0.);
+    let df = PositiveF64::new(df).unwrap();
+
+    let null = NoncentralT {
+        df,
+        noncentrality: FiniteF64::new(0.).unwrap(),
+    };
+    let alternative_distribution = NoncentralT {
+        df,
+        noncentrality: FiniteF64::new(noncentrality).unwrap(),
+    };
+    let upper_tail = |critical: FiniteF64| 1. - alternative_distribution.cdf(critical).get();
+    let lower_tail = |critical: FiniteF64| alternative_distribution.cdf(critical).get();
+    let quantile = |p: f64| null.quantile(NormalizedF64::new(p).unwrap());
+
+    let power = match alternative {
+        Alternative::TwoSided => {
+            let critical = quantile(1. - alpha.get() / 2.);
+            let negative_critical = FiniteF64::new(-critical.get()).unwrap();
+            upper_tail(critical) + lower_tail(negative_critical)
+        }
+        Alternative::Greater => upper_tail(quantile(1. - alpha.get())),
+        Alternative::Less => lower_tail(quantile(alpha.get())),
+    };
+    NormalizedF64::new(power.clamp(0., 1.)).unwrap()
+}
+
+/// Power of a one-way ANOVA from the noncentral F distribution.
+///
+/// - `groups_effect`: Cohen's f, the standard deviation of the group means in units of the within-group standard deviation
+/// - `k`: the number of groups
+/// - `n_per_group`: the count of each of the equally sized groups
+pub fn anova_power(
+    groups_effect: PositiveF64,
+    k: NonZeroUsize,
+    n_per_group: NonZeroUsize,
+    alpha: NormalizedF64,
+) -> NormalizedF64 {
+    let k = k.get();
+    let n = n_per_group.get();
+    assert!(k >= 2);
+    assert!(n >= 2);
+    let df_1 = PositiveF64::new((k - 1) as f64).unwrap();
+    let df_2 = PositiveF64::new((k * (n - 1)) as f64).unwrap();
+    let noncentrality = groups_effect.get().powi(2) * (k * n) as f64;
+
+    let null = NoncentralF {
+        df_1,
+        df_2,
+        noncentrality: PositiveF64::new(0.).unwrap(),
+    };
+    let critical = null.quantile(NormalizedF64::new(1. - alpha.get()).unwrap());
+    let alternative_distribution = NoncentralF {
+        df_1,
+        df_2,
+        noncentrality: PositiveF64::new(noncentrality).unwrap(),
+    };
+    alternative_distribution.sf(critical)
+}
+
+/// Determine the count of each group for a one-way ANOVA to reach `power`.
+///
+/// `power`: probability that the alternative hypothesis is not confused as a null hypothesis
+///
+/// - usually in
+///   ```math
+///   [0.8, 0.9]
+///   ```
+/// - less than `1`, which no finite count reaches
+pub fn anova_min_count_per_group(
+    groups_effect: PositiveF64,
+    k: NonZeroUsize,
+    power: NormalizedF64,
+    max_p_value: NormalizedF64,
+) -> usize {
+    assert!(groups_effect.get() > 0.);
+    let power_at =
+        |n: usize| anova_power(groups_effect, k, NonZeroUsize::new(n).unwrap(), max_p_value).get();
+    min_count(power_at, power)
+}
+
+/// Power of a chi-square goodness-of-fit or independence test from the noncentral chi-square distribution.
+///
+/// - `w`: Cohen's w, `sqrt(sum (p_1 - p_0)^2 / p_0)` over the cells
+/// - `n`: the total count of observations
+pub fn chi_square_power(
+    w: PositiveF64,
+    df: NonZeroUsize,
+    n: NonZeroUsize,
+    alpha: NormalizedF64,
+) -> NormalizedF64 {
+    let df = PositiveF64::new(df.get() as f64).unwrap();
+    let noncentrality = w.get().powi(2) * n.get() as f64;
+
+    let null = NoncentralChiSquare {
+        df,
+        noncentrality: PositiveF64::new(0.).unwrap(),
+    };
+    let critical = null.quantile(NormalizedF64::new(1. - alpha.get()).unwrap());
+    let alternative_distribution = NoncentralChiSquare {
+        df,
+        noncentrality: PositiveF64::new(noncentrality).unwrap(),
+    };
+    alternative_distribution.sf(critical)
+}
+
+/// Determine the total count for a chi-square test to reach `power`.
+///
+/// `power`: probability that the alternative hypothesis is not confused as a null hypothesis
+///
+/// - usually in
+///   ```math
+///   [0.8, 0.9]
+///   ```
+/// - less than `1`, which no finite count reaches
+pub fn chi_square_min_count(
+    w: PositiveF64,
+    df: NonZeroUsize,
+    power: NormalizedF64,
+    max_p_value: NormalizedF64,
+) -> usize {
+    assert!(w.get() > 0.);
+    let power_at =
+        |n: usize| chi_square_power(w, df, NonZeroUsize::new(n).unwrap(), max_p_value).get();
+    min_count(power_at, power)
+}
+
+/// Cohen's w of a goodness-of-fit test from the cell proportions under the null and the alternative hypotheses.
+///
+/// - `p_0`, `p_1`: proportions of each cell, each summing to 1
+pub fn fitness_effect(p_0: &[f64], p_1: &[f64]) -> PositiveF64 {
+    assert_eq!(p_0.len(), p_1.len());
+    assert!(p_0.len() >= 2);
+    assert!((p_0.iter().sum::<f64>() - 1.).abs() < PROPORTION_SUM_TOLERANCE);
+    assert!((p_1.iter().sum::<f64>() - 1.).abs() < PROPORTION_SUM_TOLERANCE);
+    let w_squared = p_0
+        .iter()
+        .zip(p_1)
+        .map(|(&p_0, &p_1)| {
+            assert!(p_0 > 0.);
+            assert!(p_1 >= 0.);
+            (p_1 - p_0).powi(2) / p_0
+        })
+        .sum::<f64>();
+    PositiveF64::new(w_squared.sqrt()).unwrap()
+}
+
+/// Cohen's w of an independence test from the joint proportions of the cells under the alternative hypothesis.
+///
+/// - `proportions[r][c]`: proportion of the cell at row `r` and column `c`, summing to 1 over the table
+/// - The null proportions are the products of the margins.
+pub fn independence_effect(proportions: &[Vec<f64>]) -> PositiveF64 {
+    let cols = proportions.first().unwrap().len();
+    assert!(proportions.len() >= 2);
+    assert!(cols >= 2);
+    proportions
+        .iter()
+        .for_each(|row| assert_eq!(row.len(), cols));
+    let total = proportions.iter().flatten().sum::<f64>();
+    assert!((total - 1.).abs() < PROPORTION_SUM_TOLERANCE);
+    let row_totals = proportions
+        .iter()
+        .map(|row| row.iter().sum::<f64>())
+        .collect::<Vec<_>>();
+    let col_totals = (0..cols)
+        .map(|c| proportions.iter().map(|row| row[c]).sum::<f64>())
+        .collect::<Vec<_>>();
+    let mut w_squared = 0.;
+    for (row, row_total) in proportions.iter().zip(&row_totals) {
+        for (&p, col_total) in row.iter().zip(&col_totals) {
+            assert!(p >= 0.);
+            let expected = row_total * col_total;
+            assert!(expected > 0.);
+            w_squared += (p - expected).powi(2) / expected;
+        }
+    }
+    PositiveF64::new(w_squared.sqrt()).unwrap()
+}
+
+/// Determine the total count for a goodness-of-fit test to reach `power` if the cell proportions are `p_1` rather than `p_0`.
+///
+/// - [`chi_square_min_count`] with [`fitness_effect`] and `k - 1` degrees of freedom
+pub fn fitness_min_count(
+    p_0: &[f64],
+    p_1: &[f64],
+    power: NormalizedF64,
+    max_p_value: NormalizedF64,
+) -> usize {
+    let w = fitness_effect(p_0, p_1);
+    let df = NonZeroUsize::new(p_0.len() - 1).unwrap();
+    chi_square_min_count(w, df, power, max_p_value)
+}
+
+/// Determine the total count for an independence test to reach `power` if the joint cell proportions are `proportions`.
+///
+/// - [`chi_square_min_count`] with [`independence_effect`] and `(r - 1) (c - 1)` degrees of freedom
+pub fn independence_min_count(
+    proportions: &[Vec<f64>],
+    power: NormalizedF64,
+    max_p_value: NormalizedF64,
+) -> usize {
+    let w = independence_effect(proportions);
+    let rows = proportions.len();
+    let cols = proportions.first().unwrap().len();
+    let df = NonZeroUsize::new((rows - 1) * (cols - 1)).unwrap();
+    chi_square_min_count(w, df, power, max_p_value)
+}
+
+/// Slack for proportions summing to 1 despite rounding errors
+const PROPORTION_SUM_TOLERANCE: f64 = 1e-9;
+
+/// Minimum detectable difference in means of a t-test.
+///
+/// The smallest mean difference, in the units of `standard_deviation`, that reaches `power` with `n` observations.
+///
+/// - `n`: as in [`t_test_power`]
+pub fn mde_mean(
+    design: TTestDesign,
+    standard_deviation: PositiveF64,
+    n: NonZeroUsize,
+    power: NormalizedF64,
+    max_p_value: NormalizedF64,
+    alternative: Alternative,
+) -> PositiveF64 {
+    let sign = match alternative {
+        Alternative::TwoSided | Alternative::Greater => 1.,
+        Alternative::Less => -1.,
+    };
+    let power_at = |d: f64| {
+        let d = FiniteF64::new(sign * d).unwrap();
+        t_test_power(design, d, n, max_p_value, alternative).get() - power.get()
+    };
+    let mut upper = 1.;
+    while power_at(upper) < 0. {
+        upper *= 2.;
+    }
+    let effect_size = bisect(power_at, 0., upper);
+    PositiveF64::new(effect_size * standard_deviation.get()).unwrap()
+}
+
+/// Power of a two-sample z-test of proportions with `n` observations in each sample, from the normal approximation.
+///
+/// - The null standard error pools the two proportions.
+/// - A two-sided test ignores rejections in the direction opposite to the true difference.
+/// - Proportions of `0` and `1` leave no sampling variation: the power is `1` if they differ in the tested direction and `0` otherwise.
+pub fn proportion_power(
+    proportion_1: NormalizedF64,
+    proportion_2: NormalizedF64,
+    n: NonZeroUsize,
+    alpha: NormalizedF64,
+    alternative: Alternative,
+) -> NormalizedF64 {
+    let (p_1, p_2) = (proportion_1.get(), proportion_2.get());
+    let n = n.get() as f64;
+    let (tail_p_value, diff) = match alternative {
+        Alternative::TwoSided => (alpha.get() / 2., (p_2 - p_1).abs()),
+        Alternative::Greater => (alpha.get(), p_2 - p_1),
+        Alternative::Less => (alpha.get(), p_1 - p_2),
+    };
+    let reject_region_extension = normal_quantile(1. - tail_p_value);
+    let pooled = (p_1 + p_2) / 2.;
+    let null_deviation = (2. * pooled * (1. - pooled)).sqrt();
+    let alt_deviation = (p_1 * (1. - p_1) + p_2 * (1. - p_2)).sqrt();
+    if alt_deviation == 0. {
+        let power = if diff > 0. { 1. } else { 0. };
+        return NormalizedF64::new(power).unwrap();
+    }
+    let z = (diff * n.sqrt() - reject_region_extension * null_deviation) / alt_deviation;
+    NormalizedF64::new(normal_cdf(z)).unwrap()
+}
+
+/// Minimum detectable difference between two proportions with `n` observations in each sample.
+///
+/// The smallest change from `proportion_1` that a two-sample z-test detects with `power`; an increase unless `alternative` is [`Alternative::Less`].
+pub fn mde_proportion(
+    proportion_1: NormalizedF64,
+    n: NonZeroUsize,
+    power: NormalizedF64,
+    max_p_value: NormalizedF64,
+    alternative: Alternative,
+) -> NormalizedF64 {
+    let p_1 = proportion_1.get();
+    let (sign, room) = match alternative {
+        Alternative::TwoSided | Alternative::Greater => (1., 1. - p_1),
+        Alternative::Less => (-1., p_1),
+    };
+    let power_at = |diff: f64| {
+        let p_2 = NormalizedF64::new(p_1 + sign * diff).unwrap();
+        proportion_power(proportion_1, p_2, n, max_p_value, alternative).get() - power.get()
+    };
+    assert!(power_at(room) >= 0.);
+    NormalizedF64::new(bisect(power_at, 0., room)).unwrap()
+}
+
+/// A test design whose power depends on the sample size
+#[derive(Debug, Clone, Copy)]
+pub enum PowerDesign {
+    /// [`t_test_power`]
+    TTest {
+        design: TTestDesign,
+        effect_size: FiniteF64,
+        alternative: Alternative,
+    },
+    /// [`proportion_power`]
+    Proportions {
+        proportion_1: NormalizedF64,
+        proportion_2: NormalizedF64,
+        alternative: Alternative,
+    },
+    /// [`anova_power`]
+    Anova {
+        groups_effect: PositiveF64,
+        k: NonZeroUsize,
+    },
+}
+impl PowerDesign {
+    /// Power with `n` observations, counted as by the function of each design
+    pub fn power(&self, n: NonZeroUsize, alpha: NormalizedF64) -> NormalizedF64 {
+        match *self {
+            PowerDesign::TTest {
+                design,
+                effect_size,
+                alternative,
+            } => t_test_power(design, effect_size, n, alpha, alternative),
+            PowerDesign::Proportions {
+                proportion_1,
+                proportion_2,
+                alternative,
+            } => proportion_power(proportion_1, proportion_2, n, alpha, alternative),
+            PowerDesign::Anova { groups_effect, k } => anova_power(groups_effect, k, n, alpha),
+        }
+    }
+}
+
+/// Power at each sample size in `n_range`, e.g., for plotting power against the sample size.
+///
+/// - `n_range`: e.g., `(10..=100).step_by(10)`; each `n` must be valid for the design
+pub fn curve(
+    design: PowerDesign,
+    n_range: impl IntoIterator<Item = usize>,
+    alpha: NormalizedF64,
+) -> Vec<(usize, NormalizedF64)> {
+    n_range
+        .into_iter()
+        .map(|n| (n, design.power(NonZeroUsize::new(n).unwrap(), alpha)))
+        .collect()
+}
+
+/// The smallest count of at least 2 whose power reaches `power`, given power increases with the count
+fn min_count(power_at: impl Fn(usize) -> f64, power: NormalizedF64) -> usize {
+    assert!(power.get() < 1.);
+    let mut upper = 2;
+    while power_at(upper) < power.get() {
+        upper *= 2;
+    }
+    let mut lower = upper / 2;
+    if lower < 2 {
+        return upper;
+    }
+    // `power_at(lower) < power <= power_at(upper)`
+    while upper - lower > 1 {
+        let middle = (lower + upper) / 2;
+        if power_at(middle) < power.get() {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+    upper
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_t_test_power() {
+        let d = FiniteF64::new(0.5).unwrap();
+        let alpha = NormalizedF64::new(0.05).unwrap();
+
+        let power = t_test_power(
+            TTestDesign::TwoSample,
+            d,
+            NonZeroUsize::new(64).unwrap(),
+            alpha,
+            Alternative::TwoSided,
+        );
+        assert!((power.get() - 0.801_459_6).abs() < 1e-6);
+
+        let power = t_test_power(
+            TTestDesign::TwoSample,
+            d,
+            NonZeroUsize::new(64).unwrap(),
+            alpha,
+            Alternative::Greater,
+        );
+        assert!((power.get() - 0.878_664_2).abs() < 1e-6);
+
+        let power = t_test_power(
+            TTestDesign::Paired,
+            d,
+            NonZeroUsize::new(34).unwrap(),
+            alpha,
+            Alternative::TwoSided,
+        );
+        assert!((power.get() - 0.807_777_5).abs() < 1e-6);
+
+        // Testing the wrong direction has almost no power
+        let power = t_test_power(
+            TTestDesign::OneSample,
+            d,
+            NonZeroUsize::new(34).unwrap(),
+            alpha,
+            Alternative::Less,
+        );
+        assert!(power.get() < 0.001);
+    }
+
+    #[test]
+    fn test_anova_power() {
+        let f = PositiveF64::new(0.25).unwrap();
+        let k = NonZeroUsize::new(4).unwrap();
+        let alpha = NormalizedF64::new(0.05).unwrap();
+        let power = anova_power(f, k, NonZeroUsize::new(20).unwrap(), alpha);
+        assert!((power.get() - 0.420_39).abs() < 1e-5);
+
+        let power = NormalizedF64::new(0.8).unwrap();
+        assert_eq!(anova_min_count_per_group(f, k, power, alpha), 45);
+    }
+
+    #[test]
+    fn test_chi_square_power() {
+        let w = PositiveF64::new(0.3).unwrap();
+        let df = NonZeroUsize::new(5).unwrap();
+        let alpha = NormalizedF64::new(0.05).unwrap();
+        let power = chi_square_power(w, df, NonZeroUsize::new(100).unwrap(), alpha);
+        assert!((power.get() - 0.623_584_6).abs() < 1e-6);
+
+        let power = NormalizedF64::new(0.8).unwrap();
+        assert_eq!(chi_square_min_count(w, df, power, alpha), 143);
+    }
+
+    #[test]
+    fn test_contingency_min_count() {
+        let power = NormalizedF64::new(0.8).unwrap();
+        let alpha = NormalizedF64::new(0.05).unwrap();
+
+        let p_0 = [0.25; 4];
+        let p_1 = [0.3, 0.3, 0.2, 0.2];
+        assert!((fitness_effect(&p_0, &p_1).get() - 0.2).abs() < 1e-12);
+        assert_eq!(fitness_min_count(&p_0, &p_1, power, alpha), 273);
+
+        let proportions = [vec![0.3, 0.2], vec![0.1, 0.4]];
+        let w = independence_effect(&proportions);
+        assert!((w.get() - (1_f64 / 6.).sqrt()).abs() < 1e-12);
+        assert_eq!(independence_min_count(&proportions, power, alpha), 48);
+    }
+
+    #[test]
+    fn test_mde_mean() {
+        let standard_deviation = PositiveF64::new(2.).unwrap();
+        let n = NonZeroUsize::new(64).unwrap();
+        let power = NormalizedF64::new(0.8).unwrap();
+        let alpha = NormalizedF64::new(0.05).unwrap();
+        let mde = mde_mean(
+            TTestDesign::TwoSample,
+            standard_deviation,
+            n,
+            power,
+            alpha,
+            Alternative::TwoSided,
+        );
+        // d = 0.5 already has power 0.8015
+        assert!(0.98 < mde.get() && mde.get() < 1.);
+        let d = FiniteF64::new(mde.get() / 2.).unwrap();
+        let power_at_mde = t_test_power(TTestDesign::TwoSample, d, n, alpha, Alternative::TwoSided);
+        assert!((power_at_mde.get() - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_mde_proportion() {
+        let proportion_1 = NormalizedF64::new(0.1).unwrap();
+        let n = NonZeroUsize::new(1000).unwrap();
+        let power = NormalizedF64::new(0.8).unwrap();
+        let alpha = NormalizedF64::new(0.05).unwrap();
+        let mde = mde_proportion(proportion_1, n, power, alpha, Alternative::TwoSided);
+        assert!((mde.get() - 0.040_745_154).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_curve() {
+        let alpha = NormalizedF64::new(0.05).unwrap();
+        let design = PowerDesign::TTest {
+            design: TTestDesign::TwoSample,
+            effect_size: FiniteF64::new(0.5).unwrap(),
+            alternative: Alternative::TwoSided,
+        };
+        let points = curve(design, (16..=64).step_by(16), alpha);
+        assert_eq!(
+            points.iter().map(|&(n, _)| n).collect::<Vec<_>>(),
+            [16, 32, 48, 64]
+        );
+        assert!(points.windows(2).all(|w| w[0].1 < w[1].1));
+        assert!((points[3].1.get() - 0.801_459_6).abs() < 1e-6);
+
+        let design = PowerDesign::Anova {
+            groups_effect: PositiveF64::new(0.25).unwrap(),
+            k: NonZeroUsize::new(4).unwrap(),
+        };
+        let points = curve(design, [20], alpha);
+        assert!((points[0].1.get() - 0.420_39).abs() < 1e-5);
+
+        let design = PowerDesign::Proportions {
+            proportion_1: NormalizedF64::new(0.1).unwrap(),
+            proportion_2: NormalizedF64::new(0.2).unwrap(),
+            alternative: Alternative::TwoSided,
+        };
+        let points = curve(design, [200], alpha);
+        assert!((points[0].1.get() - 0.802_048_372).abs() < 1e-9);
+
+        let design = PowerDesign::Proportions {
+            proportion_1: NormalizedF64::new(1.).unwrap(),
+            proportion_2: NormalizedF64::new(1.).unwrap(),
+            alternative: Alternative::TwoSided,
+        };
+        assert_eq!(curve(design, [200], alpha)[0].1.get(), 0.);
+    }
+}
@@ -0,0 +1,690 @@
+use std::num::NonZeroUsize;
+
+#[cfg(feature = "nalgebra")]
+use nalgebra::{DMatrix, DVector};
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+#[cfg(feature = "nalgebra")]
+use crate::distributions::t::StudentT;
+use crate::{
+    distributions::{chi_square::CHI_SQUARE_TABLE, normal::Z_SCORE_TABLE},
+    linear_algebra::{inverse_symmetric, solve_symmetric, Matrix},
+    model::FittedModel,
+    special::ln_gamma,
+};
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coefficient {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub estimate: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub standard_error: PositiveF64,
+    /// Wald statistic
+    ///
+    /// - Compared against the t distribution for [`ols`] and the standard normal otherwise.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub z: FiniteF64,
+    /// Null hypothesis: the coefficient is zero
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub p_value: NormalizedF64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoissonRegression {
+    /// In the column order of the design matrix
+    pub coefficients: Vec<Coefficient>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub deviance: PositiveF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub log_likelihood: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub pearson_chi_square: PositiveF64,
+    pub df_residual: NonZeroUsize,
+    /// Pearson chi-square over the residual degrees of freedom
+    ///
+    /// Well above 1 indicates overdispersion.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub dispersion: PositiveF64,
+    /// Null hypothesis: the counts are not overdispersed relative to the Poisson model
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub overdispersion_p_value: NormalizedF64,
+}
+
+/// Poisson regression with the log link, fitted by iteratively reweighted least squares.
+///
+/// - `design`: one row per observation; include a column of ones for an intercept
+/// - `offset`: added to the linear predictor, e.g. the log of each observation's exposure for rate models
+pub fn poisson(design: &[Vec<f64>], counts: &[usize], offset: Option<&[f64]>) -> PoissonRegression {
+    let n = design.len();
+    assert_eq!(counts.len(), n);
+    let p = design.first().unwrap().len();
+    design.iter().for_each(|row| assert_eq!(row.len(), p));
+    let zeros = vec![0.; n];
+    let offset = offset.unwrap_or(&zeros);
+    assert_eq!(offset.len(), n);
+    let df_residual = NonZeroUsize::new(n.checked_sub(p).unwrap()).unwrap();
+
+    let y = counts.iter().map(|&count| count as f64).collect::<Vec<_>>();
+    let mut mu = y.iter().map(|y| y + 0.1).collect::<Vec<_>>();
+    let mut beta = vec![0.; p];
+    let mut deviance = poisson_deviance(&y, &mu);
+    let mut converged = false;
+    for _ in 0..MAX_IRLS_ITERATIONS {
+        // Working response of the log link; the working weights are `mu`
+        let working = (0..n)
+            .map(|i| mu[i].ln() - offset[i] + (y[i] - mu[i]) / mu[i])
+            .collect::<Vec<_>>();
+        let information = weighted_cross_product(design, &mu);
+        let score = weighted_cross_product_vector(design, &mu, &working);
+        beta = solve_symmetric(&information, &score).unwrap();
+
+        mu = (0..n)
+            .map(|i| (linear_predictor(&design[i], &beta) + offset[i]).exp())
+            .collect();
+        let new_deviance = poisson_deviance(&y, &mu);
+        converged = (new_deviance - deviance).abs() <= IRLS_TOLERANCE * (new_deviance + 0.1);
+        deviance = new_deviance;
+        if converged {
+            break;
+        }
+    }
+    assert!(converged);
+
+    let covariance = inverse_symmetric(&weighted_cross_product(design, &mu)).unwrap();
+    let coefficients = beta
+        .iter()
+        .enumerate()
+        .map(|(j, &estimate)| wald(estimate, covariance[j][j]))
+        .collect();
+
+    let pearson_chi_square = (0..n).map(|i| (y[i] - mu[i]).powi(2) / mu[i]).sum();
+    let dispersion = pearson_chi_square / df_residual.get() as f64;
+    let log_likelihood = (0..n)
+        .map(|i| y[i] * mu[i].ln() - mu[i] - ln_gamma(y[i] + 1.))
+        .sum::<f64>();
+    PoissonRegression {
+        coefficients,
+        deviance: PositiveF64::new(deviance.max(0.)).unwrap(),
+        log_likelihood: FiniteF64::new(log_likelihood).unwrap(),
+        pearson_chi_square: PositiveF64::new(pearson_chi_square).unwrap(),
+        df_residual,
+        dispersion: PositiveF64::new(dispersion).unwrap(),
+        overdispersion_p_value: CHI_SQUARE_TABLE.p_value(df_residual, pearson_chi_square),
+    }
+}
+
+impl FittedModel for PoissonRegression {
+    fn log_likelihood(&self) -> FiniteF64 {
+        self.log_likelihood
+    }
+
+    fn parameter_count(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    fn observation_count(&self) -> NonZeroUsize {
+        self.df_residual
+            .checked_add(self.coefficients.len())
+            .unwrap()
+    }
+}
+
+/// Ordinary least squares fit
+#[cfg(feature = "nalgebra")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LinearRegression {
+    /// In the column order of the design matrix
+    pub coefficients: Vec<Coefficient>,
+    /// Covariance matrix of the estimates, `s^2 (X^T X)^-1`
+    pub covariance: DMatrix<f64>,
+    pub residuals: DVector<f64>,
+    /// Unbiased estimate `s^2` of the error variance
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub residual_variance: PositiveF64,
+    pub df_residual: NonZeroUsize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub r_squared: NormalizedF64,
+}
+#[cfg(feature = "nalgebra")]
+impl LinearRegression {
+    /// t test of the linear combination `contrast^T beta` of the coefficients
+    ///
+    /// Null hypothesis: the combination is zero.
+    pub fn contrast(&self, contrast: &DVector<f64>) -> Coefficient {
+        let (estimate, variance) =
+            linear_combination(&self.coefficients, &self.covariance, contrast);
+        t_wald(estimate, variance, self.df_residual)
+    }
+}
+
+/// Gaussian likelihood with the maximum likelihood error variance `SSE / n`, counted as a parameter
+///
+/// - An exact fit floors the variance at the smallest positive `f64`, keeping its unbounded likelihood finite.
+#[cfg(feature = "nalgebra")]
+impl FittedModel for LinearRegression {
+    fn log_likelihood(&self) -> FiniteF64 {
+        let n = self.residuals.len() as f64;
+        let variance = (self.residuals.norm_squared() / n).max(f64::MIN_POSITIVE);
+        let log_likelihood = -n / 2. * ((2. * std::f64::consts::PI * variance).ln() + 1.);
+        FiniteF64::new(log_likelihood).unwrap()
+    }
+
+    fn parameter_count(&self) -> usize {
+        self.coefficients.len() + 1
+    }
+
+    fn observation_count(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.residuals.len()).unwrap()
+    }
+}
+
+/// Ordinary least squares by the QR decomposition of the design matrix.
+///
+/// - `design`: one row per observation; include a column of ones for an intercept
+/// - Panics if the design matrix is rank deficient, to within a tolerance relative to its scale.
+/// - An exact fit has zero standard errors, and every nonzero coefficient has a p-value of zero.
+#[cfg(feature = "nalgebra")]
+pub fn ols(design: &DMatrix<f64>, y: &DVector<f64>) -> LinearRegression {
+    let (n, p) = design.shape();
+    assert_eq!(y.len(), n);
+    let df_residual = NonZeroUsize::new(n.checked_sub(p).unwrap()).unwrap();
+
+    let qr = design.clone().qr();
+    let r = qr.r();
+    let scale = r.diagonal().iter().fold(0_f64, |max, d| max.max(d.abs()));
+    let tolerance = scale * n.max(p) as f64 * f64::EPSILON;
+    assert!(r.diagonal().iter().all(|d| d.abs() > tolerance));
+    let beta = r.solve_upper_triangular(&(qr.q().transpose() * y)).unwrap();
+    let residuals = y - design * &beta;
+    let sse = residuals.norm_squared();
+    let residual_variance = sse / df_residual.get() as f64;
+
+    // `(X^T X)^-1 = R^-1 R^-T`
+    let r_inverse = r.solve_upper_triangular(&DMatrix::identity(p, p)).unwrap();
+    let covariance = &r_inverse * r_inverse.transpose() * residual_variance;
+    let coefficients = beta
+        .iter()
+        .enumerate()
+        .map(|(j, &estimate)| t_wald(estimate, covariance[(j, j)], df_residual))
+        .collect();
+
+    let mean = y.mean();
+    let total = y.iter().map(|y| (y - mean).powi(2)).sum::<f64>();
+    let r_squared = if total == 0. { 0. } else { 1. - sse / total };
+    LinearRegression {
+        coefficients,
+        covariance,
+        residuals,
+        residual_variance: PositiveF64::new(residual_variance).unwrap(),
+        df_residual,
+        r_squared: NormalizedF64::new(r_squared.clamp(0., 1.)).unwrap(),
+    }
+}
+
+/// Logistic regression fit
+#[cfg(feature = "nalgebra")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogisticRegression {
+    /// Log odds ratios, in the column order of the design matrix
+    pub coefficients: Vec<Coefficient>,
+    /// Covariance matrix of the estimates, the inverse of the Fisher information `X^T W X`
+    pub covariance: DMatrix<f64>,
+    /// Fitted probabilities
+    pub fitted: DVector<f64>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub deviance: PositiveF64,
+}
+#[cfg(feature = "nalgebra")]
+impl LogisticRegression {
+    /// Wald test of the linear combination `contrast^T beta` of the coefficients
+    ///
+    /// Null hypothesis: the combination is zero.
+    pub fn contrast(&self, contrast: &DVector<f64>) -> Coefficient {
+        let (estimate, variance) =
+            linear_combination(&self.coefficients, &self.covariance, contrast);
+        wald(estimate, variance)
+    }
+}
+
+/// `ln L = -deviance / 2` for binary outcomes
+#[cfg(feature = "nalgebra")]
+impl FittedModel for LogisticRegression {
+    fn log_likelihood(&self) -> FiniteF64 {
+        FiniteF64::new(-self.deviance.get() / 2.).unwrap()
+    }
+
+    fn parameter_count(&self) -> usize {
+        self.coefficients.len()
+    }
+
+    fn observation_count(&self) -> NonZeroUsize {
+        NonZeroUsize::new(self.fitted.len()).unwrap()
+    }
+}
+
+/// Logistic regression with the logit link, fitted by iteratively reweighted least squares with Cholesky solves.
+///
+/// - `design`: one row per observation; include a column of ones for an intercept
+/// - Panics on complete separation, where the estimates diverge.
+#[cfg(feature = "nalgebra")]
+pub fn logistic(design: &DMatrix<f64>, outcomes: &[bool]) -> LogisticRegression {
+    let (n, p) = design.shape();
+    assert_eq!(outcomes.len(), n);
+    assert!(n > p);
+    let y = DVector::from_iterator(n, outcomes.iter().map(|&y| y as u8 as f64));
+
+    let mut beta = DVector::zeros(p);
+    let mut mu = DVector::from_element(n, 0.5);
+    let mut deviance = binomial_deviance(&y, &mu);
+    let mut converged = false;
+    for _ in 0..MAX_IRLS_ITERATIONS {
+        let weights = mu.map(|mu| mu * (1. - mu));
+        let eta = design * &beta;
+        let working = DVector::from_fn(n, |i, _| eta[i] + (y[i] - mu[i]) / weights[i]);
+        let weighted_design = DMatrix::from_fn(n, p, |i, j| weights[i] * design[(i, j)]);
+        let information = design.transpose() * &weighted_design;
+        let score = weighted_design.transpose() * working;
+        beta = information.cholesky().unwrap().solve(&score);
+
+        mu = (design * &beta).map(|eta| 1. / (1. + (-eta).exp()));
+        let new_deviance = binomial_deviance(&y, &mu);
+        converged = (new_deviance - deviance).abs() <= IRLS_TOLERANCE * (new_deviance + 0.1);
+        deviance = new_deviance;
+        if converged {
+            break;
+        }
+    }
+    assert!(converged);
+    assert!(mu.iter().all(|&mu| 0. < mu && mu < 1.));
+
+    let weighted_design = DMatrix::from_fn(n, p, |i, j| mu[i] * (1. - mu[i]) * design[(i, j)]);
+    let information = design.transpose() * weighted_design;
+    let covariance = information.cholesky().unwrap().inverse();
+    let coefficients = beta
+        .iter()
+        .enumerate()
+        .map(|(j, &estimate)| wald(estimate, covariance[(j, j)]))
+        .collect();
+    LogisticRegression {
+        coefficients,
+        covariance,
+        fitted: mu,
+        deviance: PositiveF64::new(deviance.max(0.)).unwrap(),
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+fn binomial_deviance(y: &DVector<f64>, mu: &DVector<f64>) -> f64 {
+    -2. * y
+        .iter()
+        .zip(mu.iter())
+        .map(|(&y, &mu)| if y == 1. { mu.ln() } else { (1. - mu).ln() })
+        .sum::<f64>()
+}
+
+/// Estimate and variance of `contrast^T beta`
+#[cfg(feature = "nalgebra")]
+fn linear_combination(
+    coefficients: &[Coefficient],
+    covariance: &DMatrix<f64>,
+    contrast: &DVector<f64>,
+) -> (f64, f64) {
+    assert_eq!(contrast.len(), coefficients.len());
+    let estimate = coefficients
+        .iter()
+        .zip(contrast.iter())
+        .map(|(coefficient, c)| coefficient.estimate.get() * c)
+        .sum();
+    let variance = (contrast.transpose() * covariance * contrast)[(0, 0)];
+    (estimate, variance)
+}
+
+/// Durbin–Watson statistic for first-order autocorrelation of residuals in observation order.
+///
+/// Null hypothesis: the residuals are not autocorrelated.
+///
+/// - The statistic is near 2 without autocorrelation, toward 0 with positive and toward 4 with negative autocorrelation.
+/// - The p-value comes from the large-sample normal approximation `N(2, 4 / n)`.
+pub fn durbin_watson(residuals: &[f64]) -> (PositiveF64, NormalizedF64) {
+    let n = residuals.len();
+    assert!(n >= 3);
+    let sum_of_squares = residuals.iter().map(|e| e.powi(2)).sum::<f64>();
+    assert!(sum_of_squares > 0.);
+    let sum_of_squared_differences = residuals
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).powi(2))
+        .sum::<f64>();
+    let d = sum_of_squared_differences / sum_of_squares;
+
+    let z = (d - 2.) / (4. / n as f64).sqrt();
+    let z = FiniteF64::new(z).unwrap();
+    (
+        PositiveF64::new(d).unwrap(),
+        Z_SCORE_TABLE.p_value_two_sided(z),
+    )
+}
+
+/// Breusch–Pagan test for heteroscedasticity (Koenker's studentized form).
+///
+/// Null hypothesis: the variance of the residuals does not depend on the regressors.
+///
+/// - `design`: the design matrix the residuals came from, including its column of ones
+/// - The statistic `n R^2` of regressing the squared residuals on `design` is chi-square with one degree of freedom per non-constant column.
+pub fn breusch_pagan(design: &[Vec<f64>], residuals: &[f64]) -> (PositiveF64, NormalizedF64) {
+    squared_residual_lagrange_multiplier_test(design.to_vec(), residuals)
+}
+
+/// White test for heteroscedasticity.
+///
+/// Null hypothesis: the residuals are homoscedastic.
+///
+/// Like [`breusch_pagan`], but the squared residuals are regressed on the regressors together with all of their squares and cross products.
+pub fn white(design: &[Vec<f64>], residuals: &[f64]) -> (PositiveF64, NormalizedF64) {
+    let p = design.first().unwrap().len();
+    let mut columns = (0..p)
+        .map(|j| design.iter().map(|row| row[j]).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    for j in 0..p {
+        for k in j..p {
+            let product = design.iter().map(|row| row[j] * row[k]).collect::<Vec<_>>();
+            // Products with the column of ones and squares of indicators repeat existing columns
+            if !columns.contains(&product) {
+                columns.push(product);
+            }
+        }
+    }
+    let augmented = (0..design.len())
+        .map(|i| columns.iter().map(|column| column[i]).collect())
+        .collect();
+    squared_residual_lagrange_multiplier_test(augmented, residuals)
+}
+
+fn squared_residual_lagrange_multiplier_test(
+    design: Matrix,
+    residuals: &[f64],
+) -> (PositiveF64, NormalizedF64) {
+    let n = design.len();
+    assert_eq!(residuals.len(), n);
+    let p = design.first().unwrap().len();
+    assert!(n > p);
+
+    let squared = residuals.iter().map(|e| e.powi(2)).collect::<Vec<_>>();
+    let r_squared = r_squared(&design, &squared);
+    let lm = n as f64 * r_squared;
+
+    let constant_columns = (0..p)
+        .filter(|&j| design.iter().all(|row| row[j] == design[0][j]))
+        .count();
+    assert_eq!(constant_columns, 1);
+    let df = NonZeroUsize::new(p - constant_columns).unwrap();
+    (
+        PositiveF64::new(lm).unwrap(),
+        CHI_SQUARE_TABLE.p_value(df, lm),
+    )
+}
+
+/// Coefficient of determination of the least squares fit of `y` on `design`
+fn r_squared(design: &[Vec<f64>], y: &[f64]) -> f64 {
+    let ones = vec![1.; y.len()];
+    let beta = solve_symmetric(
+        &weighted_cross_product(design, &ones),
+        &weighted_cross_product_vector(design, &ones, y),
+    )
+    .unwrap();
+    let mean = y.iter().sum::<f64>() / y.len() as f64;
+    let total = y.iter().map(|y| (y - mean).powi(2)).sum::<f64>();
+    if total == 0. {
+        return 0.;
+    }
+    let residual = design
+        .iter()
+        .zip(y)
+        .map(|(row, y)| (y - linear_predictor(row, &beta)).powi(2))
+        .sum::<f64>();
+    (1. - residual / total).clamp(0., 1.)
+}
+
+/// [`wald`] against the t distribution with `df` degrees of freedom
+#[cfg(feature = "nalgebra")]
+fn t_wald(estimate: f64, variance: f64, df: NonZeroUsize) -> Coefficient {
+    let mut coefficient = wald(estimate, variance);
+    if coefficient.standard_error.get() == 0. {
+        return coefficient;
+    }
+    let t = StudentT {
+        df: PositiveF64::new(df.get() as f64).unwrap(),
+    };
+    let tail = t
+        .cdf(FiniteF64::new(-coefficient.z.get().abs()).unwrap())
+        .get();
+    coefficient.p_value = NormalizedF64::new((2. * tail).min(1.)).unwrap();
+    coefficient
+}
+
+const IRLS_TOLERANCE: f64 = 1e-10;
+const MAX_IRLS_ITERATIONS: usize = 100;
+
+/// A zero `variance` gives the limiting `z` of `±f64::MAX` and a p-value of zero, or `z = 0` and a p-value of one for a zero estimate
+fn wald(estimate: f64, variance: f64) -> Coefficient {
+    let standard_error = variance.max(0.).sqrt();
+    if standard_error == 0. {
+        let (z, p_value) = if estimate == 0. {
+            (0., 1.)
+        } else {
+            (estimate.signum() * f64::MAX, 0.)
+        };
+        return Coefficient {
+            estimate: FiniteF64::new(estimate).unwrap(),
+            standard_error: PositiveF64::new(0.).unwrap(),
+            z: FiniteF64::new(z).unwrap(),
+            p_value: NormalizedF64::new(p_value).unwrap(),
+        };
+    }
+    let z = estimate / standard_error;
+    let z = FiniteF64::new(z).unwrap();
+    Coefficient {
+        estimate: FiniteF64::new(estimate).unwrap(),
+        standard_error: PositiveF64::new(standard_error).unwrap(),
+        z,
+        p_value: Z_SCORE_TABLE.p_value_two_sided(z),
+    }
+}
+
+fn poisson_deviance(y: &[f64], mu: &[f64]) -> f64 {
+    2. * y
+        .iter()
+        .zip(mu)
+        .map(|(&y, &mu)| {
+            let log_ratio = if y == 0. { 0. } else { y * (y / mu).ln() };
+            log_ratio - (y - mu)
+        })
+        .sum::<f64>()
+}
+
+fn linear_predictor(row: &[f64], beta: &[f64]) -> f64 {
+    row.iter().zip(beta).map(|(x, b)| x * b).sum()
+}
+
+/// `X^T W X`
+fn weighted_cross_product(design: &[Vec<f64>], weights: &[f64]) -> Matrix {
+    let p = design[0].len();
+    let mut product = vec![vec![0.; p]; p];
+    design.iter().zip(weights).for_each(|(row, w)| {
+        (0..p).for_each(|j| (0..p).for_each(|k| product[j][k] += w * row[j] * row[k]));
+    });
+    product
+}
+
+/// `X^T W z`
+fn weighted_cross_product_vector(design: &[Vec<f64>], weights: &[f64], z: &[f64]) -> Vec<f64> {
+    let p = design[0].len();
+    let mut product = vec![0.; p];
+    design
+        .iter()
+        .zip(weights.iter().zip(z))
+        .for_each(|(row, (w, z))| (0..p).for_each(|j| product[j] += w * row[j] * z));
+    product
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poisson() {
+        // With a single binary predictor the fit is closed-form:
+        // the intercept is the log mean of group 0 and the slope is the log ratio of the means
+        let group_0 = [2, 3, 6, 7, 8, 9, 10, 12, 15];
+        let group_1 = [1, 0, 2, 3, 1, 2, 4, 2, 1];
+        let counts = group_0.iter().chain(&group_1).copied().collect::<Vec<_>>();
+        let design = (0..counts.len())
+            .map(|i| vec![1., (i >= group_0.len()) as u8 as f64])
+            .collect::<Vec<_>>();
+        let fit = poisson(&design, &counts, None);
+
+        let sum_0 = group_0.iter().sum::<usize>() as f64;
+        let sum_1 = group_1.iter().sum::<usize>() as f64;
+        let intercept = fit.coefficients[0];
+        let slope = fit.coefficients[1];
+        assert!((intercept.estimate.get() - (sum_0 / 9.).ln()).abs() < 1e-8);
+        assert!((slope.estimate.get() - (sum_1 / sum_0).ln()).abs() < 1e-8);
+        assert!((intercept.standard_error.get() - (1. / sum_0).sqrt()).abs() < 1e-8);
+        assert!((slope.standard_error.get() - (1. / sum_0 + 1. / sum_1).sqrt()).abs() < 1e-8);
+        assert!(slope.p_value.get() < 0.05);
+        assert_eq!(fit.df_residual.get(), 16);
+        assert!(fit.dispersion.get() > 1.);
+
+        // The likelihood-ratio statistic is the drop in deviance
+        let intercept_only = vec![vec![1.]; counts.len()];
+        let null = poisson(&intercept_only, &counts, None);
+        let result = crate::model::compare_nested(&null, &fit);
+        let drop = null.deviance.get() - fit.deviance.get();
+        assert!((result.statistic.get() - drop).abs() < 1e-8);
+        assert_eq!(fit.observation_count().get(), 18);
+    }
+
+    #[test]
+    fn test_poisson_offset() {
+        let counts = [3, 10, 4, 9];
+        let exposure = [1000_f64, 3000., 1500., 2500.];
+        let offset = exposure.iter().map(|e| e.ln()).collect::<Vec<_>>();
+        let design = vec![vec![1.]; counts.len()];
+        let fit = poisson(&design, &counts, Some(&offset));
+        let rate = 26_f64 / 8000.;
+        assert!((fit.coefficients[0].estimate.get() - rate.ln()).abs() < 1e-8);
+        assert!(fit.overdispersion_p_value.get() > 0.05);
+    }
+
+    #[test]
+    fn test_durbin_watson() {
+        let alternating = (0..50)
+            .map(|i| if i % 2 == 0 { 1. } else { -1. })
+            .collect::<Vec<_>>();
+        let (d, p) = durbin_watson(&alternating);
+        assert!((d.get() - 4. * 49. / 50.).abs() < 1e-12);
+        assert!(p.get() < 0.05);
+
+        let trending = (0..50).map(|i| i as f64 - 24.5).collect::<Vec<_>>();
+        let (d, p) = durbin_watson(&trending);
+        assert!(d.get() < 0.1);
+        assert!(p.get() < 0.05);
+    }
+
+    #[test]
+    fn test_breusch_pagan() {
+        let x = (1..=60).map(|i| i as f64).collect::<Vec<_>>();
+        let design = x.iter().map(|&x| vec![1., x]).collect::<Vec<_>>();
+        let signs = (0..60).map(|i| if (i * 7) % 3 == 0 { 1. } else { -1. });
+
+        // Spread grows with `x`
+        let residuals = x
+            .iter()
+            .zip(signs.clone())
+            .map(|(x, s)| s * x)
+            .collect::<Vec<_>>();
+        let (_, p) = breusch_pagan(&design, &residuals);
+        assert!(p.get() < 0.05);
+        let (_, p) = white(&design, &residuals);
+        assert!(p.get() < 0.05);
+
+        // Spread cycles independently of `x`
+        let residuals = signs
+            .enumerate()
+            .map(|(i, s)| s * (1 + i % 3) as f64)
+            .collect::<Vec<_>>();
+        let (_, p) = breusch_pagan(&design, &residuals);
+        assert!(p.get() > 0.05);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_ols() {
+        let x = [1., 2., 3., 4., 5., 6.];
+        let y = DVector::from_vec(vec![1.1, 2.3, 2.8, 4.1, 4.9, 6.2]);
+        let design = DMatrix::from_fn(x.len(), 2, |i, j| if j == 0 { 1. } else { x[i] });
+        let fit = ols(&design, &y);
+
+        // Closed form of simple linear regression
+        let mean_x = 3.5;
+        let mean_y = y.mean();
+        let s_xx = x.iter().map(|x| (x - mean_x).powi(2)).sum::<f64>();
+        let s_xy = (0..6)
+            .map(|i| (x[i] - mean_x) * (y[i] - mean_y))
+            .sum::<f64>();
+        let slope = s_xy / s_xx;
+        let s_2 = fit.residuals.norm_squared() / 4.;
+        assert!((fit.coefficients[1].estimate.get() - slope).abs() < 1e-12);
+        assert!((fit.coefficients[0].estimate.get() - (mean_y - slope * mean_x)).abs() < 1e-12);
+        assert!((fit.coefficients[1].standard_error.get() - (s_2 / s_xx).sqrt()).abs() < 1e-12);
+        assert!((fit.residual_variance.get() - s_2).abs() < 1e-12);
+        assert!(fit.r_squared.get() > 0.99);
+        assert!(fit.coefficients[1].p_value.get() < 1e-4);
+
+        // The fitted mean at `x = 3.5` has variance `s^2 / n`
+        let at_mean = fit.contrast(&DVector::from_vec(vec![1., 3.5]));
+        assert!((at_mean.estimate.get() - mean_y).abs() < 1e-12);
+        assert!((at_mean.standard_error.get() - (s_2 / 6.).sqrt()).abs() < 1e-12);
+
+        // An exact fit
+        let fit = ols(&design, &DVector::zeros(x.len()));
+        assert_eq!(fit.coefficients[1].standard_error.get(), 0.);
+        assert_eq!(fit.coefficients[1].p_value.get(), 1.);
+        assert!(fit.aic().get() < 0.);
+        // A tiny scale is still full rank
+        let fit = ols(&(design * 1e-17), &DVector::from_fn(x.len(), |i, _| x[i]));
+        assert!(fit.coefficients[1].p_value.get() < 1e-6);
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_logistic() {
+        // With a single binary predictor the fit is closed-form:
+        // the intercept is the log odds of group 0 and the slope is the log odds ratio
+        let (a, b, c, d) = (12, 8, 5, 15);
+        let mut outcomes = vec![];
+        let mut group = vec![];
+        for (count, outcome, g) in [(a, true, 0.), (b, false, 0.), (c, true, 1.), (d, false, 1.)] {
+            outcomes.extend(std::iter::repeat_n(outcome, count));
+            group.extend(std::iter::repeat_n(g, count));
+        }
+        let design = DMatrix::from_fn(group.len(), 2, |i, j| if j == 0 { 1. } else { group[i] });
+        let fit = logistic(&design, &outcomes);
+
+        let (a, b, c, d) = (a as f64, b as f64, c as f64, d as f64);
+        let intercept = fit.coefficients[0];
+        let slope = fit.coefficients[1];
+        assert!((intercept.estimate.get() - (a / b).ln()).abs() < 1e-8);
+        assert!((slope.estimate.get() - (b * c / (a * d)).ln()).abs() < 1e-8);
+        let se = (1. / a + 1. / b + 1. / c + 1. / d).sqrt();
+        assert!((slope.standard_error.get() - se).abs() < 1e-8);
+
+        // The log odds of group 1
+        let group_1 = fit.contrast(&DVector::from_vec(vec![1., 1.]));
+        assert!((group_1.estimate.get() - (c / d).ln()).abs() < 1e-8);
+        assert!((group_1.standard_error.get() - (1. / c + 1. / d).sqrt()).abs() < 1e-8);
+    }
+}
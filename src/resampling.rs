@@ -0,0 +1,1092 @@
+#[cfg(feature = "rayon")]
+use rand::{rngs::StdRng, SeedableRng};
+use rand::{seq::SliceRandom, Rng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    descriptive::quantile_of_sorted, distributions::normal::Z_SCORE_TABLE, special::normal_quantile,
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PermutationTest {
+    /// The statistic on the observed grouping
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub statistic: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub p_value: NormalizedF64,
+    /// The statistic on each random regrouping
+    pub distribution: Vec<f64>,
+}
+
+/// Two-sample permutation test of an arbitrary statistic.
+///
+/// Null hypothesis: `x` and `y` come from the same distribution, so group labels are exchangeable.
+///
+/// - `statistic`: should be centered at zero under the null hypothesis, e.g. a difference of medians
+/// - The p-value is two-sided: the proportion of regroupings whose statistic is at least as far from zero as the observed one, counting the observed grouping itself.
+pub fn permutation_test(
+    x: &[f64],
+    y: &[f64],
+    statistic: impl Fn(&[f64], &[f64]) -> f64,
+    n_perms: usize,
+    rng: &mut impl Rng,
+) -> PermutationTest {
+    assert!(!x.is_empty());
+    assert!(!y.is_empty());
+    assert!(n_perms >= 1);
+
+    let observed = statistic(x, y);
+    let mut pooled = x.iter().chain(y).copied().collect::<Vec<_>>();
+    let distribution = (0..n_perms)
+        .map(|_| {
+            pooled.shuffle(rng);
+            let (x, y) = pooled.split_at(x.len());
+            statistic(x, y)
+        })
+        .collect::<Vec<_>>();
+
+    let p_value = empirical_p_value(observed, &distribution);
+    PermutationTest {
+        statistic: FiniteF64::new(observed).unwrap(),
+        p_value,
+        distribution,
+    }
+}
+
+/// [`permutation_test`] with the regroupings spread over the Rayon thread pool.
+///
+/// - The result depends only on `seed`, not on the number of threads.
+#[cfg(feature = "rayon")]
+pub fn par_permutation_test(
+    x: &[f64],
+    y: &[f64],
+    statistic: impl Fn(&[f64], &[f64]) -> f64 + Sync,
+    n_perms: usize,
+    seed: u64,
+) -> PermutationTest {
+    assert!(!x.is_empty());
+    assert!(!y.is_empty());
+    assert!(n_perms >= 1);
+
+    let observed = statistic(x, y);
+    let pooled = x.iter().chain(y).copied().collect::<Vec<_>>();
+    let distribution = par_resample(
+        n_perms,
+        seed,
+        || pooled.clone(),
+        |pooled, rng| {
+            pooled.shuffle(rng);
+            let (x, y) = pooled.split_at(x.len());
+            statistic(x, y)
+        },
+    );
+
+    let p_value = empirical_p_value(observed, &distribution);
+    PermutationTest {
+        statistic: FiniteF64::new(observed).unwrap(),
+        p_value,
+        distribution,
+    }
+}
+
+/// Permutation F-test: one-way ANOVA without assuming normality.
+///
+/// Null hypothesis: all groups come from the same distribution, so group labels are exchangeable.
+///
+/// - The statistic is the F statistic of [`crate::numerical::anova`].
+/// - The p-value is the proportion of relabelings whose F statistic is at least the observed one, counting the observed labeling itself.
+pub fn permutation_anova(groups: &[&[f64]], n_perms: usize, rng: &mut impl Rng) -> PermutationTest {
+    assert!(groups.len() >= 2);
+    groups.iter().for_each(|group| assert!(!group.is_empty()));
+    let sizes = groups.iter().map(|group| group.len()).collect::<Vec<_>>();
+    assert!(sizes.iter().sum::<usize>() > groups.len());
+    assert!(n_perms >= 1);
+
+    let mut pooled = groups.concat();
+    let observed = f_statistic(&pooled, &sizes);
+    let distribution = (0..n_perms)
+        .map(|_| {
+            pooled.shuffle(rng);
+            f_statistic(&pooled, &sizes)
+        })
+        .collect::<Vec<_>>();
+
+    let p_value = empirical_p_value(observed, &distribution);
+    PermutationTest {
+        statistic: FiniteF64::new(observed).unwrap(),
+        p_value,
+        distribution,
+    }
+}
+
+/// PERMANOVA: permutation ANOVA of any dissimilarity between observations.
+///
+/// Null hypothesis: all groups come from the same distribution, so group labels are exchangeable.
+///
+/// - `distance_matrix[i][j]`: symmetric dissimilarity between observations `i` and `j`, zero on the diagonal
+/// - `group_labels[i]`: group of observation `i`, from `0` to `k - 1`
+/// - The statistic is the pseudo-F of the squared dissimilarities, equal to the F statistic of [`permutation_anova`] for Euclidean distances of one variable.
+/// - The p-value is the proportion of relabelings whose pseudo-F is at least the observed one, counting the observed labeling itself.
+pub fn permanova(
+    distance_matrix: &[Vec<f64>],
+    group_labels: &[usize],
+    n_perms: usize,
+    rng: &mut impl Rng,
+) -> PermutationTest {
+    let n = distance_matrix.len();
+    assert_eq!(group_labels.len(), n);
+    assert!(distance_matrix.iter().all(|row| row.len() == n));
+    let k = group_labels.iter().max().unwrap() + 1;
+    assert!(k >= 2);
+    assert!(n > k);
+    assert!(n_perms >= 1);
+
+    let squared = distance_matrix
+        .iter()
+        .map(|row| row.iter().map(|d| d.powi(2)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let pairs = || (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j)));
+    let total = pairs().map(|(i, j)| squared[i][j]).sum::<f64>() / n as f64;
+    let pseudo_f = |labels: &[usize]| {
+        let mut sizes = vec![0; k];
+        labels.iter().for_each(|&label| sizes[label] += 1);
+        assert!(sizes.iter().all(|&size| size > 0));
+        let within = pairs()
+            .filter(|&(i, j)| labels[i] == labels[j])
+            .map(|(i, j)| squared[i][j] / sizes[labels[i]] as f64)
+            .sum::<f64>();
+        let between = total - within;
+        if within == 0. {
+            return if between == 0. { 0. } else { f64::MAX };
+        }
+        (between / (k - 1) as f64) / (within / (n - k) as f64)
+    };
+
+    let observed = pseudo_f(group_labels);
+    let mut labels = group_labels.to_vec();
+    let distribution = (0..n_perms)
+        .map(|_| {
+            labels.shuffle(rng);
+            pseudo_f(&labels)
+        })
+        .collect::<Vec<_>>();
+
+    let p_value = empirical_p_value(observed, &distribution);
+    PermutationTest {
+        statistic: FiniteF64::new(observed).unwrap(),
+        p_value,
+        distribution,
+    }
+}
+
+/// Step-down procedure of Westfall and Young
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WestfallYoung {
+    /// Compare each statistic with the maximum absolute statistic of the hypotheses not yet rejected
+    MaxT,
+    /// Compare each p-value with the minimum permutation p-value of the hypotheses not yet rejected, which weighs hypotheses with differently scaled statistics equally
+    MinP,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiplePermutationTest {
+    /// The statistic of each hypothesis on the observed arrangement
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub statistics: Vec<FiniteF64>,
+    /// Two-sided permutation p-value of each hypothesis on its own
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub p_values: Vec<NormalizedF64>,
+    /// p-values controlling the family-wise error rate, to be compared against the significance level directly
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub adjusted_p_values: Vec<NormalizedF64>,
+}
+
+/// Westfall–Young permutation test of several hypotheses on the same observations.
+///
+/// Unlike [`crate::multiple_testing::adjust`], it accounts for the dependence between the statistics, so it is less conservative than Holm when they are correlated.
+///
+/// - `n`: count of observations
+/// - `statistics(order)`: the statistic of each hypothesis, centered at zero under the null hypothesis, on the observations rearranged so that position `i` holds observation `order[i]`
+/// - Every p-value counts the observed arrangement itself.
+pub fn westfall_young(
+    n: usize,
+    statistics: impl Fn(&[usize]) -> Vec<f64>,
+    method: WestfallYoung,
+    n_perms: usize,
+    rng: &mut impl Rng,
+) -> MultiplePermutationTest {
+    assert!(n >= 2);
+    assert!(n_perms >= 1);
+
+    let mut order = (0..n).collect::<Vec<_>>();
+    let observed = statistics(&order);
+    let m = observed.len();
+    assert!(m >= 1);
+    // Row 0 is the observed arrangement
+    let mut magnitudes = vec![observed.iter().map(|t| t.abs()).collect::<Vec<_>>()];
+    for _ in 0..n_perms {
+        order.shuffle(rng);
+        let permuted = statistics(&order);
+        assert_eq!(permuted.len(), m);
+        magnitudes.push(permuted.iter().map(|t| t.abs()).collect());
+    }
+    let rows = magnitudes.len() as f64;
+
+    // Proportion of arrangements at least as extreme, per arrangement and hypothesis
+    let p_values = (0..m)
+        .map(|j| {
+            let mut column = magnitudes.iter().map(|row| row[j]).collect::<Vec<_>>();
+            column.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            magnitudes
+                .iter()
+                .map(|row| {
+                    let less = column.partition_point(|&t| t < row[j]);
+                    (column.len() - less) as f64 / rows
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Evidence against each null hypothesis, larger being stronger
+    let evidence = |row: usize, j: usize| match method {
+        WestfallYoung::MaxT => magnitudes[row][j],
+        WestfallYoung::MinP => -p_values[j][row],
+    };
+    let mut hypotheses = (0..m).collect::<Vec<_>>();
+    hypotheses.sort_by(|&a, &b| evidence(0, b).partial_cmp(&evidence(0, a)).unwrap());
+
+    let mut adjusted = vec![0.; m];
+    let mut successive = vec![f64::NEG_INFINITY; magnitudes.len()];
+    for &j in hypotheses.iter().rev() {
+        successive
+            .iter_mut()
+            .enumerate()
+            .for_each(|(row, strongest)| *strongest = strongest.max(evidence(row, j)));
+        let at_least = successive.iter().filter(|&&e| e >= evidence(0, j)).count();
+        adjusted[j] = at_least as f64 / rows;
+    }
+    let mut running_max = 0_f64;
+    for &j in &hypotheses {
+        running_max = running_max.max(adjusted[j]);
+        adjusted[j] = running_max;
+    }
+
+    MultiplePermutationTest {
+        statistics: observed
+            .into_iter()
+            .map(|t| FiniteF64::new(t).unwrap())
+            .collect(),
+        p_values: p_values
+            .iter()
+            .map(|p| NormalizedF64::new(p[0]).unwrap())
+            .collect(),
+        adjusted_p_values: adjusted
+            .into_iter()
+            .map(|p| NormalizedF64::new(p).unwrap())
+            .collect(),
+    }
+}
+
+/// One-way ANOVA F statistic of `pooled` split into consecutive groups of `sizes`
+fn f_statistic(pooled: &[f64], sizes: &[usize]) -> f64 {
+    let n = pooled.len() as f64;
+    let k = sizes.len() as f64;
+    let grand_mean = pooled.iter().sum::<f64>() / n;
+    let mut between = 0.;
+    let mut within = 0.;
+    let mut start = 0;
+    for &size in sizes {
+        let group = &pooled[start..start + size];
+        start += size;
+        let mean = group.iter().sum::<f64>() / size as f64;
+        between += size as f64 * (mean - grand_mean).powi(2);
+        within += group.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+    }
+    if within == 0. {
+        return if between == 0. { 0. } else { f64::MAX };
+    }
+    (between / (k - 1.)) / (within / (n - k))
+}
+
+/// Energy-distance test of two multivariate samples, sensitive to any difference between the distributions.
+///
+/// Null hypothesis: `x` and `y` come from the same distribution, so group labels are exchangeable.
+///
+/// - Each observation is a point of the same dimension; univariate samples are points of one coordinate.
+/// - The statistic is `n m / (n + m) (2 E|X - Y| - E|X - X'| - E|Y - Y'|)` with Euclidean distances, zero for identical samples.
+/// - The p-value is the proportion of regroupings whose statistic is at least the observed one, counting the observed grouping itself.
+pub fn energy_test(
+    x: &[Vec<f64>],
+    y: &[Vec<f64>],
+    n_perms: usize,
+    rng: &mut impl Rng,
+) -> PermutationTest {
+    assert!(!x.is_empty());
+    assert!(!y.is_empty());
+    assert!(n_perms >= 1);
+
+    let pooled = x.iter().chain(y).collect::<Vec<_>>();
+    let distances = distance_matrix(&pooled);
+    let mut order = (0..pooled.len()).collect::<Vec<_>>();
+    let observed = energy_statistic(&distances, &order, x.len());
+    let distribution = (0..n_perms)
+        .map(|_| {
+            order.shuffle(rng);
+            energy_statistic(&distances, &order, x.len())
+        })
+        .collect::<Vec<_>>();
+
+    let p_value = empirical_p_value(observed, &distribution);
+    PermutationTest {
+        statistic: FiniteF64::new(observed).unwrap(),
+        p_value,
+        distribution,
+    }
+}
+
+/// Energy statistic of the points `order[..n_x]` against the points `order[n_x..]`
+fn energy_statistic(distances: &[Vec<f64>], order: &[usize], n_x: usize) -> f64 {
+    let (x, y) = order.split_at(n_x);
+    let mean_distance = |a: &[usize], b: &[usize]| mean_entry(distances, a, b);
+    let (n, m) = (x.len() as f64, y.len() as f64);
+    let energy = 2. * mean_distance(x, y) - mean_distance(x, x) - mean_distance(y, y);
+    n * m / (n + m) * energy
+}
+
+/// Maximum mean discrepancy test of two multivariate samples with a Gaussian kernel.
+///
+/// Null hypothesis: `x` and `y` come from the same distribution, so group labels are exchangeable.
+///
+/// - Each observation is a point of the same dimension; univariate samples are points of one coordinate.
+/// - `bandwidth`: `σ` of the kernel `exp(-|a - b|^2 / (2 σ^2))`; defaults to the median distance between the pooled points, or to the mean nonzero distance if most points coincide, or to 1 if all do
+/// - The statistic is the biased estimate of the squared MMD, `E k(X, X') + E k(Y, Y') - 2 E k(X, Y)`, which is never negative.
+/// - The p-value is the proportion of regroupings whose statistic is at least the observed one, counting the observed grouping itself.
+pub fn mmd_test(
+    x: &[Vec<f64>],
+    y: &[Vec<f64>],
+    bandwidth: Option<PositiveF64>,
+    n_perms: usize,
+    rng: &mut impl Rng,
+) -> PermutationTest {
+    assert!(!x.is_empty());
+    assert!(!y.is_empty());
+    assert!(n_perms >= 1);
+
+    let pooled = x.iter().chain(y).collect::<Vec<_>>();
+    let distances = distance_matrix(&pooled);
+    let bandwidth = match bandwidth {
+        Some(bandwidth) => bandwidth.get(),
+        None => {
+            let mut pairwise = (0..pooled.len())
+                .flat_map(|i| distances[i][i + 1..].iter().copied())
+                .collect::<Vec<_>>();
+            assert!(!pairwise.is_empty());
+            pairwise.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let median = quantile_of_sorted(&pairwise, 0.5);
+            let nonzero = pairwise.iter().filter(|&&d| d > 0.).collect::<Vec<_>>();
+            if median > 0. {
+                median
+            } else if nonzero.is_empty() {
+                1.
+            } else {
+                nonzero.iter().copied().sum::<f64>() / nonzero.len() as f64
+            }
+        }
+    };
+    assert!(bandwidth > 0.);
+    let kernel = distances
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|d| (-d.powi(2) / (2. * bandwidth.powi(2))).exp())
+                .collect()
+        })
+        .collect::<Vec<Vec<f64>>>();
+
+    let statistic = |order: &[usize]| {
+        let (x, y) = order.split_at(x.len());
+        let mean_kernel = |a: &[usize], b: &[usize]| mean_entry(&kernel, a, b);
+        (mean_kernel(x, x) + mean_kernel(y, y) - 2. * mean_kernel(x, y)).max(0.)
+    };
+    let mut order = (0..pooled.len()).collect::<Vec<_>>();
+    let observed = statistic(&order);
+    let distribution = (0..n_perms)
+        .map(|_| {
+            order.shuffle(rng);
+            statistic(&order)
+        })
+        .collect::<Vec<_>>();
+
+    let p_value = empirical_p_value(observed, &distribution);
+    PermutationTest {
+        statistic: FiniteF64::new(observed).unwrap(),
+        p_value,
+        distribution,
+    }
+}
+
+/// Mean of `matrix[i][j]` over `i` in `a` and `j` in `b`
+fn mean_entry(matrix: &[Vec<f64>], a: &[usize], b: &[usize]) -> f64 {
+    let sum = a
+        .iter()
+        .flat_map(|&i| b.iter().map(move |&j| matrix[i][j]))
+        .sum::<f64>();
+    sum / (a.len() * b.len()) as f64
+}
+
+/// Euclidean distance between every pair of points
+fn distance_matrix(points: &[&Vec<f64>]) -> Vec<Vec<f64>> {
+    points
+        .iter()
+        .map(|a| points.iter().map(|b| euclidean_distance(a, b)).collect())
+        .collect()
+}
+
+fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
+    assert_eq!(a.len(), b.len());
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Resamples drawn from one RNG stream; fixed so that the streams do not depend on the number of threads
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 1024;
+
+/// `n` draws in chunks of [`PARALLEL_CHUNK_SIZE`], each chunk with its own scratch state and its own RNG seeded from `seed`
+#[cfg(feature = "rayon")]
+fn par_resample<S>(
+    n: usize,
+    seed: u64,
+    scratch: impl Fn() -> S + Sync,
+    draw: impl Fn(&mut S, &mut StdRng) -> f64 + Sync,
+) -> Vec<f64> {
+    let mut seeds = StdRng::seed_from_u64(seed);
+    let chunk_seeds = (0..n.div_ceil(PARALLEL_CHUNK_SIZE))
+        .map(|_| seeds.gen::<u64>())
+        .collect::<Vec<_>>();
+    chunk_seeds
+        .into_par_iter()
+        .enumerate()
+        .map(|(i, seed)| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut scratch = scratch();
+            let len = PARALLEL_CHUNK_SIZE.min(n - i * PARALLEL_CHUNK_SIZE);
+            (0..len)
+                .map(|_| draw(&mut scratch, &mut rng))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// `(1 + #{|t| >= |t_obs|}) / (1 + n)`, which never reports an impossible zero p-value
+fn empirical_p_value(observed: f64, distribution: &[f64]) -> NormalizedF64 {
+    let extreme = distribution
+        .iter()
+        .filter(|t| t.abs() >= observed.abs())
+        .count();
+    NormalizedF64::new((1 + extreme) as f64 / (1 + distribution.len()) as f64).unwrap()
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bootstrap {
+    /// The statistic on the original data
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub estimate: FiniteF64,
+    /// The statistic on each resample, in ascending order
+    pub distribution: Vec<f64>,
+    /// BCa acceleration constant from the jackknife
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub acceleration: FiniteF64,
+}
+impl Bootstrap {
+    pub fn standard_error(&self) -> PositiveF64 {
+        let n = self.distribution.len() as f64;
+        let mean = self.distribution.iter().sum::<f64>() / n;
+        let variance = self
+            .distribution
+            .iter()
+            .map(|t| (t - mean).powi(2))
+            .sum::<f64>()
+            / (n - 1.);
+        PositiveF64::new(variance.sqrt()).unwrap()
+    }
+
+    /// The `(1 - confidence) / 2` and `(1 + confidence) / 2` quantiles of the resample distribution
+    pub fn percentile_interval(&self, confidence: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        let alpha = (1. - confidence.get()) / 2.;
+        (self.quantile(alpha), self.quantile(1. - alpha))
+    }
+
+    /// The percentile interval reflected around the estimate
+    pub fn basic_interval(&self, confidence: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        let (lower, upper) = self.percentile_interval(confidence);
+        let estimate = self.estimate.get();
+        (
+            FiniteF64::new(2. * estimate - upper.get()).unwrap(),
+            FiniteF64::new(2. * estimate - lower.get()).unwrap(),
+        )
+    }
+
+    /// Bias-corrected and accelerated interval
+    pub fn bca_interval(&self, confidence: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        // ref: Efron, B. (1987). Better bootstrap confidence intervals.
+        let below = self
+            .distribution
+            .iter()
+            .filter(|&&t| t < self.estimate.get())
+            .count();
+        // Keep the bias correction finite when every resample falls on one side
+        let n = self.distribution.len() as f64;
+        let proportion_below = (below as f64).clamp(0.5, n - 0.5) / n;
+        let bias_correction = Z_SCORE_TABLE.z(NormalizedF64::new(proportion_below).unwrap());
+
+        let alpha = (1. - confidence.get()) / 2.;
+        let adjust = |alpha: f64| {
+            let z_alpha = Z_SCORE_TABLE.z(NormalizedF64::new(alpha).unwrap()).get();
+            let z_0 = bias_correction.get();
+            let a = self.acceleration.get();
+            let z = z_0 + (z_0 + z_alpha) / (1. - a * (z_0 + z_alpha));
+            let z = FiniteF64::new(z).unwrap();
+            self.quantile(Z_SCORE_TABLE.area_up_to(z).get())
+        };
+        (adjust(alpha), adjust(1. - alpha))
+    }
+
+    fn quantile(&self, q: f64) -> FiniteF64 {
+        FiniteF64::new(quantile_of_sorted(&self.distribution, q)).unwrap()
+    }
+}
+
+/// Nonparametric bootstrap of a one-sample statistic.
+///
+/// Draws `n_resamples` samples of `data` with replacement and evaluates `statistic` on each.
+pub fn bootstrap(
+    data: &[f64],
+    statistic: impl Fn(&[f64]) -> f64,
+    n_resamples: usize,
+    rng: &mut impl Rng,
+) -> Bootstrap {
+    assert!(data.len() >= 2);
+    assert!(n_resamples >= 2);
+
+    let estimate = statistic(data);
+    let mut resample = vec![0.; data.len()];
+    let mut distribution = (0..n_resamples)
+        .map(|_| {
+            resample
+                .iter_mut()
+                .for_each(|x| *x = data[rng.gen_range(0..data.len())]);
+            statistic(&resample)
+        })
+        .collect::<Vec<_>>();
+    distribution.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    Bootstrap {
+        estimate: FiniteF64::new(estimate).unwrap(),
+        distribution,
+        acceleration: jackknife(data, &statistic).acceleration(),
+    }
+}
+
+/// [`bootstrap`] with the resamples spread over the Rayon thread pool.
+///
+/// - The result depends only on `seed`, not on the number of threads.
+#[cfg(feature = "rayon")]
+pub fn par_bootstrap(
+    data: &[f64],
+    statistic: impl Fn(&[f64]) -> f64 + Sync,
+    n_resamples: usize,
+    seed: u64,
+) -> Bootstrap {
+    assert!(data.len() >= 2);
+    assert!(n_resamples >= 2);
+
+    let estimate = statistic(data);
+    let mut distribution = par_resample(
+        n_resamples,
+        seed,
+        || vec![0.; data.len()],
+        |resample, rng| {
+            resample
+                .iter_mut()
+                .for_each(|x| *x = data[rng.gen_range(0..data.len())]);
+            statistic(resample)
+        },
+    );
+    distribution.par_sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+
+    Bootstrap {
+        estimate: FiniteF64::new(estimate).unwrap(),
+        distribution,
+        acceleration: jackknife(data, &statistic).acceleration(),
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BootstrapTest {
+    /// The statistic on the original samples
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub statistic: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub p_value: NormalizedF64,
+    /// The statistic on each pair of resamples, centered at the original estimate to mimic the null hypothesis
+    pub distribution: Vec<f64>,
+}
+
+/// Two-sample bootstrap test of the difference of medians `median(x) - median(y)`, e.g. for heavy-tailed latencies.
+///
+/// Null hypothesis: `x` and `y` have the same median.
+///
+/// - `x` and `y` are resampled separately, so their spreads may differ.
+/// - `studentized`: divide the difference by its standard error in the original samples and in each resample, which is usually more accurate for skewed data; the standard error of each median is the McKean–Schrader estimate, and resamples with a zero standard error are dropped.
+/// - The p-value is two-sided as in [`permutation_test`].
+pub fn median_difference_test(
+    x: &[f64],
+    y: &[f64],
+    n_resamples: usize,
+    studentized: bool,
+    rng: &mut impl Rng,
+) -> BootstrapTest {
+    assert!(x.len() >= 2);
+    assert!(y.len() >= 2);
+    assert!(n_resamples >= 1);
+
+    // Difference of medians and its standard error
+    let estimate = |x: &mut [f64], y: &mut [f64]| {
+        x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        y.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let difference = quantile_of_sorted(x, 0.5) - quantile_of_sorted(y, 0.5);
+        let standard_error =
+            (median_standard_error(x).powi(2) + median_standard_error(y).powi(2)).sqrt();
+        (difference, standard_error)
+    };
+    let (difference, standard_error) = estimate(&mut x.to_vec(), &mut y.to_vec());
+    if studentized {
+        assert!(standard_error > 0.);
+    }
+
+    let mut resample_x = vec![0.; x.len()];
+    let mut resample_y = vec![0.; y.len()];
+    let distribution = (0..n_resamples)
+        .filter_map(|_| {
+            resample_x
+                .iter_mut()
+                .for_each(|v| *v = x[rng.gen_range(0..x.len())]);
+            resample_y
+                .iter_mut()
+                .for_each(|v| *v = y[rng.gen_range(0..y.len())]);
+            let (resampled, resampled_error) = estimate(&mut resample_x, &mut resample_y);
+            let centered = resampled - difference;
+            if !studentized {
+                return Some(centered);
+            }
+            (resampled_error > 0.).then(|| centered / resampled_error)
+        })
+        .collect::<Vec<_>>();
+    assert!(!distribution.is_empty());
+
+    let observed = if studentized {
+        difference / standard_error
+    } else {
+        difference
+    };
+    let p_value = empirical_p_value(observed, &distribution);
+    BootstrapTest {
+        statistic: FiniteF64::new(observed).unwrap(),
+        p_value,
+        distribution,
+    }
+}
+
+/// McKean–Schrader standard error of the median of ascending values: the half-width of the distribution-free 95% interval of the median divided by `z_0.975`
+fn median_standard_error(sorted: &[f64]) -> f64 {
+    let n = sorted.len() as f64;
+    let z = normal_quantile(0.975);
+    let c = ((n + 1.) / 2. - z * (n / 4.).sqrt()).round().max(1.) as usize;
+    (sorted[sorted.len() - c] - sorted[c - 1]) / (2. * z)
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Jackknife {
+    /// The statistic on the original data
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub estimate: FiniteF64,
+    /// The statistic with each observation left out in turn
+    pub replicates: Vec<f64>,
+}
+impl Jackknife {
+    fn replicate_mean(&self) -> f64 {
+        self.replicates.iter().sum::<f64>() / self.replicates.len() as f64
+    }
+
+    /// `(n - 1) (mean of replicates - estimate)`
+    pub fn bias(&self) -> FiniteF64 {
+        let n = self.replicates.len() as f64;
+        FiniteF64::new((n - 1.) * (self.replicate_mean() - self.estimate.get())).unwrap()
+    }
+
+    pub fn bias_corrected_estimate(&self) -> FiniteF64 {
+        FiniteF64::new(self.estimate.get() - self.bias().get()).unwrap()
+    }
+
+    pub fn standard_error(&self) -> PositiveF64 {
+        let n = self.replicates.len() as f64;
+        let mean = self.replicate_mean();
+        let squares = self
+            .replicates
+            .iter()
+            .map(|t| (t - mean).powi(2))
+            .sum::<f64>();
+        PositiveF64::new(((n - 1.) / n * squares).sqrt()).unwrap()
+    }
+
+    /// BCa acceleration constant `sum (mean - t_i)^3 / (6 (sum (mean - t_i)^2)^(3/2))`
+    pub fn acceleration(&self) -> FiniteF64 {
+        let mean = self.replicate_mean();
+        let squares = self
+            .replicates
+            .iter()
+            .map(|t| (mean - t).powi(2))
+            .sum::<f64>();
+        if squares == 0. {
+            return FiniteF64::new(0.).unwrap();
+        }
+        let cubes = self
+            .replicates
+            .iter()
+            .map(|t| (mean - t).powi(3))
+            .sum::<f64>();
+        FiniteF64::new(cubes / (6. * squares.powf(1.5))).unwrap()
+    }
+}
+
+/// Leave-one-out jackknife of a one-sample statistic.
+pub fn jackknife(data: &[f64], statistic: impl Fn(&[f64]) -> f64) -> Jackknife {
+    assert!(data.len() >= 2);
+
+    let mut left_out = Vec::with_capacity(data.len() - 1);
+    let replicates = (0..data.len())
+        .map(|i| {
+            left_out.clear();
+            left_out.extend_from_slice(&data[..i]);
+            left_out.extend_from_slice(&data[i + 1..]);
+            statistic(&left_out)
+        })
+        .collect();
+    Jackknife {
+        estimate: FiniteF64::new(statistic(data)).unwrap(),
+        replicates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn difference_of_means(x: &[f64], y: &[f64]) -> f64 {
+        x.iter().sum::<f64>() / x.len() as f64 - y.iter().sum::<f64>() / y.len() as f64
+    }
+
+    #[test]
+    fn test_permutation_test() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let x = [12.1, 14.3, 13.8, 15.2, 14.9, 13.5, 14.1, 15.6];
+        let y = [10.2, 11.8, 10.9, 12.4, 11.1, 10.7, 12.0, 11.5];
+        let test = permutation_test(&x, &y, difference_of_means, 2000, &mut rng);
+        assert_eq!(test.distribution.len(), 2000);
+        assert!(test.p_value.get() < 0.05);
+
+        let test = permutation_test(&x, &x, difference_of_means, 2000, &mut rng);
+        assert_eq!(test.statistic.get(), 0.);
+        assert_eq!(test.p_value.get(), 1.);
+    }
+
+    fn mean(data: &[f64]) -> f64 {
+        data.iter().sum::<f64>() / data.len() as f64
+    }
+
+    #[test]
+    fn test_bootstrap() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let data = [
+            2.1, 3.4, 1.9, 5.6, 4.4, 3.8, 2.7, 6.1, 3.3, 4.0, 2.5, 3.9, 4.8, 3.1, 2.2, 5.0,
+        ];
+        let bootstrap = bootstrap(&data, mean, 4000, &mut rng);
+        assert_eq!(bootstrap.distribution.len(), 4000);
+
+        // The standard error of a mean is close to `s / sqrt(n)`
+        let m = mean(&data);
+        let s = (data.iter().map(|x| (x - m).powi(2)).sum::<f64>() / 15.).sqrt();
+        assert!((bootstrap.standard_error().get() - s / 4.).abs() < 0.05);
+
+        let confidence = NormalizedF64::new(0.95).unwrap();
+        for (lower, upper) in [
+            bootstrap.percentile_interval(confidence),
+            bootstrap.basic_interval(confidence),
+            bootstrap.bca_interval(confidence),
+        ] {
+            assert!(lower.get() < m && m < upper.get());
+            assert!((upper.get() - lower.get() - 2. * 1.96 * s / 4.).abs() < 0.3);
+        }
+    }
+
+    #[test]
+    fn test_permutation_anova() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let a = [4.2, 4.8, 5.1, 4.5, 4.9, 5.3];
+        let b = [5.9, 6.3, 5.7, 6.8, 6.1, 6.0];
+        let c = [4.9, 5.2, 5.6, 5.0, 5.4];
+        let test = permutation_anova(&[&a, &b, &c], 2000, &mut rng);
+        let samples = [a.as_slice(), &b, &c].map(crate::numerical::NumericalSample::from_values);
+        let anova = crate::numerical::anova_result(&samples);
+        assert!((test.statistic.get() - anova.statistic.get()).abs() < 1e-9);
+        assert!(test.p_value.get() < 0.01);
+
+        let test = permutation_anova(&[&a, &a, &a], 2000, &mut rng);
+        assert_eq!(test.statistic.get(), 0.);
+        assert_eq!(test.p_value.get(), 1.);
+    }
+
+    #[test]
+    fn test_energy_test() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let test = energy_test(&[vec![0.], vec![1.]], &[vec![3.]], 100, &mut rng);
+        assert!((test.statistic.get() - 3.).abs() < 1e-12);
+
+        // Same means, different spreads
+        let x = [
+            [-0.1, 0.2],
+            [0.3, -0.2],
+            [0.0, 0.1],
+            [-0.2, -0.1],
+            [0.1, 0.3],
+            [0.2, 0.0],
+            [-0.3, 0.1],
+            [0.0, -0.3],
+        ]
+        .map(|point| point.to_vec());
+        let y = x
+            .clone()
+            .map(|point| point.iter().map(|v| v * 10. + 0.05).collect());
+        let test = energy_test(&x, &y, 2000, &mut rng);
+        assert!(test.p_value.get() < 0.01);
+
+        let test = energy_test(&x, &x, 2000, &mut rng);
+        assert_eq!(test.statistic.get(), 0.);
+        assert_eq!(test.p_value.get(), 1.);
+    }
+
+    #[test]
+    fn test_mmd_test() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let bandwidth = Some(PositiveF64::new(1.).unwrap());
+        let test = mmd_test(&[vec![0.]], &[vec![1.]], bandwidth, 100, &mut rng);
+        assert!((test.statistic.get() - (2. - 2. * (-0.5_f64).exp())).abs() < 1e-12);
+
+        // Same means, different spreads
+        let x = [
+            -0.9, 1.1, 0.2, -0.4, 0.7, -1.3, 0.5, -0.1, 0.9, -0.6, 0.3, -0.2,
+        ]
+        .map(|v| vec![v]);
+        let y = x.clone().map(|point| vec![point[0] * 5.]);
+        let test = mmd_test(&x, &y, None, 2000, &mut rng);
+        assert!(test.p_value.get() < 0.01);
+
+        let test = mmd_test(&x, &x, None, 2000, &mut rng);
+        assert_eq!(test.statistic.get(), 0.);
+        assert_eq!(test.p_value.get(), 1.);
+
+        // Most pairwise distances are zero
+        let x = [0., 0., 0., 0., 1.].map(|v| vec![v]);
+        let y = [0., 0., 0., 0.].map(|v| vec![v]);
+        let test = mmd_test(&x, &y, None, 100, &mut rng);
+        assert!(test.statistic.get() > 0.);
+        let test = mmd_test(&y, &y, None, 100, &mut rng);
+        assert_eq!(test.statistic.get(), 0.);
+    }
+
+    #[test]
+    fn test_permanova() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let a = [4.2, 4.8, 5.1, 4.5, 4.9, 5.3];
+        let b = [5.9, 6.3, 5.7, 6.8, 6.1, 6.0];
+        let c = [4.9, 5.2, 5.6, 5.0, 5.4];
+        let values = [a.as_slice(), &b, &c].concat();
+        let labels = [[0; 6].as_slice(), &[1; 6], &[2; 5]].concat();
+        let distances = values
+            .iter()
+            .map(|x: &f64| values.iter().map(|y| (x - y).abs()).collect())
+            .collect::<Vec<Vec<f64>>>();
+        let test = permanova(&distances, &labels, 2000, &mut rng);
+        let anova = permutation_anova(&[&a, &b, &c], 1, &mut rng);
+        assert!((test.statistic.get() - anova.statistic.get()).abs() < 1e-9);
+        assert!(test.p_value.get() < 0.01);
+    }
+
+    #[test]
+    fn test_westfall_young() {
+        let x = [12.1, 14.3, 13.8, 15.2, 14.9, 13.5, 14.1, 15.6];
+        let y = [10.2, 11.8, 10.9, 12.4, 11.1, 10.7, 12.0, 11.5];
+        let pooled = [x.as_slice(), &y].concat();
+        let difference = |order: &[usize]| {
+            let arranged = order.iter().map(|&i| pooled[i]).collect::<Vec<_>>();
+            let (x, y) = arranged.split_at(x.len());
+            difference_of_means(x, y)
+        };
+
+        // A single hypothesis needs no adjustment
+        let test = westfall_young(
+            pooled.len(),
+            |order| vec![difference(order)],
+            WestfallYoung::MaxT,
+            2000,
+            &mut StdRng::seed_from_u64(0),
+        );
+        let single = permutation_test(
+            &x,
+            &y,
+            difference_of_means,
+            2000,
+            &mut StdRng::seed_from_u64(0),
+        );
+        assert_eq!(test.p_values[0], single.p_value);
+        assert_eq!(test.adjusted_p_values[0], single.p_value);
+
+        // A real difference, a noisy copy of it and a difference of noise
+        let noise = [
+            0.3, -1.2, 0.8, 0.1, -0.5, 1.1, -0.9, 0.4, -0.2, 0.9, -1.0, 0.6, 0.2, -0.7, 0.5, -0.3,
+        ];
+        let noisy = pooled
+            .iter()
+            .zip(noise)
+            .map(|(x, e)| x + 3. * e)
+            .collect::<Vec<_>>();
+        let statistics = |order: &[usize]| {
+            let arrange = |data: &[f64]| order.iter().map(|&i| data[i]).collect::<Vec<_>>();
+            [pooled.as_slice(), &noisy, &noise]
+                .map(|data| {
+                    let arranged = arrange(data);
+                    let (x, y) = arranged.split_at(x.len());
+                    difference_of_means(x, y)
+                })
+                .to_vec()
+        };
+        let test = |method| {
+            let mut rng = StdRng::seed_from_u64(0);
+            westfall_young(pooled.len(), statistics, method, 2000, &mut rng)
+        };
+        let (max_t, min_p) = (test(WestfallYoung::MaxT), test(WestfallYoung::MinP));
+        for test in [&max_t, &min_p] {
+            assert!(test.adjusted_p_values[0].get() < 0.05);
+            assert!(test.adjusted_p_values[2].get() > 0.5);
+            assert!(test
+                .p_values
+                .iter()
+                .zip(&test.adjusted_p_values)
+                .all(|(p, adjusted)| p <= adjusted));
+        }
+        // The widely spread noisy copy dominates the maxima, but not the minimum p-values
+        assert!(min_p.adjusted_p_values[0] < max_t.adjusted_p_values[0]);
+        let bonferroni = crate::multiple_testing::adjust(
+            &min_p.p_values,
+            crate::multiple_testing::Correction::Bonferroni,
+        );
+        assert!(min_p
+            .adjusted_p_values
+            .iter()
+            .zip(&bonferroni)
+            .all(|(adjusted, bonferroni)| adjusted <= bonferroni));
+    }
+
+    #[test]
+    fn test_median_difference_test() {
+        let mut rng = StdRng::seed_from_u64(0);
+        // Latencies with a long tail
+        let x = [
+            12., 15., 11., 14., 13., 95., 16., 12., 14., 13., 240., 15., 11., 13., 14., 12.,
+        ];
+        let y = [
+            19., 22., 18., 21., 20., 130., 23., 19., 21., 20., 310., 22., 18., 20., 21., 19.,
+        ];
+        for studentized in [false, true] {
+            let test = median_difference_test(&x, &y, 2000, studentized, &mut rng);
+            assert!(test.p_value.get() < 0.01);
+
+            let test = median_difference_test(&x, &x, 2000, studentized, &mut rng);
+            assert_eq!(test.statistic.get(), 0.);
+            assert_eq!(test.p_value.get(), 1.);
+        }
+
+        let test = median_difference_test(&x, &y, 2000, false, &mut rng);
+        assert_eq!(test.statistic.get(), -7.);
+        assert_eq!(test.distribution.len(), 2000);
+    }
+
+    #[test]
+    fn test_median_standard_error() {
+        // `c = round(5.5 - 1.96 * sqrt(2.5)) = 2`
+        let sorted = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+        let z = normal_quantile(0.975);
+        assert!((median_standard_error(&sorted) - 7. / (2. * z)).abs() < 1e-12);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel() {
+        let x = [12.1, 14.3, 13.8, 15.2, 14.9, 13.5, 14.1, 15.6];
+        let y = [10.2, 11.8, 10.9, 12.4, 11.1, 10.7, 12.0, 11.5];
+        let test = par_permutation_test(&x, &y, difference_of_means, 5000, 0);
+        assert_eq!(test.distribution.len(), 5000);
+        assert!(test.p_value.get() < 0.05);
+        let again = par_permutation_test(&x, &y, difference_of_means, 5000, 0);
+        assert_eq!(test.distribution, again.distribution);
+
+        let bootstrap = par_bootstrap(&x, mean, 5000, 0);
+        assert_eq!(bootstrap.distribution.len(), 5000);
+        let jackknife = jackknife(&x, mean);
+        assert!((bootstrap.standard_error().get() - jackknife.standard_error().get()).abs() < 0.05);
+
+        // Identical on a single thread
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap();
+        let single = pool.install(|| par_bootstrap(&x, mean, 5000, 0));
+        assert_eq!(bootstrap.distribution, single.distribution);
+    }
+
+    #[test]
+    fn test_jackknife() {
+        let data = [2.1, 3.4, 1.9, 5.6, 4.4, 3.8, 2.7, 6.1];
+        let n = data.len() as f64;
+
+        // The jackknife standard error of a mean is exactly `s / sqrt(n)`
+        let jackknife = jackknife(&data, mean);
+        let m = mean(&data);
+        let s = (data.iter().map(|x| (x - m).powi(2)).sum::<f64>() / (n - 1.)).sqrt();
+        assert!((jackknife.standard_error().get() - s / n.sqrt()).abs() < 1e-12);
+        assert!(jackknife.bias().get().abs() < 1e-12);
+
+        // The jackknife removes the bias of the plug-in variance
+        let plug_in_variance = |data: &[f64]| {
+            let m = mean(data);
+            data.iter().map(|x| (x - m).powi(2)).sum::<f64>() / data.len() as f64
+        };
+        let jackknife = super::jackknife(&data, plug_in_variance);
+        assert!((jackknife.bias_corrected_estimate().get() - s.powi(2)).abs() < 1e-12);
+    }
+}
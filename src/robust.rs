@@ -0,0 +1,278 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    correlation::{kendall_tau, tie_group_sizes},
+    descriptive::quantile_of_sorted,
+    distributions::t::StudentT,
+    special::normal_quantile,
+    Alternative, DegreesOfFreedom, TestResult,
+};
+
+/// Yuen's test of the difference of two trimmed means, robust to outliers and unequal variances.
+///
+/// Null hypothesis: the two populations have the same trimmed mean.
+///
+/// - `trim`: the proportion trimmed from each end of each sample, less than 0.5; usually 0.2
+/// - The standard error is from the winsorized variances, the degrees of freedom are from the Welch–Satterthwaite equation, and the p-value is from the exact t distribution.
+/// - If both winsorized variances are zero, there are no degrees of freedom; the p-value is `1` if the trimmed means are equal and `0` otherwise.
+pub fn yuen(x: &[f64], y: &[f64], trim: NormalizedF64) -> TestResult {
+    assert!(trim.get() < 0.5);
+    let (mean_x, d_x, h_x) = trimmed_mean_and_squared_error(x, trim);
+    let (mean_y, d_y, h_y) = trimmed_mean_and_squared_error(y, trim);
+    let standard_error_squared = d_x + d_y;
+    let difference = mean_x - mean_y;
+    if standard_error_squared == 0. {
+        let t = if difference == 0. {
+            0.
+        } else {
+            difference.signum() * f64::MAX
+        };
+        return TestResult {
+            statistic: FiniteF64::new(t).unwrap(),
+            df: None,
+            p_value: NormalizedF64::new(if t == 0. { 1. } else { 0. }).unwrap(),
+            alternative: Alternative::TwoSided,
+            effect_size: None,
+            confidence_interval: None,
+        };
+    }
+    let t = difference / standard_error_squared.sqrt();
+    let df = standard_error_squared.powi(2) / (d_x.powi(2) / (h_x - 1.) + d_y.powi(2) / (h_y - 1.));
+
+    let df = PositiveF64::new(df).unwrap();
+    let tail = StudentT { df }.cdf(FiniteF64::new(-t.abs()).unwrap()).get();
+    TestResult {
+        statistic: FiniteF64::new(t).unwrap(),
+        df: Some(DegreesOfFreedom::One(df)),
+        p_value: NormalizedF64::new((2. * tail).min(1.)).unwrap(),
+        alternative: Alternative::TwoSided,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// The trimmed mean, its squared standard error `(n - 1) s_w^2 / (h (h - 1))`, and the count `h` left after trimming
+fn trimmed_mean_and_squared_error(data: &[f64], trim: NormalizedF64) -> (f64, f64, f64) {
+    let n = data.len() as f64;
+    let trimmed = self::trim(data, trim);
+    let h = trimmed.values.len() as f64;
+    assert!(h >= 2.);
+    let winsorized_variance = winsorize(data, trim).variance.get();
+    let squared_error = (n - 1.) * winsorized_variance / (h * (h - 1.));
+    (trimmed.mean.get(), squared_error, h)
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Winsorized {
+    /// The data in the original order, clamped between the smallest and the largest values kept by [`trim`]
+    pub values: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub mean: FiniteF64,
+    /// Unbiased variance of [`Self::values`]
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub variance: PositiveF64,
+}
+
+/// Replace the `floor(fraction n)` smallest and largest values with the nearest remaining ones.
+///
+/// - `fraction`: less than 0.5
+pub fn winsorize(data: &[f64], fraction: NormalizedF64) -> Winsorized {
+    assert!(data.len() >= 2);
+    let sorted = sorted_untrimmed(data, fraction);
+    let (lower, upper) = (sorted[0], sorted[sorted.len() - 1]);
+    let values = data
+        .iter()
+        .map(|x| x.clamp(lower, upper))
+        .collect::<Vec<_>>();
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.);
+    Winsorized {
+        values,
+        mean: FiniteF64::new(mean).unwrap(),
+        variance: PositiveF64::new(variance).unwrap(),
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trimmed {
+    /// The remaining values in ascending order
+    pub values: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub mean: FiniteF64,
+}
+
+/// Drop the `floor(fraction n)` smallest and largest values.
+///
+/// - `fraction`: less than 0.5
+pub fn trim(data: &[f64], fraction: NormalizedF64) -> Trimmed {
+    let values = sorted_untrimmed(data, fraction).to_vec();
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    Trimmed {
+        values,
+        mean: FiniteF64::new(mean).unwrap(),
+    }
+}
+
+/// Ascending values left after dropping `floor(fraction n)` values from each end
+fn sorted_untrimmed(data: &[f64], fraction: NormalizedF64) -> Vec<f64> {
+    assert!(fraction.get() < 0.5);
+    assert!(!data.is_empty());
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = sorted.len();
+    let g = (fraction.get() * n as f64).floor() as usize;
+    sorted.truncate(n - g);
+    sorted.drain(..g);
+    sorted
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TheilSen {
+    /// Median of the pairwise slopes
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub slope: FiniteF64,
+    /// Median of `y - slope x`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub intercept: FiniteF64,
+    /// Slopes of every pair of points with distinct `x`, in ascending order
+    pub slopes: Vec<f64>,
+    /// Variance of Kendall's S under the null hypothesis, corrected for ties in `x`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub statistic_variance: PositiveF64,
+    /// [`kendall_tau`] of `x` and `y`
+    ///
+    /// Null hypothesis: the slope is zero.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub tau: FiniteF64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub p_value: NormalizedF64,
+}
+impl TheilSen {
+    /// Sen's distribution-free interval of the slope from order statistics of [`Self::slopes`]
+    pub fn slope_interval(&self, confidence: NormalizedF64) -> (FiniteF64, FiniteF64) {
+        let n = self.slopes.len() as f64;
+        let z = normal_quantile(1. - (1. - confidence.get()) / 2.);
+        let c = z * self.statistic_variance.get().sqrt();
+        let lower = ((n - c) / 2.).round().max(1.) as usize;
+        let upper = ((n + c) / 2.).round() as usize;
+        (
+            FiniteF64::new(self.slopes[lower - 1]).unwrap(),
+            FiniteF64::new(self.slopes[upper.min(self.slopes.len() - 1)]).unwrap(),
+        )
+    }
+}
+
+/// Theil–Sen estimator of a line, robust to outliers in `y`.
+///
+/// - The significance of the slope is from [`kendall_tau`].
+pub fn theil_sen(x: &[f64], y: &[f64]) -> TheilSen {
+    assert_eq!(x.len(), y.len());
+    assert!(x.len() >= 3);
+    let mut slopes = vec![];
+    for i in 0..x.len() {
+        for j in i + 1..x.len() {
+            if x[i] != x[j] {
+                slopes.push((y[j] - y[i]) / (x[j] - x[i]));
+            }
+        }
+    }
+    assert!(!slopes.is_empty());
+    slopes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let slope = quantile_of_sorted(&slopes, 0.5);
+
+    let mut residuals = x
+        .iter()
+        .zip(y)
+        .map(|(x, y)| y - slope * x)
+        .collect::<Vec<_>>();
+    residuals.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let intercept = quantile_of_sorted(&residuals, 0.5);
+
+    let mut sorted_x = x.to_vec();
+    sorted_x.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = x.len() as f64;
+    let x_ties = tie_group_sizes(sorted_x.iter().copied())
+        .iter()
+        .map(|&t| {
+            let t = t as f64;
+            t * (t - 1.) * (2. * t + 5.)
+        })
+        .sum::<f64>();
+    let statistic_variance = (n * (n - 1.) * (2. * n + 5.) - x_ties) / 18.;
+
+    let (tau, p_value) = kendall_tau(x, y);
+    TheilSen {
+        slope: FiniteF64::new(slope).unwrap(),
+        intercept: FiniteF64::new(intercept).unwrap(),
+        slopes,
+        statistic_variance: PositiveF64::new(statistic_variance).unwrap(),
+        tau,
+        p_value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yuen() {
+        let x = [2.1, 3.4, 1.9, 5.6, 4.4, 3.8, 2.7, 6.1, 3.3, 40.0];
+        let y = [4.5, 5.2, 6.1, 4.9, 5.8, 6.6, 5.4, 7.0, 5.1, 6.3, -20.0, 5.7];
+        let result = yuen(&x, &y, NormalizedF64::new(0.2).unwrap());
+        assert!((result.statistic.get() - -2.339_079_135).abs() < 1e-9);
+        let Some(DegreesOfFreedom::One(df)) = result.df else {
+            panic!();
+        };
+        assert!((df.get() - 6.438_162_347).abs() < 1e-9);
+        assert!((result.p_value.get() - 0.055_045_774).abs() < 1e-6);
+
+        let trim = NormalizedF64::new(0.2).unwrap();
+        let result = yuen(&[1., 2., 2., 2., 9.], &[2., 2., 2.], trim);
+        assert_eq!(result.statistic.get(), 0.);
+        assert!(result.df.is_none());
+        assert_eq!(result.p_value.get(), 1.);
+        let result = yuen(&[1., 1., 1.], &[2., 2., 2.], trim);
+        assert_eq!(result.statistic.get(), f64::MIN);
+        assert_eq!(result.p_value.get(), 0.);
+    }
+
+    #[test]
+    fn test_winsorize_and_trim() {
+        let data = [7., 1., 5., 3., 100., 4., 2., 6., 8., -50.];
+        let fraction = NormalizedF64::new(0.2).unwrap();
+
+        let trimmed = trim(&data, fraction);
+        assert_eq!(trimmed.values, [2., 3., 4., 5., 6., 7.]);
+        assert_eq!(trimmed.mean.get(), 4.5);
+
+        let winsorized = winsorize(&data, fraction);
+        assert_eq!(winsorized.values, [7., 2., 5., 3., 7., 4., 2., 6., 7., 2.]);
+        assert_eq!(winsorized.mean.get(), 4.5);
+        assert!((winsorized.variance.get() - 42.5 / 9.).abs() < 1e-12);
+
+        // Nothing to drop
+        let winsorized = winsorize(&data[..4], fraction);
+        assert_eq!(winsorized.values, data[..4]);
+    }
+
+    #[test]
+    fn test_theil_sen() {
+        let x = [1., 2., 3., 4., 5., 6., 7., 8., 9., 10.];
+        let y = [2.1, 3.9, 6.2, 8.1, 9.8, 12.2, 13.9, 16.1, 18.0, 45.0];
+        let fit = theil_sen(&x, &y);
+        assert!((fit.slope.get() - 2.014_285_714).abs() < 1e-9);
+        assert!((fit.intercept.get() - 0.014_285_714).abs() < 1e-9);
+        assert_eq!(fit.tau.get(), 1.);
+        assert!(fit.p_value.get() < 0.01);
+
+        // `C = 1.96 sqrt(125)`, so the 12th and the 34th of the 45 slopes
+        let (lower, upper) = fit.slope_interval(NormalizedF64::new(0.95).unwrap());
+        assert!((lower.get() - 1.966_666_667).abs() < 1e-9);
+        assert!((upper.get() - 2.2).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,114 @@
+//! `serde(with = "crate::serde_strict")` for fields of the `strict_num` types, which serialize as plain numbers and are range-checked on deserialization.
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+pub(crate) trait Strict: Sized {
+    type Raw: Serialize + DeserializeOwned;
+    fn to_raw(&self) -> Self::Raw;
+    /// `None` if `raw` is out of the range of `Self`
+    fn from_raw(raw: Self::Raw) -> Option<Self>;
+}
+
+macro_rules! impl_strict {
+    ($($t:ty),*) => {
+        $(
+            impl Strict for $t {
+                type Raw = f64;
+                fn to_raw(&self) -> f64 {
+                    self.get()
+                }
+                fn from_raw(raw: f64) -> Option<Self> {
+                    <$t>::new(raw)
+                }
+            }
+        )*
+    };
+}
+impl_strict!(FiniteF64, NormalizedF64, PositiveF64);
+
+impl<T: Strict> Strict for Option<T> {
+    type Raw = Option<T::Raw>;
+    fn to_raw(&self) -> Self::Raw {
+        self.as_ref().map(T::to_raw)
+    }
+    fn from_raw(raw: Self::Raw) -> Option<Self> {
+        match raw {
+            Some(raw) => T::from_raw(raw).map(Some),
+            None => Some(None),
+        }
+    }
+}
+
+impl<A: Strict, B: Strict> Strict for (A, B) {
+    type Raw = (A::Raw, B::Raw);
+    fn to_raw(&self) -> Self::Raw {
+        (self.0.to_raw(), self.1.to_raw())
+    }
+    fn from_raw(raw: Self::Raw) -> Option<Self> {
+        Some((A::from_raw(raw.0)?, B::from_raw(raw.1)?))
+    }
+}
+
+impl<T: Strict> Strict for Vec<T> {
+    type Raw = Vec<T::Raw>;
+    fn to_raw(&self) -> Self::Raw {
+        self.iter().map(T::to_raw).collect()
+    }
+    fn from_raw(raw: Self::Raw) -> Option<Self> {
+        raw.into_iter().map(T::from_raw).collect()
+    }
+}
+
+pub(crate) fn serialize<T: Strict, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value.to_raw().serialize(serializer)
+}
+
+pub(crate) fn deserialize<'de, T: Strict, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    let raw = T::Raw::deserialize(deserializer)?;
+    T::from_raw(raw).ok_or_else(|| serde::de::Error::custom("number out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use crate::{
+        numerical::{difference_of_two_means_result, NumericalSample},
+        TestResult,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let sample = NumericalSample {
+            mean: FiniteF64::new(7.18).unwrap(),
+            variance: PositiveF64::new(2.56).unwrap(),
+            count: NonZeroUsize::new(100).unwrap(),
+            population: None,
+        };
+        let json = serde_json::to_string(&sample).unwrap();
+        assert_eq!(json, r#"{"mean":7.18,"variance":2.56,"count":100}"#);
+        let other: NumericalSample = serde_json::from_str(&json).unwrap();
+        assert_eq!(other.mean, sample.mean);
+
+        let result = difference_of_two_means_result(sample, other, FiniteF64::new(0.).unwrap());
+        let json = serde_json::to_string(&result).unwrap();
+        let other: TestResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(other.p_value, result.p_value);
+        assert_eq!(other.effect_size, result.effect_size);
+        assert_eq!(other.confidence_interval, None);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let json = r#"{"count":10,"proportion":1.5}"#;
+        assert!(serde_json::from_str::<crate::categorical::CountAndProportion>(json).is_err());
+    }
+}
@@ -0,0 +1,372 @@
+use std::f64::consts::PI;
+
+const LANCZOS_G: f64 = 7.;
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+const MAX_ITERATIONS: usize = 10_000;
+const EPSILON: f64 = 1e-15;
+/// Smallest magnitude kept by the modified Lentz algorithm to avoid division by zero
+const TINY: f64 = 1e-300;
+
+/// Natural logarithm of the gamma function for `x > 0` (Lanczos approximation).
+pub fn ln_gamma(x: f64) -> f64 {
+    assert!(x > 0.);
+    if x < 0.5 {
+        // Reflection formula
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1. - x);
+    }
+    let x = x - 1.;
+    let mut sum = LANCZOS_COEFFICIENTS[0];
+    for (i, coefficient) in LANCZOS_COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += coefficient / (x + i as f64);
+    }
+    let t = x + LANCZOS_G + 0.5;
+    0.5 * (2. * PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Digamma function `ψ(x) = d ln Γ(x) / dx` for `x > 0`.
+pub fn digamma(x: f64) -> f64 {
+    assert!(x > 0.);
+    // Shift `x` up by `ψ(x) = ψ(x + 1) - 1 / x` until the asymptotic series is accurate
+    let mut x = x;
+    let mut shift = 0.;
+    while x < 10. {
+        shift -= 1. / x;
+        x += 1.;
+    }
+    let r = 1. / (x * x);
+    let series = r
+        * (1. / 12.
+            - r * (1. / 120.
+                - r * (1. / 252. - r * (1. / 240. - r * (1. / 132. - r * 691. / 32_760.)))));
+    shift + x.ln() - 0.5 / x - series
+}
+
+/// Natural logarithm of the binomial coefficient `n choose k` for `k <= n`.
+pub fn ln_binomial_coefficient(n: usize, k: usize) -> f64 {
+    assert!(k <= n);
+    let (n, k) = (n as f64, k as f64);
+    ln_gamma(n + 1.) - ln_gamma(k + 1.) - ln_gamma(n - k + 1.)
+}
+
+/// Regularized lower incomplete gamma function `P(a, x)` for `a > 0` and `x >= 0`.
+pub fn gammainc_regularized(a: f64, x: f64) -> f64 {
+    assert!(a > 0.);
+    assert!(x >= 0.);
+    if x == 0. {
+        return 0.;
+    }
+    if x < a + 1. {
+        gamma_series(a, x)
+    } else {
+        1. - gamma_continued_fraction(a, x)
+    }
+}
+
+/// Regularized upper incomplete gamma function `Q(a, x) = 1 - P(a, x)`, accurate in the far tail.
+pub fn gammainc_upper_regularized(a: f64, x: f64) -> f64 {
+    assert!(a > 0.);
+    assert!(x >= 0.);
+    if x == 0. {
+        return 1.;
+    }
+    if x < a + 1. {
+        1. - gamma_series(a, x)
+    } else {
+        gamma_continued_fraction(a, x)
+    }
+}
+
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let mut term = 1. / a;
+    let mut sum = term;
+    let mut denominator = a;
+    for _ in 0..MAX_ITERATIONS {
+        denominator += 1.;
+        term *= x / denominator;
+        sum += term;
+        if term.abs() < sum.abs() * EPSILON {
+            break;
+        }
+    }
+    sum * (-x + a * x.ln() - ln_gamma(a)).exp()
+}
+
+fn gamma_continued_fraction(a: f64, x: f64) -> f64 {
+    // Modified Lentz algorithm
+    let mut b = x + 1. - a;
+    let mut c = 1. / TINY;
+    let mut d = 1. / b;
+    let mut h = d;
+    for i in 1..MAX_ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.;
+        d = an * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + an / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1. / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.).abs() < EPSILON {
+            break;
+        }
+    }
+    (-x + a * x.ln() - ln_gamma(a)).exp() * h
+}
+
+/// Regularized incomplete beta function `I_x(a, b)` for `a, b > 0` and `0 <= x <= 1`, by a continued fraction.
+pub fn betainc_regularized(a: f64, b: f64, x: f64) -> f64 {
+    assert!(a > 0.);
+    assert!(b > 0.);
+    assert!((0. ..=1.).contains(&x));
+    if x == 0. || x == 1. {
+        return x;
+    }
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1. - x).ln();
+    let front = ln_front.exp();
+    // The continued fraction converges quickly only on this side of the mean
+    if x < (a + 1.) / (a + b + 2.) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1. - front * beta_continued_fraction(b, a, 1. - x) / b
+    }
+}
+
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    // Modified Lentz algorithm
+    let qab = a + b;
+    let qap = a + 1.;
+    let qam = a - 1.;
+    let mut c = 1.;
+    let mut d = 1. - qab * x / qap;
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1. / d;
+    let mut h = d;
+    for m in 1..MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2. * m;
+
+        // Even step
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1. + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1. + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1. / d;
+        h *= d * c;
+
+        // Odd step
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1. + aa * d;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = 1. + aa / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1. / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.).abs() < EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+/// Standard normal CDF `Φ(x)`.
+pub fn normal_cdf(x: f64) -> f64 {
+    if x.is_infinite() {
+        return if x > 0. { 1. } else { 0. };
+    }
+    // `erf(y) = P(1/2, y^2)`
+    let half_x_squared = x * x / 2.;
+    if x >= 0. {
+        1. - 0.5 * gammainc_upper_regularized(0.5, half_x_squared)
+    } else {
+        0.5 * gammainc_upper_regularized(0.5, half_x_squared)
+    }
+}
+
+/// Standard normal quantile `Φ^-1(p)` for `0 < p < 1`.
+pub fn normal_quantile(p: f64) -> f64 {
+    assert!(0. < p && p < 1.);
+    // ref: Acklam, P. J. An algorithm for computing the inverse normal cumulative distribution function.
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    };
+    let x = if p < P_LOW {
+        tail((-2. * p.ln()).sqrt())
+    } else if p <= 1. - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+    } else {
+        -tail((-2. * (1. - p).ln()).sqrt())
+    };
+
+    // One step of Halley's method brings the relative error to machine precision
+    let error = normal_cdf(x) - p;
+    let u = error * (2. * PI).sqrt() * (x * x / 2.).exp();
+    x - u / (1. + x * u / 2.)
+}
+
+/// Find `x` in `[lower, upper]` where the increasing function `f` crosses zero, by bisection.
+pub(crate) fn bisect(f: impl Fn(f64) -> f64, mut lower: f64, mut upper: f64) -> f64 {
+    for _ in 0..MAX_BISECTIONS {
+        let middle = (lower + upper) / 2.;
+        if middle <= lower || middle >= upper {
+            break;
+        }
+        if f(middle) < 0. {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+    (lower + upper) / 2.
+}
+
+const MAX_BISECTIONS: usize = 200;
+
+/// Find `x` in `[lower, upper]` minimizing the unimodal function `f`, by golden-section search.
+pub(crate) fn golden_section_min(f: impl Fn(f64) -> f64, mut lower: f64, mut upper: f64) -> f64 {
+    let inverse_golden_ratio = (5_f64.sqrt() - 1.) / 2.;
+    for _ in 0..GOLDEN_SECTIONS {
+        let left = upper - inverse_golden_ratio * (upper - lower);
+        let right = lower + inverse_golden_ratio * (upper - lower);
+        if f(left) < f(right) {
+            upper = right;
+        } else {
+            lower = left;
+        }
+    }
+    (lower + upper) / 2.
+}
+
+/// Golden-section steps, shrinking the search range by a factor below `1e-20`
+const GOLDEN_SECTIONS: usize = 100;
+
+/// Integrate `f` over `[lower, upper]` by Simpson's rule with an even number of `steps`.
+pub(crate) fn simpson(f: impl Fn(f64) -> f64, lower: f64, upper: f64, steps: usize) -> f64 {
+    assert!(steps >= 2 && steps.is_multiple_of(2));
+    let h = (upper - lower) / steps as f64;
+    let sum = (0..=steps)
+        .map(|i| {
+            let weight = if i == 0 || i == steps {
+                1.
+            } else if i % 2 == 1 {
+                4.
+            } else {
+                2.
+            };
+            weight * f(lower + i as f64 * h)
+        })
+        .sum::<f64>();
+    sum * h / 3.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ln_gamma() {
+        assert!((ln_gamma(0.5) - PI.sqrt().ln()).abs() < 1e-14);
+        assert!((ln_gamma(10.) - 362_880_f64.ln()).abs() < 1e-12);
+        assert!((ln_gamma(0.1) - 2.252_712_651_734_206).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_digamma() {
+        assert!((digamma(1.) - -0.577_215_664_901_532_9).abs() < 1e-14);
+        assert!((digamma(0.3) - -3.502_524_222_200_133).abs() < 1e-13);
+        assert!((digamma(7.5) - 1.946_757_484_246_087).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_gammainc_regularized() {
+        // `P(1, x) = 1 - e^-x`
+        assert!((gammainc_regularized(1., 2.) - (1. - (-2_f64).exp())).abs() < 1e-14);
+        // `P(1/2, 1) = erf(1)`
+        assert!((gammainc_regularized(0.5, 1.) - 0.842_700_792_949_714_9).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_gammainc_upper_regularized() {
+        // `Q(1, x) = e^-x`
+        assert!((gammainc_upper_regularized(1., 2.) - (-2_f64).exp()).abs() < 1e-14);
+        // `Q(1/2, 1) = erfc(1)`
+        assert!((gammainc_upper_regularized(0.5, 1.) - 0.157_299_207_050_285_1).abs() < 1e-14);
+        assert!((gammainc_upper_regularized(3., 20.) - 4.555_149_505_589_213e-7).abs() < 1e-18);
+    }
+
+    #[test]
+    fn test_betainc_regularized() {
+        assert!((betainc_regularized(2., 3., 0.5) - 11. / 16.).abs() < 1e-14);
+        assert!((betainc_regularized(1., 1., 0.3) - 0.3).abs() < 1e-14);
+        assert!((betainc_regularized(0.5, 0.5, 0.5) - 0.5).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_normal() {
+        assert!((normal_cdf(1.959_963_984_540_054) - 0.975).abs() < 1e-15);
+        assert!((normal_cdf(-5.) - 2.866_515_718_791_946e-7).abs() < 1e-18);
+        assert!((normal_quantile(0.975) - 1.959_963_984_540_054).abs() < 1e-13);
+        assert!((normal_quantile(1e-10) - -6.361_340_902_404_056).abs() < 1e-10);
+    }
+}
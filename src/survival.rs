@@ -0,0 +1,185 @@
+use strict_num::{FiniteF64, NormalizedF64, PositiveF64};
+
+use crate::{
+    special::{gammainc_upper_regularized, normal_quantile},
+    Alternative, DegreesOfFreedom, TestResult,
+};
+
+/// Kaplan–Meier estimate right after one event time
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SurvivalStep {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub time: PositiveF64,
+    pub at_risk: usize,
+    pub events: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub survival: NormalizedF64,
+    /// Greenwood's standard error of `survival`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub standard_error: PositiveF64,
+    /// Normal interval of `ln(survival)`, clamped to `[0, 1]`
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_strict"))]
+    pub confidence_interval: (NormalizedF64, NormalizedF64),
+}
+
+/// Kaplan–Meier survival curve of right-censored durations.
+///
+/// - `events[i]`: whether `durations[i]` ends in the event rather than in censoring
+/// - One step per distinct event time, in ascending order
+pub fn kaplan_meier(
+    durations: &[f64],
+    events: &[bool],
+    confidence: NormalizedF64,
+) -> Vec<SurvivalStep> {
+    let z = normal_quantile(0.5 + confidence.get() / 2.);
+    let mut survival = 1.;
+    let mut greenwood = 0.;
+    event_times(durations, events)
+        .into_iter()
+        .map(|time| {
+            let (at_risk, died) = risk_set(durations, events, time);
+            survival *= 1. - died as f64 / at_risk as f64;
+            // The sum is undefined once no one survives, where the curve stays at zero
+            if died < at_risk {
+                greenwood += died as f64 / (at_risk * (at_risk - died)) as f64;
+            }
+            let margin = z * greenwood.sqrt();
+            let bound = |x: f64| NormalizedF64::new(x.clamp(0., 1.)).unwrap();
+            SurvivalStep {
+                time: PositiveF64::new(time).unwrap(),
+                at_risk,
+                events: died,
+                survival: NormalizedF64::new(survival).unwrap(),
+                standard_error: PositiveF64::new(survival * greenwood.sqrt()).unwrap(),
+                confidence_interval: (
+                    bound(survival * (-margin).exp()),
+                    bound(survival * margin.exp()),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Log-rank test of two groups of right-censored durations.
+///
+/// Null hypothesis: both groups have the same hazard at all times.
+///
+/// - The statistic is chi-square with 1 degree of freedom.
+/// - Without any event that could have fallen in either group, the statistic is 0 and the p-value is 1.
+pub fn log_rank(
+    durations_1: &[f64],
+    events_1: &[bool],
+    durations_2: &[f64],
+    events_2: &[bool],
+) -> TestResult {
+    assert_eq!(durations_1.len(), events_1.len());
+    assert_eq!(durations_2.len(), events_2.len());
+    let durations = [durations_1, durations_2].concat();
+    let events = [events_1, events_2].concat();
+
+    let mut observed = 0.;
+    let mut expected = 0.;
+    let mut variance = 0.;
+    for time in event_times(&durations, &events) {
+        let (at_risk, died) = risk_set(&durations, &events, time);
+        let (at_risk_1, died_1) = risk_set(durations_1, events_1, time);
+        let (n, n_1, d) = (at_risk as f64, at_risk_1 as f64, died as f64);
+        observed += died_1 as f64;
+        expected += d * n_1 / n;
+        if at_risk > 1 {
+            variance += n_1 * (n - n_1) * d * (n - d) / (n.powi(2) * (n - 1.));
+        }
+    }
+    // No variance leaves the observed events no room to differ from the expected ones
+    let chi_square = if variance == 0. {
+        0.
+    } else {
+        (observed - expected).powi(2) / variance
+    };
+    let p_value = gammainc_upper_regularized(0.5, chi_square / 2.);
+    TestResult {
+        statistic: FiniteF64::new(chi_square).unwrap(),
+        df: Some(DegreesOfFreedom::one(1)),
+        p_value: NormalizedF64::new(p_value).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+/// Distinct times of observed events, ascending
+fn event_times(durations: &[f64], events: &[bool]) -> Vec<f64> {
+    assert_eq!(durations.len(), events.len());
+    assert!(durations.iter().all(|&d| d.is_finite() && d >= 0.));
+    let mut times = durations
+        .iter()
+        .zip(events)
+        .filter(|(_, &event)| event)
+        .map(|(&d, _)| d)
+        .collect::<Vec<_>>();
+    times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    times.dedup();
+    times
+}
+
+/// Count still at risk at `time` and count of events at `time`
+fn risk_set(durations: &[f64], events: &[bool], time: f64) -> (usize, usize) {
+    let at_risk = durations.iter().filter(|&&d| d >= time).count();
+    let died = durations
+        .iter()
+        .zip(events)
+        .filter(|(&d, &event)| event && d == time)
+        .count();
+    (at_risk, died)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Acute myelogenous leukemia remission times of the maintained and the nonmaintained groups
+    fn aml() -> ([f64; 11], [bool; 11], [f64; 12], [bool; 12]) {
+        (
+            [9., 13., 13., 18., 23., 28., 31., 34., 45., 48., 161.],
+            [
+                true, true, false, true, true, false, true, true, false, true, false,
+            ],
+            [5., 5., 8., 8., 12., 16., 23., 27., 30., 33., 43., 45.],
+            [
+                true, true, true, true, true, false, true, true, true, true, true, true,
+            ],
+        )
+    }
+
+    #[test]
+    fn test_kaplan_meier() {
+        let (durations, events, _, _) = aml();
+        let curve = kaplan_meier(&durations, &events, NormalizedF64::new(0.95).unwrap());
+        assert_eq!(curve.len(), 7);
+        let step = curve[1];
+        assert_eq!(step.time.get(), 13.);
+        assert_eq!((step.at_risk, step.events), (10, 1));
+        assert!((step.survival.get() - 0.818_181_818).abs() < 1e-9);
+        assert!((step.standard_error.get() - 0.116_291_300).abs() < 1e-9);
+        assert!((step.confidence_interval.0.get() - 0.619_248_987).abs() < 1e-8);
+        assert_eq!(step.confidence_interval.1.get(), 1.);
+        let step = curve[6];
+        assert!((step.survival.get() - 0.184_090_909).abs() < 1e-9);
+        assert!((step.standard_error.get() - 0.153_492_746).abs() < 1e-9);
+        assert!((step.confidence_interval.0.get() - 0.035_917_898).abs() < 1e-8);
+        assert!((step.confidence_interval.1.get() - 0.943_525_769).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_log_rank() {
+        let (durations_1, events_1, durations_2, events_2) = aml();
+        let test = log_rank(&durations_1, &events_1, &durations_2, &events_2);
+        assert!((test.statistic.get() - 3.396_388_699).abs() < 1e-8);
+        assert!((test.p_value.get() - 0.065_339_322).abs() < 1e-8);
+
+        let test = log_rank(&[1., 2.], &[false, false], &[3.], &[false]);
+        assert_eq!(test.statistic.get(), 0.);
+        assert_eq!(test.p_value.get(), 1.);
+    }
+}
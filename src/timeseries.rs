@@ -0,0 +1,68 @@
+use strict_num::{FiniteF64, NormalizedF64};
+
+use crate::{special::gammainc_upper_regularized, Alternative, DegreesOfFreedom, TestResult};
+
+/// Sample autocorrelation of `series` at `lag`, normalized by the lag-0 autocovariance
+pub fn autocorrelation(series: &[f64], lag: usize) -> FiniteF64 {
+    assert!(lag < series.len());
+    let n = series.len() as f64;
+    let mean = series.iter().sum::<f64>() / n;
+    let variance = series.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+    assert!(variance > 0.);
+    let covariance = series
+        .iter()
+        .zip(&series[lag..])
+        .map(|(x, y)| (x - mean) * (y - mean))
+        .sum::<f64>();
+    FiniteF64::new(covariance / variance).unwrap()
+}
+
+/// Ljung–Box test of the autocorrelations up to `lags`.
+///
+/// Null hypothesis: the series is independently distributed.
+///
+/// - The statistic `Q = n (n + 2) sum_k r_k^2 / (n - k)` is chi-square with `lags` degrees of freedom.
+/// - On the residuals of a fitted model, subtract the count of fitted parameters from the degrees of freedom.
+pub fn ljung_box(series: &[f64], lags: usize) -> TestResult {
+    assert!(lags >= 1);
+    assert!(lags < series.len());
+    let n = series.len() as f64;
+    let q = n
+        * (n + 2.)
+        * (1..=lags)
+            .map(|k| autocorrelation(series, k).get().powi(2) / (n - k as f64))
+            .sum::<f64>();
+    let p_value = gammainc_upper_regularized(lags as f64 / 2., q / 2.);
+    TestResult {
+        statistic: FiniteF64::new(q).unwrap(),
+        df: Some(DegreesOfFreedom::one(lags)),
+        p_value: NormalizedF64::new(p_value).unwrap(),
+        alternative: Alternative::Greater,
+        effect_size: None,
+        confidence_interval: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERIES: [f64; 20] = [
+        1.2, 0.8, 1.5, 1.9, 1.4, 1.1, 0.7, 0.9, 1.6, 2.1, 1.8, 1.3, 0.6, 0.8, 1.2, 1.7, 2.0, 1.5,
+        1.0, 0.9,
+    ];
+
+    #[test]
+    fn test_autocorrelation() {
+        assert_eq!(autocorrelation(&SERIES, 0).get(), 1.);
+        assert!((autocorrelation(&SERIES, 1).get() - 0.469_230_769).abs() < 1e-9);
+        assert!((autocorrelation(&SERIES, 3).get() - -0.807_692_308).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_ljung_box() {
+        let test = ljung_box(&SERIES, 5);
+        assert!((test.statistic.get() - 34.415_268_366).abs() < 1e-8);
+        assert!((test.p_value.get() - 1.968_095_827e-6).abs() < 1e-14);
+    }
+}